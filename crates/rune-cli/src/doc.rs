@@ -0,0 +1,951 @@
+//! The `rune doc` command, which generates a static HTML site documenting
+//! scripts and the native modules installed in the context.
+
+use crate::{DocFlags, ExitCode};
+use anyhow::{Context as _, Result};
+use rune::termcolor::StandardStream;
+use rune::EmitDiagnostics as _;
+use runestick::debug::DebugArgs;
+use runestick::{
+    Call, CompileMeta, CompileMetaKind, ComponentRef, ContextSignature, Item, SourceId, TypeInfo,
+    Unit, UnitFn,
+};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write as _;
+use std::fs;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// The stylesheet shared by all pages.
+const STYLE: &str = r#"body { font-family: sans-serif; max-width: 960px; margin: 0 auto; padding: 1em; color: #222; }
+a { color: #3465a4; text-decoration: none; }
+a:hover { text-decoration: underline; }
+nav { margin-bottom: 1em; }
+code, pre { font-family: monospace; background: #f4f4f4; }
+pre { padding: 0.5em; overflow-x: auto; }
+.item { margin: 1em 0; }
+.signature { font-family: monospace; font-weight: bold; }
+.members, .functions { margin-left: 2em; }
+.kind { color: #888; }
+"#;
+
+/// A compile visitor which collects the items and doc comments of a script.
+#[derive(Default)]
+pub(crate) struct DocVisitor {
+    metas: RefCell<Vec<CompileMeta>>,
+    docs: RefCell<HashMap<Item, Vec<String>>>,
+    field_docs: RefCell<HashMap<(Item, String), Vec<String>>>,
+}
+
+impl rune::CompileVisitor for DocVisitor {
+    fn register_meta(&self, meta: &CompileMeta) {
+        self.metas.borrow_mut().push(meta.clone());
+    }
+
+    fn visit_doc_comment(&self, _: SourceId, item: &Item, docs: &[String]) {
+        self.docs
+            .borrow_mut()
+            .entry(item.clone())
+            .or_default()
+            .extend(docs.iter().cloned());
+    }
+
+    fn visit_field_doc_comment(&self, _: SourceId, item: &Item, field: &str, docs: &[String]) {
+        self.field_docs
+            .borrow_mut()
+            .entry((item.clone(), field.to_owned()))
+            .or_default()
+            .extend(docs.iter().cloned());
+    }
+}
+
+/// Generate documentation for all the given scripts, and for the native
+/// modules in the context.
+pub(crate) fn run(
+    out: &mut StandardStream,
+    flags: &DocFlags,
+    options: &rune::Options,
+    paths: Vec<PathBuf>,
+) -> Result<ExitCode> {
+    let context = flags.shared.context(None)?;
+    let mut site = Site::default();
+
+    for path in paths {
+        writeln!(out, "Documenting: {}", path.display())?;
+
+        let source = runestick::Source::from_path(&path)
+            .with_context(|| format!("reading file: {}", path.display()))?;
+
+        let mut sources = rune::Sources::new();
+        sources.insert(source);
+
+        let mut diagnostics = if flags.shared.warnings {
+            rune::Diagnostics::new()
+        } else {
+            rune::Diagnostics::without_warnings()
+        };
+
+        let visitor = Rc::new(DocVisitor::default());
+        let source_loader = Rc::new(rune::FileSourceLoader::new());
+
+        let result = rune::load_sources_with_visitor(
+            &context,
+            options,
+            &mut sources,
+            &mut diagnostics,
+            visitor.clone(),
+            source_loader,
+        );
+
+        diagnostics.emit_diagnostics(out, &sources)?;
+
+        let unit = match result {
+            Ok(unit) => unit,
+            Err(..) => return Ok(ExitCode::Failure),
+        };
+
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_else(|| String::from("script"));
+
+        site.add_script(&name, &visitor, &unit, flags.private);
+    }
+
+    if !flags.no_native {
+        site.add_context(&context);
+    }
+
+    site.write(&flags.output)?;
+    writeln!(
+        out,
+        "Generated documentation in: {}",
+        flags.output.join("index.html").display()
+    )?;
+
+    Ok(ExitCode::Success)
+}
+
+/// The path of a module or item, as its components.
+type ItemPath = Vec<String>;
+
+/// A documented item, like a function or a type.
+#[derive(Default)]
+struct Entry {
+    /// The kind of the item, like `fn` or `struct`.
+    kind: &'static str,
+    /// The name of the item.
+    name: String,
+    /// The signature of the item which follows its name, as HTML.
+    signature: String,
+    /// Lines of documentation.
+    docs: Vec<String>,
+    /// Fields of a struct or variants of an enum.
+    members: Vec<Entry>,
+    /// Associated and instance functions of a type.
+    functions: BTreeMap<String, Entry>,
+}
+
+/// A documented module.
+#[derive(Default)]
+struct Module {
+    /// Lines of documentation.
+    docs: Vec<String>,
+    /// Items declared in the module.
+    items: BTreeMap<String, Entry>,
+    /// Modules declared in the module.
+    modules: BTreeSet<ItemPath>,
+}
+
+/// All documented modules.
+#[derive(Default)]
+struct Site {
+    modules: BTreeMap<ItemPath, Module>,
+    /// Paths of known types, so that items can be placed inside of them.
+    types: BTreeSet<ItemPath>,
+    /// Links to items, keyed by their full path and by the name of native
+    /// types.
+    links: HashMap<String, String>,
+}
+
+impl Site {
+    /// Add the items collected when compiling a script, which are placed in a
+    /// module with the given name.
+    fn add_script(&mut self, name: &str, visitor: &DocVisitor, unit: &Unit, private: bool) {
+        let docs = visitor.docs.borrow();
+        let field_docs = visitor.field_docs.borrow();
+
+        let signatures = unit
+            .debug_info()
+            .map(|debug| {
+                debug
+                    .functions
+                    .values()
+                    .map(|signature| (signature.path.clone(), &signature.args))
+                    .collect::<HashMap<_, _>>()
+            })
+            .unwrap_or_default();
+
+        let root = vec![name.to_owned()];
+        self.module_mut(&root).docs = docs.get(&Item::new()).cloned().unwrap_or_default();
+
+        let mut metas = visitor
+            .metas
+            .borrow()
+            .iter()
+            .filter(|meta| private || meta.item.is_public())
+            .cloned()
+            .collect::<Vec<_>>();
+
+        // NB: types are added first, so that their functions and variants can
+        // be placed inside of them.
+        metas.sort_by_cached_key(|meta| {
+            let order = match &meta.kind {
+                CompileMetaKind::Enum { .. }
+                | CompileMetaKind::UnitStruct { .. }
+                | CompileMetaKind::TupleStruct { .. }
+                | CompileMetaKind::Struct { .. } => 0,
+                _ => 1,
+            };
+
+            (order, item_path(&meta.item.item))
+        });
+
+        for meta in &metas {
+            let item = &meta.item.item;
+            let path = script_path(name, item);
+            let item_docs = docs.get(item).cloned().unwrap_or_default();
+
+            let fields = |fields: &mut dyn Iterator<Item = &str>| {
+                let mut fields = fields.map(str::to_owned).collect::<Vec<_>>();
+                fields.sort();
+
+                fields
+                    .into_iter()
+                    .map(|field| Entry {
+                        kind: "field",
+                        docs: field_docs
+                            .get(&(item.clone(), field.clone()))
+                            .cloned()
+                            .unwrap_or_default(),
+                        name: field,
+                        ..Entry::default()
+                    })
+                    .collect::<Vec<_>>()
+            };
+
+            match &meta.kind {
+                CompileMetaKind::Function { is_test, type_hash } => {
+                    if *is_test {
+                        continue;
+                    }
+
+                    let kind = match unit.lookup(*type_hash) {
+                        Some(UnitFn::Offset {
+                            call: Call::Async, ..
+                        })
+                        | Some(UnitFn::Offset {
+                            call: Call::Stream, ..
+                        }) => "async fn",
+                        _ => "fn",
+                    };
+
+                    let args = match signatures.get(item) {
+                        Some(DebugArgs::Named(args)) => args.join(", "),
+                        Some(DebugArgs::TupleArgs(n)) => numbered_args(*n),
+                        Some(DebugArgs::EmptyArgs) | None => String::new(),
+                    };
+
+                    let entry = Entry {
+                        kind,
+                        name: last(&path).to_owned(),
+                        signature: format!("({})", escape(&args)),
+                        docs: item_docs,
+                        ..Entry::default()
+                    };
+
+                    self.insert(path, entry);
+                }
+                CompileMetaKind::Const { .. } => {
+                    let entry = Entry {
+                        kind: "const",
+                        name: last(&path).to_owned(),
+                        docs: item_docs,
+                        ..Entry::default()
+                    };
+
+                    self.insert(path, entry);
+                }
+                CompileMetaKind::Enum { .. } => {
+                    self.insert_type(path, "enum", String::new(), item_docs, Vec::new());
+                }
+                CompileMetaKind::UnitStruct { .. } => {
+                    self.insert_type(path, "struct", String::new(), item_docs, Vec::new());
+                }
+                CompileMetaKind::TupleStruct { tuple, .. } => {
+                    let args = format!("({})", numbered_args(tuple.args));
+                    self.insert_type(path, "struct", args, item_docs, Vec::new());
+                }
+                CompileMetaKind::Struct { object, .. } => {
+                    let members = fields(&mut object.fields.iter().map(|f| f.as_ref()));
+                    self.insert_type(path, "struct", String::new(), item_docs, members);
+                }
+                CompileMetaKind::UnitVariant { .. } => {
+                    self.insert_variant(path, String::new(), item_docs, Vec::new());
+                }
+                CompileMetaKind::TupleVariant { tuple, .. } => {
+                    let args = format!("({})", numbered_args(tuple.args));
+                    self.insert_variant(path, args, item_docs, Vec::new());
+                }
+                CompileMetaKind::StructVariant { object, .. } => {
+                    let members = fields(&mut object.fields.iter().map(|f| f.as_ref()));
+                    self.insert_variant(path, String::new(), item_docs, members);
+                }
+                _ => (),
+            }
+        }
+
+        // Modules have no meta, so their docs are associated with the modules
+        // which have documented items in them.
+        for (item, docs) in docs.iter() {
+            if let Some(module) = self.modules.get_mut(&script_path(name, item)) {
+                if module.docs.is_empty() {
+                    module.docs = docs.clone();
+                }
+            }
+        }
+    }
+
+    /// Add the native types and functions of the given context.
+    fn add_context(&mut self, context: &runestick::Context) {
+        let mut types = context
+            .iter_types()
+            .map(|(_, ty)| (item_path(&ty.item), ty))
+            .filter(|(path, _)| !path.is_empty())
+            .collect::<Vec<_>>();
+
+        // NB: enums are added before their variants.
+        types.sort_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+
+        let mut variants = BTreeSet::new();
+
+        for (path, ty) in types {
+            if self.types.contains(&path[..path.len() - 1]) {
+                variants.insert(path.clone());
+                self.insert_variant(path, String::new(), Vec::new(), Vec::new());
+                continue;
+            }
+
+            self.links
+                .insert(ty.type_info.to_string(), page_link(&path, "type"));
+            self.insert_type(path, "type", String::new(), Vec::new(), Vec::new());
+        }
+
+        for (_, signature) in context.iter_functions() {
+            let path = match signature {
+                ContextSignature::Function { item, .. } => item_path(item),
+                ContextSignature::Instance { item, name, .. } => {
+                    let mut path = item_path(item);
+                    path.push(name.clone());
+                    path
+                }
+            };
+
+            if path.len() < 2 || variants.contains(&path) {
+                continue;
+            }
+
+            let entry = Entry {
+                kind: "fn",
+                name: last(&path).to_owned(),
+                signature: self.native_signature(signature),
+                docs: signature.meta().docs.clone(),
+                ..Entry::default()
+            };
+
+            self.insert(path, entry);
+        }
+    }
+
+    /// Render the signature of a native function.
+    fn native_signature(&self, signature: &ContextSignature) -> String {
+        let mut args = Vec::new();
+
+        if let ContextSignature::Instance { self_type_info, .. } = signature {
+            args.push(format!("self: {}", self.type_link(self_type_info)));
+        }
+
+        for (name, type_info) in signature.args().unwrap_or_default() {
+            match type_info {
+                Some(type_info) => {
+                    args.push(format!("{}: {}", escape(&name), self.type_link(type_info)))
+                }
+                None => args.push(escape(&name)),
+            }
+        }
+
+        let mut out = format!("({})", args.join(", "));
+
+        match signature.return_type() {
+            Some(TypeInfo::StaticType(ty)) if *ty == runestick::UNIT_TYPE => (),
+            Some(return_type) => {
+                write!(out, " -&gt; {}", self.type_link(return_type)).unwrap();
+            }
+            None => (),
+        }
+
+        out
+    }
+
+    /// Render a link to the given type if it's documented.
+    fn type_link(&self, type_info: &TypeInfo) -> String {
+        let name = type_info.to_string();
+
+        match self.links.get(&name) {
+            Some(href) => format!("<a href=\"{}\">{}</a>", href, escape(&name)),
+            None => escape(&name),
+        }
+    }
+
+    /// Access the module with the given path, creating it and all of its
+    /// parents if necessary.
+    fn module_mut(&mut self, path: &[String]) -> &mut Module {
+        for n in 1..path.len() {
+            let parent = path[..n].to_vec();
+            let child = path[..=n].to_vec();
+            self.modules
+                .entry(parent)
+                .or_default()
+                .modules
+                .insert(child);
+        }
+
+        self.modules.entry(path.to_vec()).or_default()
+    }
+
+    /// Insert a type into its module.
+    fn insert_type(
+        &mut self,
+        path: ItemPath,
+        kind: &'static str,
+        signature: String,
+        docs: Vec<String>,
+        members: Vec<Entry>,
+    ) {
+        let name = last(&path).to_owned();
+        self.links.insert(path.join("::"), page_link(&path, "type"));
+        self.types.insert(path.clone());
+
+        let module = self.module_mut(&path[..path.len() - 1]);
+        let entry = module.items.entry(name.clone()).or_default();
+
+        entry.kind = kind;
+        entry.name = name;
+        entry.signature = signature;
+        entry.docs.extend(docs);
+        entry.members.extend(members);
+    }
+
+    /// Insert a variant into its enum.
+    fn insert_variant(
+        &mut self,
+        path: ItemPath,
+        signature: String,
+        docs: Vec<String>,
+        members: Vec<Entry>,
+    ) {
+        let entry = Entry {
+            kind: "variant",
+            name: last(&path).to_owned(),
+            signature,
+            docs,
+            members,
+            ..Entry::default()
+        };
+
+        let parent = &path[..path.len() - 1];
+
+        if let Some(ty) = self.type_mut(parent) {
+            ty.members.push(entry);
+        }
+    }
+
+    /// Insert a function or constant, either into the type it's associated
+    /// with or into its module.
+    fn insert(&mut self, path: ItemPath, entry: Entry) {
+        let name = last(&path).to_owned();
+        let parent = &path[..path.len() - 1];
+
+        if self.types.contains(parent) {
+            self.links.insert(
+                path.join("::"),
+                format!(
+                    "{}#fn.{}.{}",
+                    page_name(&parent[..parent.len() - 1]),
+                    last(parent),
+                    name
+                ),
+            );
+
+            if let Some(ty) = self.type_mut(parent) {
+                ty.functions.insert(name, entry);
+            }
+
+            return;
+        }
+
+        self.links
+            .insert(path.join("::"), page_link(&path, entry.kind));
+        self.module_mut(parent).items.insert(name, entry);
+    }
+
+    /// Access the type with the given path.
+    fn type_mut(&mut self, path: &[String]) -> Option<&mut Entry> {
+        let (name, module) = path.split_last()?;
+        self.modules.get_mut(module)?.items.get_mut(name)
+    }
+
+    /// Write the site to the given directory.
+    fn write(&self, output: &Path) -> Result<()> {
+        fs::create_dir_all(output)
+            .with_context(|| format!("creating directory: {}", output.display()))?;
+
+        write_file(&output.join("style.css"), STYLE)?;
+
+        let mut index = String::new();
+        writeln!(index, "<h1>Documentation</h1>")?;
+        writeln!(index, "<ul>")?;
+
+        for (path, module) in self.modules.iter().filter(|(path, _)| path.len() == 1) {
+            writeln!(
+                index,
+                "<li><a href=\"{}\">{}</a> {}</li>",
+                page_name(path),
+                escape(&path.join("::")),
+                self.summary(path, &module.docs)
+            )?;
+        }
+
+        writeln!(index, "</ul>")?;
+        write_file(&output.join("index.html"), &page("Documentation", &index))?;
+
+        for (path, module) in &self.modules {
+            let body = self.module_page(path, module)?;
+            let title = path.join("::");
+            write_file(&output.join(page_name(path)), &page(&title, &body))?;
+        }
+
+        Ok(())
+    }
+
+    /// Render the body of the page of a module.
+    fn module_page(&self, path: &[String], module: &Module) -> Result<String> {
+        let mut out = String::new();
+
+        write!(out, "<nav><a href=\"index.html\">index</a>")?;
+
+        for n in 1..=path.len() {
+            write!(
+                out,
+                " :: <a href=\"{}\">{}</a>",
+                page_name(&path[..n]),
+                escape(&path[n - 1])
+            )?;
+        }
+
+        writeln!(out, "</nav>")?;
+        writeln!(out, "<h1>Module {}</h1>", escape(&path.join("::")))?;
+        out.push_str(&self.render_docs(path, &module.docs));
+
+        if !module.modules.is_empty() {
+            writeln!(out, "<h2>Modules</h2>")?;
+            writeln!(out, "<ul>")?;
+
+            for child in &module.modules {
+                let docs = self
+                    .modules
+                    .get(child)
+                    .map(|m| &m.docs[..])
+                    .unwrap_or_default();
+
+                writeln!(
+                    out,
+                    "<li><a href=\"{}\">{}</a> {}</li>",
+                    page_name(child),
+                    escape(last(child)),
+                    self.summary(child, docs)
+                )?;
+            }
+
+            writeln!(out, "</ul>")?;
+        }
+
+        let sections = [
+            ("Types", &["struct", "enum", "type"][..]),
+            ("Functions", &["fn", "async fn"][..]),
+            ("Constants", &["const"][..]),
+        ];
+
+        for (title, kinds) in sections.iter() {
+            let mut entries = module
+                .items
+                .iter()
+                .filter(|(_, entry)| kinds.contains(&entry.kind))
+                .peekable();
+
+            if entries.peek().is_none() {
+                continue;
+            }
+
+            writeln!(out, "<h2>{}</h2>", title)?;
+
+            for (name, entry) in entries {
+                let anchor = anchor(entry.kind, name);
+                self.render_entry(&mut out, path, &anchor, entry)?;
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Render a single entry, and everything inside of it.
+    fn render_entry(
+        &self,
+        out: &mut String,
+        module: &[String],
+        anchor: &str,
+        entry: &Entry,
+    ) -> Result<()> {
+        writeln!(out, "<div class=\"item\" id=\"{}\">", anchor)?;
+        writeln!(
+            out,
+            "<div class=\"signature\"><span class=\"kind\">{}</span> <a href=\"#{}\">{}</a>{}</div>",
+            entry.kind,
+            anchor,
+            escape(&entry.name),
+            entry.signature
+        )?;
+        out.push_str(&self.render_docs(module, &entry.docs));
+
+        if !entry.members.is_empty() {
+            writeln!(out, "<div class=\"members\">")?;
+
+            for member in &entry.members {
+                writeln!(
+                    out,
+                    "<div class=\"item\"><div class=\"signature\"><span class=\"kind\">{}</span> {}{}</div>",
+                    member.kind,
+                    escape(&member.name),
+                    member.signature
+                )?;
+                out.push_str(&self.render_docs(module, &member.docs));
+
+                for field in &member.members {
+                    writeln!(
+                        out,
+                        "<div class=\"members\"><div class=\"signature\"><span class=\"kind\">{}</span> {}{}</div>",
+                        field.kind,
+                        escape(&field.name),
+                        field.signature
+                    )?;
+                    out.push_str(&self.render_docs(module, &field.docs));
+                    writeln!(out, "</div>")?;
+                }
+
+                writeln!(out, "</div>")?;
+            }
+
+            writeln!(out, "</div>")?;
+        }
+
+        if !entry.functions.is_empty() {
+            writeln!(out, "<div class=\"functions\">")?;
+
+            for (name, function) in &entry.functions {
+                let anchor = format!("fn.{}.{}", entry.name, name);
+                self.render_entry(out, module, &anchor, function)?;
+            }
+
+            writeln!(out, "</div>")?;
+        }
+
+        writeln!(out, "</div>")?;
+        Ok(())
+    }
+
+    /// Render the first paragraph of the given docs, used as a summary.
+    fn summary(&self, module: &[String], docs: &[String]) -> String {
+        let first = docs
+            .iter()
+            .map(|line| strip_doc_line(line))
+            .take_while(|line| !line.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        self.render_inline(module, &first)
+    }
+
+    /// Render lines of documentation as HTML.
+    ///
+    /// Paragraphs are separated by empty lines, and fenced code blocks are
+    /// rendered as-is.
+    fn render_docs(&self, module: &[String], docs: &[String]) -> String {
+        let mut out = String::new();
+        let mut paragraph = Vec::new();
+        let mut code = None::<Vec<&str>>;
+
+        for line in docs.iter().map(|line| strip_doc_line(line)) {
+            if line.trim_start().starts_with("```") {
+                match code.take() {
+                    Some(lines) => {
+                        let _ =
+                            writeln!(out, "<pre><code>{}</code></pre>", escape(&lines.join("\n")));
+                    }
+                    None => {
+                        self.flush_paragraph(&mut out, module, &mut paragraph);
+                        code = Some(Vec::new());
+                    }
+                }
+
+                continue;
+            }
+
+            match &mut code {
+                Some(lines) => lines.push(line),
+                None if line.trim().is_empty() => {
+                    self.flush_paragraph(&mut out, module, &mut paragraph);
+                }
+                None => paragraph.push(line.trim()),
+            }
+        }
+
+        if let Some(lines) = code {
+            let _ = writeln!(out, "<pre><code>{}</code></pre>", escape(&lines.join("\n")));
+        }
+
+        self.flush_paragraph(&mut out, module, &mut paragraph);
+        out
+    }
+
+    /// Write out the collected paragraph, if any.
+    fn flush_paragraph(&self, out: &mut String, module: &[String], paragraph: &mut Vec<&str>) {
+        if paragraph.is_empty() {
+            return;
+        }
+
+        let text = paragraph.join(" ");
+        paragraph.clear();
+        let _ = writeln!(out, "<p>{}</p>", self.render_inline(module, &text));
+    }
+
+    /// Render inline markup, which is `code` spans and links to items written
+    /// like `[std::string::String]`.
+    fn render_inline(&self, module: &[String], text: &str) -> String {
+        let mut out = String::new();
+        let mut rest = text;
+
+        while let Some(n) = rest.find(['`', '[']) {
+            out.push_str(&escape(&rest[..n]));
+            let c = &rest[n..n + 1];
+            let tail = &rest[n + 1..];
+            let close = if c == "`" { '`' } else { ']' };
+
+            let end = match tail.find(close) {
+                Some(end) => end,
+                None => {
+                    out.push_str(&escape(&rest[n..]));
+                    return out;
+                }
+            };
+
+            let inner = &tail[..end];
+            rest = &tail[end + 1..];
+
+            if c == "`" {
+                write!(out, "<code>{}</code>", escape(inner)).unwrap();
+                continue;
+            }
+
+            let reference = inner.trim_matches('`');
+
+            match self.resolve(module, reference) {
+                Some(href) => write!(
+                    out,
+                    "<a href=\"{}\"><code>{}</code></a>",
+                    href,
+                    escape(reference)
+                )
+                .unwrap(),
+                None => write!(out, "[{}]", escape(inner)).unwrap(),
+            }
+        }
+
+        out.push_str(&escape(rest));
+        out
+    }
+
+    /// Resolve a reference to an item, relative to the given module or to
+    /// any of its parents.
+    fn resolve(&self, module: &[String], reference: &str) -> Option<&str> {
+        let mut module = module;
+        let mut reference = reference;
+
+        loop {
+            if let Some(rest) = reference.strip_prefix("self::") {
+                reference = rest;
+            } else if let Some(rest) = reference.strip_prefix("super::") {
+                module = module.split_last().map(|(_, m)| m).unwrap_or_default();
+                reference = rest;
+            } else if let Some(rest) = reference.strip_prefix("crate::") {
+                module = module.get(..1).unwrap_or_default();
+                reference = rest;
+            } else {
+                break;
+            }
+        }
+
+        for n in (0..=module.len()).rev() {
+            let mut path = module[..n].join("::");
+
+            if !path.is_empty() {
+                path.push_str("::");
+            }
+
+            path.push_str(reference);
+
+            if let Some(href) = self.links.get(&path) {
+                return Some(href);
+            }
+        }
+
+        None
+    }
+}
+
+/// Construct the path of an item in a script, which is placed in a module
+/// with the given name.
+fn script_path(name: &str, item: &Item) -> ItemPath {
+    let mut path = vec![name.to_owned()];
+    path.extend(item_path(item));
+    path
+}
+
+/// Convert an item into its path.
+fn item_path(item: &Item) -> ItemPath {
+    item.iter()
+        .map(|c| match c {
+            ComponentRef::Crate(s) | ComponentRef::Str(s) => s.to_owned(),
+            ComponentRef::Id(n) => format!("${}", n),
+        })
+        .collect()
+}
+
+/// Get the last component of a path.
+fn last(path: &[String]) -> &str {
+    path.last().map(String::as_str).unwrap_or_default()
+}
+
+/// Render numbered arguments, like `#0, #1`.
+fn numbered_args(n: usize) -> String {
+    (0..n)
+        .map(|n| format!("#{}", n))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Get the file name of the page of the given module.
+fn page_name(path: &[String]) -> String {
+    format!("{}.html", path.join("."))
+}
+
+/// Get a link to the item with the given path.
+fn page_link(path: &[String], kind: &str) -> String {
+    let (name, module) = match path.split_last() {
+        Some(split) => split,
+        None => return String::from("index.html"),
+    };
+
+    format!("{}#{}", page_name(module), anchor(kind, name))
+}
+
+/// Get the anchor of an item of the given kind.
+fn anchor(kind: &str, name: &str) -> String {
+    let kind = match kind {
+        "struct" | "enum" | "type" => "type",
+        "async fn" => "fn",
+        kind => kind,
+    };
+
+    format!("{}.{}", kind, name)
+}
+
+/// Strip the leading space that doc comments like `/// ...` have.
+fn strip_doc_line(line: &str) -> &str {
+    let line = line.trim_end();
+    line.strip_prefix(' ').unwrap_or(line)
+}
+
+/// Escape text for HTML.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+/// Wrap the body of a page.
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<link rel=\"stylesheet\" href=\"style.css\">\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape(title),
+        body
+    )
+}
+
+/// Write a file, adding the path to any errors.
+fn write_file(path: &Path, contents: &str) -> Result<()> {
+    fs::write(path, contents).with_context(|| format!("writing file: {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{strip_doc_line, Site};
+
+    #[test]
+    fn test_render_docs() {
+        let mut site = Site::default();
+        site.links.insert(
+            String::from("script::List"),
+            String::from("script.html#type.List"),
+        );
+
+        let docs = [
+            " Construct a [List] with `new`.",
+            "",
+            " ```",
+            " a < b",
+            " ```",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            site.render_docs(&[String::from("script")], &docs),
+            "<p>Construct a <a href=\"script.html#type.List\"><code>List</code></a> with <code>new</code>.</p>\n<pre><code>a &lt; b</code></pre>\n"
+        );
+
+        assert_eq!(strip_doc_line(" hello\r"), "hello");
+    }
+}
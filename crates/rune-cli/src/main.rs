@@ -61,6 +61,7 @@ use std::sync::Arc;
 use structopt::StructOpt;
 
 use runestick::{Unit, Value, VmExecution};
+mod doc;
 mod tests;
 
 pub const VERSION: &str = include_str!(concat!(env!("OUT_DIR"), "/version.txt"));
@@ -75,6 +76,12 @@ enum Command {
 
     /// Run the designated script
     Run(RunFlags),
+
+    /// Format the designated scripts
+    Fmt(FmtFlags),
+
+    /// Generate HTML documentation for the designated scripts
+    Doc(DocFlags),
 }
 
 impl Command {
@@ -82,6 +89,8 @@ impl Command {
         match self {
             Command::Check(_) => {}
             Command::Test(_) => {}
+            Command::Fmt(_) => {}
+            Command::Doc(_) => {}
             Command::Run(args) => {
                 if args.dump {
                     args.dump_unit = true;
@@ -148,8 +157,16 @@ struct SharedArgs {
 
 impl SharedArgs {
     /// Construct a runestick context according to the specified argument.
-    fn context(&self) -> Result<runestick::Context, runestick::ContextError> {
-        let mut context = rune_modules::default_context()?;
+    ///
+    /// If a clock is specified, it's used by the `time` module.
+    fn context(
+        &self,
+        clock: Option<&rune_modules::time::Clock>,
+    ) -> Result<runestick::Context, runestick::ContextError> {
+        let mut context = match clock {
+            Some(clock) => rune_modules::with_clock(true, clock.clone())?,
+            None => rune_modules::default_context()?,
+        };
 
         if self.experimental {
             context.install(&rune_modules::experiments::module(true)?)?;
@@ -179,6 +196,41 @@ pub(crate) struct TestFlags {
     #[structopt(long)]
     no_fail_fast: bool,
 
+    /// Run each test with a virtual clock, where timers fire as soon as
+    /// nothing else is running and `time::advance` can be used to move time
+    /// forward.
+    #[structopt(long)]
+    virtual_time: bool,
+
+    #[structopt(flatten)]
+    shared: SharedArgs,
+}
+
+#[derive(StructOpt, Debug, Clone)]
+struct FmtFlags {
+    /// Only check if files are formatted, exiting with a non-zero exit-code if
+    /// any of them would be changed
+    #[structopt(long)]
+    check: bool,
+
+    #[structopt(flatten)]
+    shared: SharedArgs,
+}
+
+#[derive(StructOpt, Debug, Clone)]
+struct DocFlags {
+    /// The directory to write the documentation to
+    #[structopt(short, long, parse(from_os_str), default_value = "target/rune-doc")]
+    output: PathBuf,
+
+    /// Include items which are not public
+    #[structopt(long)]
+    private: bool,
+
+    /// Do not document the native modules installed in the context
+    #[structopt(long)]
+    no_native: bool,
+
     #[structopt(flatten)]
     shared: SharedArgs,
 }
@@ -223,6 +275,11 @@ struct RunFlags {
     #[structopt(long)]
     with_source: bool,
 
+    /// Watch the script and any modules it loads for changes, reloading and
+    /// running it again when they change.
+    #[structopt(long)]
+    watch: bool,
+
     #[structopt(flatten)]
     shared: SharedArgs,
 }
@@ -257,7 +314,10 @@ impl Args {
                 options.test(true);
                 options.bytecode(false);
             }
-            Command::Run(_) => (),
+            Command::Doc(_) => {
+                options.bytecode(false);
+            }
+            Command::Run(_) | Command::Fmt(_) => (),
         }
 
         for option in &self.shared().compiler_options {
@@ -273,6 +333,8 @@ impl Args {
             Command::Check(args) => &args.shared,
             Command::Test(args) => &args.shared,
             Command::Run(args) => &args.shared,
+            Command::Fmt(args) => &args.shared,
+            Command::Doc(args) => &args.shared,
         }
     }
 
@@ -282,8 +344,30 @@ impl Args {
             Command::Check(args) => &mut args.shared,
             Command::Test(args) => &mut args.shared,
             Command::Run(args) => &mut args.shared,
+            Command::Fmt(args) => &mut args.shared,
+            Command::Doc(args) => &mut args.shared,
         }
     }
+
+    /// Construct the stream to write output to, according to the color
+    /// choice.
+    fn stdout(&self) -> StandardStream {
+        let choice = match self.color.as_str() {
+            "always" => ColorChoice::Always,
+            "ansi" => ColorChoice::AlwaysAnsi,
+            "auto" => {
+                if atty::is(atty::Stream::Stdout) {
+                    ColorChoice::Auto
+                } else {
+                    ColorChoice::Never
+                }
+            }
+            "never" => ColorChoice::Never,
+            _ => ColorChoice::Auto,
+        };
+
+        StandardStream::stdout(choice)
+    }
 }
 
 const SPECIAL_FILES: &[&str] = &[
@@ -322,6 +406,13 @@ async fn try_main() -> Result<ExitCode> {
 
     let paths = walk_paths(shared.recursive, std::mem::take(&mut shared.paths));
 
+    // NB: documentation is generated for all paths at once, since items link
+    // to each other.
+    if let Command::Doc(docflags) = &args.cmd {
+        let paths = paths.collect::<io::Result<Vec<_>>>()?;
+        return doc::run(&mut args.stdout(), docflags, &options, paths);
+    }
+
     for path in paths {
         let path = path?;
 
@@ -379,6 +470,7 @@ fn load_path(
     args: &Args,
     options: &rune::Options,
     path: &Path,
+    clock: Option<&rune_modules::time::Clock>,
 ) -> Result<(
     Arc<Unit>,
     runestick::Context,
@@ -387,7 +479,7 @@ fn load_path(
     Vec<(runestick::Hash, runestick::CompileMeta)>,
 )> {
     let shared = args.shared();
-    let context = shared.context()?;
+    let context = shared.context(clock)?;
 
     let bytecode_path = path.with_extension("rnc");
 
@@ -465,27 +557,13 @@ fn load_path(
 
 /// Run a single path.
 async fn run_path(args: &Args, options: &rune::Options, path: &Path) -> Result<ExitCode> {
-    let choice = match args.color.as_str() {
-        "always" => ColorChoice::Always,
-        "ansi" => ColorChoice::AlwaysAnsi,
-        "auto" => {
-            if atty::is(atty::Stream::Stdout) {
-                ColorChoice::Auto
-            } else {
-                ColorChoice::Never
-            }
-        }
-        "never" => ColorChoice::Never,
-        _ => ColorChoice::Auto,
-    };
-
-    let mut out = StandardStream::stdout(choice);
+    let mut out = args.stdout();
 
     match &args.cmd {
         Command::Check(checkargs) => {
             writeln!(out, "Checking: {}", path.display())?;
 
-            let context = checkargs.shared.context()?;
+            let context = checkargs.shared.context(None)?;
 
             let source = runestick::Source::from_path(path)
                 .with_context(|| format!("reading file: {}", path.display()))?;
@@ -522,15 +600,63 @@ async fn run_path(args: &Args, options: &rune::Options, path: &Path) -> Result<E
                 Ok(ExitCode::Success)
             }
         }
-        Command::Test(testflags) => match load_path(&mut out, args, &options, path) {
-            Ok((unit, _context, runtime, sources, tests)) => {
-                tests::do_tests(testflags, out, runtime, unit, sources, tests).await
+        Command::Fmt(fmtflags) => {
+            let source = runestick::Source::from_path(path)
+                .with_context(|| format!("reading file: {}", path.display()))?;
+
+            let formatted = match rune::format_source(source.as_str()) {
+                Ok(formatted) => formatted,
+                Err(rune::FormatError::ParseError { error }) => {
+                    let mut sources = rune::Sources::new();
+                    let source_id = sources.insert(source);
+
+                    let mut diagnostics = rune::Diagnostics::new();
+                    diagnostics.error(source_id, error);
+                    diagnostics.emit_diagnostics(&mut out, &sources)?;
+                    return Ok(ExitCode::Failure);
+                }
+                Err(error) => {
+                    writeln!(out, "Failed to format: {}: {}", path.display(), error)?;
+                    return Ok(ExitCode::Failure);
+                }
+            };
+
+            if formatted == source.as_str() {
+                return Ok(ExitCode::Success);
+            }
+
+            if fmtflags.check {
+                writeln!(out, "Would reformat: {}", path.display())?;
+                return Ok(ExitCode::Failure);
             }
-            Err(_) => Ok(ExitCode::Failure),
-        },
+
+            fs::write(path, formatted)
+                .with_context(|| format!("writing file: {}", path.display()))?;
+
+            writeln!(out, "Formatted: {}", path.display())?;
+            Ok(ExitCode::Success)
+        }
+        Command::Test(testflags) => {
+            let clock = if testflags.virtual_time {
+                Some(rune_modules::time::Clock::virtual_at(
+                    std::time::SystemTime::now(),
+                ))
+            } else {
+                None
+            };
+
+            match load_path(&mut out, args, options, path, clock.as_ref()) {
+                Ok((unit, _context, runtime, sources, tests)) => {
+                    tests::do_tests(testflags, out, runtime, unit, sources, tests, clock).await
+                }
+                Err(_) => Ok(ExitCode::Failure),
+            }
+        }
+        Command::Doc(..) => unreachable!("documentation is generated for all paths at once"),
+        Command::Run(runargs) if runargs.watch => do_watch(args, runargs, options, path).await,
         Command::Run(runargs) => {
             let (unit, context, runtime, sources, _tests) =
-                match load_path(&mut out, args, &options, path) {
+                match load_path(&mut out, args, options, path, None) {
                     Ok(v) => v,
                     Err(_) => return Ok(ExitCode::Failure),
                 };
@@ -540,6 +666,10 @@ async fn run_path(args: &Args, options: &rune::Options, path: &Path) -> Result<E
 
                 for (i, (hash, f)) in context.iter_functions().enumerate() {
                     writeln!(out, "{:04} = {} ({})", i, f, hash)?;
+
+                    for line in &f.meta().docs {
+                        writeln!(out, "     /// {}", line)?;
+                    }
                 }
             }
 
@@ -597,6 +727,111 @@ async fn run_path(args: &Args, options: &rune::Options, path: &Path) -> Result<E
     }
 }
 
+/// Run the script at the given path, and run it again each time it or any of
+/// the modules it loads changes.
+async fn do_watch(
+    args: &Args,
+    runargs: &RunFlags,
+    options: &rune::Options,
+    path: &Path,
+) -> Result<ExitCode> {
+    let context = args.shared().context(None)?;
+    let mut runtime = rune::Runtime::with_options(context, *options);
+
+    let source = runestick::Source::from_path(path)
+        .with_context(|| format!("reading file: {}", path.display()))?;
+
+    runtime.insert(source);
+    let mut first = true;
+
+    loop {
+        let mut out = args.stdout();
+        let result = runtime.reload();
+
+        if runtime.diagnostics().has_error() || args.shared().warnings {
+            runtime.emit_diagnostics(&mut out)?;
+        }
+
+        match result {
+            Ok(reload) => {
+                if !first {
+                    writeln!(out, "== reloaded: {}", path.display())?;
+
+                    for change in reload.functions.iter().chain(&reload.types) {
+                        writeln!(out, "  {}", change)?;
+                    }
+                }
+
+                first = false;
+
+                let runtime_context = runtime.runtime_context().clone();
+                let unit = runtime.unit().clone();
+                let sources = runtime.loaded_sources().clone();
+
+                if let Err(error) = do_run(runargs, out, runtime_context, unit, sources).await {
+                    let mut out = args.stdout();
+
+                    match error.downcast_ref::<runestick::VmError>() {
+                        Some(error) => runtime.emit_vm_error(&mut out, error)?,
+                        None => writeln!(out, "== error: {}", error)?,
+                    }
+                }
+            }
+            Err(..) => {
+                writeln!(out, "== failed to build: {}", path.display())?;
+            }
+        }
+
+        tokio::task::block_in_place(|| wait_for_changes(&mut runtime, path))?;
+    }
+}
+
+/// Block until the script at the given path, or any of the modules it loaded
+/// during the last build, has changed.
+fn wait_for_changes(runtime: &mut rune::Runtime, path: &Path) -> Result<()> {
+    use notify::Watcher as _;
+    use std::collections::BTreeSet;
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut watcher = notify::watcher(tx, std::time::Duration::from_millis(100))?;
+
+    // NB: watch directories rather than files, since editors commonly save
+    // files by replacing them.
+    let mut directories = BTreeSet::new();
+
+    for path in runtime.paths().chain(std::iter::once(path)) {
+        // NB: a file might have been removed since it was loaded, in which
+        // case we keep watching the rest.
+        let path = match path.canonicalize() {
+            Ok(path) => path,
+            Err(error) => {
+                log::warn!("failed to watch `{}`: {}", path.display(), error);
+                continue;
+            }
+        };
+
+        if let Some(parent) = path.parent() {
+            directories.insert(parent.to_owned());
+        }
+    }
+
+    for directory in &directories {
+        watcher.watch(directory, notify::RecursiveMode::NonRecursive)?;
+    }
+
+    loop {
+        rx.recv()?;
+
+        match runtime.refresh() {
+            Ok(true) => return Ok(()),
+            Ok(false) => (),
+            Err(error) => {
+                log::warn!("failed to read sources: {}", error);
+            }
+        }
+    }
+}
+
 async fn do_run(
     args: &RunFlags,
     mut out: StandardStream,
@@ -168,6 +168,7 @@ pub(crate) async fn do_tests(
     unit: Arc<Unit>,
     sources: Sources,
     tests: Vec<(Hash, CompileMeta)>,
+    clock: Option<rune_modules::time::Clock>,
 ) -> anyhow::Result<ExitCode> {
     let mut cases = tests
         .into_iter()
@@ -184,7 +185,22 @@ pub(crate) async fn do_tests(
         let vm = Vm::new(runtime.clone(), unit.clone());
 
         test.start(&mut out, test_args.quiet)?;
-        let success = test.execute(unit.as_ref(), vm).await?;
+
+        let success = match &clock {
+            // NB: a virtual clock pauses the time of the runtime it's started
+            // in, so each test gets a fresh current-thread runtime of its own.
+            Some(clock) => tokio::task::block_in_place(|| -> anyhow::Result<bool> {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()?;
+
+                Ok(runtime.block_on(async {
+                    clock.start();
+                    test.execute(unit.as_ref(), vm).await
+                })?)
+            })?,
+            None => test.execute(unit.as_ref(), vm).await?,
+        };
         test.end(&mut out, test_args.quiet)?;
         if !success {
             failure_count += 1;
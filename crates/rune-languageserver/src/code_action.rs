@@ -0,0 +1,214 @@
+//! Quick fixes for diagnostics produced by the compiler.
+//!
+//! Fixes are computed when a project is built and attached to the `data` field
+//! of the diagnostic they belong to. When the client asks for code actions it
+//! sends the relevant diagnostics back, from which the fixes are extracted.
+
+use crate::state::span_to_lsp_range;
+use runestick::{ComponentRef, Item, Span};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A quick fix which can be applied to a single diagnostic.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Fix {
+    /// The title of the fix, as shown to the user.
+    pub(crate) title: String,
+    /// Edits to perform to the document the diagnostic belongs to.
+    pub(crate) edits: Vec<lsp::TextEdit>,
+}
+
+impl Fix {
+    /// Construct a fix with a single edit.
+    fn new<T>(title: T, range: lsp::Range, new_text: String) -> Self
+    where
+        T: Into<String>,
+    {
+        Self {
+            title: title.into(),
+            edits: vec![lsp::TextEdit { range, new_text }],
+        }
+    }
+}
+
+/// Encode the given fixes as diagnostics data.
+pub(crate) fn to_data(fixes: Vec<Fix>) -> Option<serde_json::Value> {
+    if fixes.is_empty() {
+        return None;
+    }
+
+    serde_json::to_value(fixes).ok()
+}
+
+/// Compute fixes for the given compiler warning.
+pub(crate) fn warning_fixes(source: &runestick::Source, kind: &rune::WarningKind) -> Vec<Fix> {
+    let mut fixes = Vec::new();
+
+    match kind {
+        rune::WarningKind::RemoveTupleCallParams { span, variant, .. } => {
+            if let (Some(range), Some(variant)) =
+                (span_to_lsp_range(source, *span), source.source(*variant))
+            {
+                fixes.push(Fix::new(
+                    "Remove call parentheses",
+                    range,
+                    variant.to_owned(),
+                ));
+            }
+        }
+        rune::WarningKind::UnecessarySemiColon { span } => {
+            if let Some(range) = span_to_lsp_range(source, *span) {
+                fixes.push(Fix::new("Remove semicolon", range, String::new()));
+            }
+        }
+        // NB: the compiler doesn't warn about unused bindings, so there's no
+        // fix prefixing them with `_`.
+        _ => (),
+    }
+
+    fixes
+}
+
+/// Compute fixes which imports the given missing path, by looking for items
+/// with a matching name among the `candidates`.
+pub(crate) fn missing_item_fixes<'a, I>(
+    source: &runestick::Source,
+    span: Span,
+    candidates: I,
+) -> Vec<Fix>
+where
+    I: IntoIterator<Item = &'a Item>,
+{
+    let mut fixes = Vec::new();
+
+    let name = match source.source(span).and_then(|path| path.split("::").next()) {
+        Some(name) => name.trim(),
+        None => return fixes,
+    };
+
+    let mut imports = candidates
+        .into_iter()
+        .filter(|item| matches!(item.last(), Some(ComponentRef::Str(last)) if last == name))
+        .filter_map(import_path)
+        .collect::<Vec<_>>();
+
+    imports.sort();
+    imports.dedup();
+
+    let position = use_position(source.as_str());
+    let range = lsp::Range::new(position, position);
+
+    for import in imports {
+        fixes.push(Fix::new(
+            format!("Import `{}`", import),
+            range,
+            format!("use {};\n", import),
+        ));
+    }
+
+    fixes
+}
+
+/// Format the path used to import the given item, like `std::collections`.
+///
+/// Items which can't be named, like closures, have no import path.
+fn import_path(item: &Item) -> Option<String> {
+    let mut path = String::new();
+
+    for c in item {
+        let c = match c {
+            ComponentRef::Crate(c) | ComponentRef::Str(c) => c,
+            ComponentRef::Id(..) => return None,
+        };
+
+        if !path.is_empty() {
+            path.push_str("::");
+        }
+
+        path.push_str(c);
+    }
+
+    Some(path)
+}
+
+/// Convert the fixes attached to the given diagnostics into code actions.
+pub(crate) fn code_actions(
+    uri: &lsp::Url,
+    diagnostics: &[lsp::Diagnostic],
+) -> Vec<lsp::CodeActionOrCommand> {
+    let mut actions = Vec::new();
+
+    for diagnostic in diagnostics {
+        let fixes = match &diagnostic.data {
+            Some(data) => match Vec::<Fix>::deserialize(data) {
+                Ok(fixes) => fixes,
+                Err(error) => {
+                    log::warn!("bad fixes in diagnostic: {}", error);
+                    continue;
+                }
+            },
+            None => continue,
+        };
+
+        let is_preferred = fixes.len() == 1;
+
+        for fix in fixes {
+            let mut changes = HashMap::new();
+            changes.insert(uri.clone(), fix.edits);
+
+            actions.push(lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
+                title: fix.title,
+                kind: Some(lsp::CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![diagnostic.clone()]),
+                edit: Some(lsp::WorkspaceEdit::new(changes)),
+                is_preferred: Some(is_preferred),
+                ..Default::default()
+            }));
+        }
+    }
+
+    actions
+}
+
+/// Find the position at which a new `use` declaration should be inserted,
+/// which is after the last top-level `use` in the file.
+fn use_position(source: &str) -> lsp::Position {
+    let line = source
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.starts_with("use ") || line.starts_with("pub use "))
+        .map(|(n, _)| n + 1)
+        .last()
+        .unwrap_or_default();
+
+    lsp::Position::new(line as u32, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::missing_item_fixes;
+    use runestick::{Item, Source, Span};
+
+    #[test]
+    fn test_missing_item_fixes() {
+        let source = Source::new(
+            "test",
+            "use std::string;\n\npub fn main() { HashMap::new() }\n",
+        );
+
+        let candidates = vec![
+            Item::with_crate_item("std", &["collections", "HashMap"]),
+            Item::with_crate_item("std", &["collections", "HashSet"]),
+        ];
+
+        let fixes = missing_item_fixes(&source, Span::new(34, 46), &candidates);
+
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(fixes[0].title, "Import `std::collections::HashMap`");
+        assert_eq!(fixes[0].edits[0].range.start, lsp::Position::new(1, 0));
+        assert_eq!(
+            fixes[0].edits[0].new_text,
+            "use std::collections::HashMap;\n"
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use crate::envelope;
 use anyhow::{anyhow, bail, Result};
 use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::io;
 use tokio::io::{
@@ -48,6 +49,8 @@ impl Input {
 #[derive(Clone)]
 pub struct Output {
     stdout: Arc<Mutex<io::Stdout>>,
+    /// The id of the next request sent to the client.
+    next_id: Arc<AtomicU64>,
 }
 
 impl Output {
@@ -112,6 +115,27 @@ impl Output {
         Ok(())
     }
 
+    /// Send the given request to the client.
+    ///
+    /// The response to the request is ignored.
+    pub async fn request<R>(&self, request: R::Params) -> Result<()>
+    where
+        R: lsp::request::Request,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+
+        let request = crate::envelope::RequestMessage {
+            jsonrpc: crate::envelope::V2,
+            id: envelope::RequestId::Number(id),
+            method: R::METHOD,
+            params: request,
+        };
+
+        let mut bytes = serde_json::to_vec(&request)?;
+        self.write_response(&mut bytes).await?;
+        Ok(())
+    }
+
     /// Send a log message.
     pub async fn log<M>(&self, typ: lsp::MessageType, message: M) -> Result<()>
     where
@@ -155,6 +179,7 @@ pub fn stdio() -> Result<(Input, Output)> {
 
     let output = Output {
         stdout: Arc::new(Mutex::new(stdout)),
+        next_id: Arc::new(AtomicU64::new(0)),
     };
 
     Ok((input, output))
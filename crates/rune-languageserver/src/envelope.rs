@@ -10,14 +10,27 @@ pub enum RequestId {
     String(String),
 }
 
+/// An incoming message.
+///
+/// Responses to requests sent by the server have no method.
 #[derive(Debug, Clone, Deserialize)]
 pub struct IncomingMessage {
     pub jsonrpc: V2,
     pub id: Option<RequestId>,
+    #[serde(default)]
     pub method: String,
+    #[serde(default)]
     pub params: serde_json::Value,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct RequestMessage<T> {
+    pub jsonrpc: V2,
+    pub id: RequestId,
+    pub method: &'static str,
+    pub params: T,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct NotificationMessage<T> {
     pub jsonrpc: V2,
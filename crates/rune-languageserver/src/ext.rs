@@ -0,0 +1,86 @@
+//! Protocol extensions which are not yet available in [lsp].
+
+use serde::{Deserialize, Serialize};
+
+/// The initialize request, with a result which can declare capabilities that
+/// [lsp::ServerCapabilities] doesn't know about yet.
+pub(crate) enum Initialize {}
+
+impl lsp::request::Request for Initialize {
+    type Params = lsp::InitializeParams;
+    type Result = InitializeResult;
+    const METHOD: &'static str = <lsp::request::Initialize as lsp::request::Request>::METHOD;
+}
+
+/// The result of an initialize request.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InitializeResult {
+    /// The capabilities of the language server.
+    pub(crate) capabilities: ServerCapabilities,
+    /// Information about the server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) server_info: Option<lsp::ServerInfo>,
+}
+
+/// Server capabilities, extended with ones that are not supported by [lsp].
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ServerCapabilities {
+    /// Capabilities known by [lsp].
+    #[serde(flatten)]
+    pub(crate) base: lsp::ServerCapabilities,
+    /// The server provides inlay hints.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) inlay_hint_provider: Option<bool>,
+}
+
+/// The `textDocument/inlayHint` request.
+pub(crate) enum InlayHintRequest {}
+
+impl lsp::request::Request for InlayHintRequest {
+    type Params = InlayHintParams;
+    type Result = Option<Vec<InlayHint>>;
+    const METHOD: &'static str = "textDocument/inlayHint";
+}
+
+/// Parameters of the `textDocument/inlayHint` request.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InlayHintParams {
+    /// The text document.
+    pub(crate) text_document: lsp::TextDocumentIdentifier,
+    /// The visible range for which hints should be computed.
+    pub(crate) range: lsp::Range,
+}
+
+/// A single inlay hint.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InlayHint {
+    /// The position of the hint.
+    pub(crate) position: lsp::Position,
+    /// The label of the hint.
+    pub(crate) label: String,
+    /// The kind of the hint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) kind: Option<InlayHintKind>,
+    /// Render padding before the hint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) padding_left: Option<bool>,
+    /// Render padding after the hint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) padding_right: Option<bool>,
+}
+
+/// The kind of an inlay hint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(crate) struct InlayHintKind(u32);
+
+impl InlayHintKind {
+    /// A hint for the type of a binding.
+    pub(crate) const TYPE: Self = Self(1);
+    /// A hint for the name of a parameter.
+    pub(crate) const PARAMETER: Self = Self(2);
+}
@@ -0,0 +1,579 @@
+//! Inlay hints for parameter names and the kinds of values bound by `let`.
+//!
+//! Like signature help this operates on tokens, so that hints remain available
+//! while a source is being edited and doesn't parse.
+
+use crate::signature_help::{self, Callee};
+use rune::ast;
+use rune::K;
+use runestick::debug::DebugArgs;
+use runestick::{ComponentRef, Context, ContextSignature, Item, Unit};
+
+/// The kind of a hint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HintKind {
+    /// The name of the parameter an argument is passed to.
+    Parameter,
+    /// The kind of value bound to a variable.
+    Type,
+}
+
+/// A single hint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Hint {
+    /// The byte offset in the source at which the hint should be shown.
+    pub(crate) offset: usize,
+    /// The label of the hint.
+    pub(crate) label: String,
+    /// The kind of the hint.
+    pub(crate) kind: HintKind,
+}
+
+/// What's known about the items which can be referenced by a source.
+pub(crate) struct Known<'a> {
+    /// The last unit successfully built from the source.
+    pub(crate) unit: Option<&'a Unit>,
+    /// The context the source is built for.
+    pub(crate) context: &'a Context,
+    /// Constructors defined in the source, and the type they construct.
+    pub(crate) constructors: &'a [(Item, Item)],
+}
+
+impl Known<'_> {
+    /// Names of the parameters of the given callee, as long as they can be
+    /// unambiguously determined.
+    ///
+    /// For instance calls the `self` parameter is omitted.
+    fn parameters(&self, callee: &Callee) -> Option<Vec<String>> {
+        let debug_info = self.unit?.debug_info()?;
+        let mut found = None::<Vec<String>>;
+
+        for signature in debug_info.functions.values() {
+            if !signature_help::matches(callee, &signature.path) {
+                continue;
+            }
+
+            let mut args = match &signature.args {
+                DebugArgs::Named(args) => args.clone(),
+                _ => return None,
+            };
+
+            if let Callee::Instance(..) = callee {
+                if args.first().map(String::as_str) != Some("self") {
+                    continue;
+                }
+
+                args.remove(0);
+            }
+
+            match &found {
+                Some(existing) if *existing != args => return None,
+                _ => found = Some(args),
+            }
+        }
+
+        // NB: native functions don't carry parameter names, so if the name
+        // also refers to one of them the hints might be wrong.
+        if let Callee::Instance(name) = callee {
+            let native = self.context.iter_functions().any(|(_, signature)| {
+                matches!(signature, ContextSignature::Instance { name: n, .. } if n == name)
+            });
+
+            if native {
+                return None;
+            }
+        }
+
+        found
+    }
+
+    /// The kind of value constructed by the given path, if it's a known struct
+    /// or variant.
+    fn constructed(&self, path: &[String]) -> Option<String> {
+        let callee = Callee::Path(path.to_vec());
+
+        unique(
+            self.constructors
+                .iter()
+                .filter(|(item, _)| signature_help::matches(&callee, item))
+                .filter_map(|(_, ty)| match ty.last()? {
+                    ComponentRef::Str(name) => Some(name.to_owned()),
+                    _ => None,
+                }),
+        )
+    }
+
+    /// The kind of value returned when calling the native function at the
+    /// given path.
+    fn returned(&self, path: &[String]) -> Option<String> {
+        let callee = Callee::Path(path.to_vec());
+
+        let kinds = self
+            .context
+            .iter_functions()
+            .filter(|(_, signature)| match signature {
+                ContextSignature::Function { item, .. } => signature_help::matches(&callee, item),
+                _ => false,
+            })
+            .map(|(_, signature)| signature.return_type().map(ToString::to_string));
+
+        unique(kinds)?
+    }
+
+    /// The kind of value returned when calling the native instance function
+    /// with the given name.
+    fn returned_instance(&self, name: &str) -> Option<String> {
+        let kinds = self
+            .context
+            .iter_functions()
+            .filter(|(_, signature)| {
+                matches!(signature, ContextSignature::Instance { name: n, .. } if n == name)
+            })
+            .map(|(_, signature)| signature.return_type().map(ToString::to_string));
+
+        unique(kinds)?
+    }
+}
+
+/// Compute all hints in the given source.
+pub(crate) fn hints(source: &str, known: &Known<'_>) -> Vec<Hint> {
+    let mut lexer = rune::Lexer::new(source);
+    let mut tokens = Vec::new();
+
+    while let Ok(Some(token)) = lexer.next() {
+        tokens.push(token);
+    }
+
+    let mut hints = Vec::new();
+    parameter_hints(source, &tokens, known, &mut hints);
+    let_hints(source, &tokens, known, &mut hints);
+    hints.sort_by_key(|hint| hint.offset);
+    hints
+}
+
+/// A delimiter which is open while looking for arguments.
+struct Open {
+    /// Parameter names, if this is the argument list of a call with known
+    /// parameters.
+    parameters: Option<Vec<String>>,
+    /// The index of the current argument.
+    argument: usize,
+    /// If the next token starts a new argument.
+    start: bool,
+    /// If we're inside of the parameters of a closure, whose commas doesn't
+    /// separate arguments.
+    closure: bool,
+}
+
+/// Add hints for the names of parameters at the start of each argument in a
+/// call.
+fn parameter_hints(source: &str, tokens: &[ast::Token], known: &Known<'_>, hints: &mut Vec<Hint>) {
+    let mut stack = Vec::<Open>::new();
+
+    for (n, token) in tokens.iter().enumerate() {
+        if let Some(open) = stack.last_mut() {
+            if std::mem::take(&mut open.start) && !matches!(token.kind, K![')']) {
+                if let Some(name) = open.parameters.as_ref().and_then(|p| p.get(open.argument)) {
+                    // NB: `foo(a)` where the parameter is named `a` is
+                    // redundant to hint.
+                    let redundant = matches!(token.kind, K![ident])
+                        && source.get(token.span.range()) == Some(name.as_str())
+                        && matches!(tokens.get(n + 1).map(|t| t.kind), Some(K![,] | K![')']));
+
+                    if !redundant && name != "_" {
+                        hints.push(Hint {
+                            offset: token.span.start.into_usize(),
+                            label: format!("{}:", name),
+                            kind: HintKind::Parameter,
+                        });
+                    }
+                }
+
+                if let K![|] = token.kind {
+                    open.closure = true;
+                    continue;
+                }
+            }
+        }
+
+        match token.kind {
+            K!['('] => {
+                let parameters = signature_help::callee(source, &tokens[..n])
+                    .and_then(|callee| known.parameters(&callee));
+
+                stack.push(Open {
+                    parameters,
+                    argument: 0,
+                    start: true,
+                    closure: false,
+                });
+            }
+            K!['['] | K!['{'] => {
+                stack.push(Open {
+                    parameters: None,
+                    argument: 0,
+                    start: false,
+                    closure: false,
+                });
+            }
+            K![')'] | K![']'] | K!['}'] => {
+                stack.pop();
+            }
+            K![|] => {
+                if let Some(open) = stack.last_mut() {
+                    open.closure = false;
+                }
+            }
+            K![,] => {
+                if let Some(open) = stack.last_mut() {
+                    if !open.closure && open.parameters.is_some() {
+                        open.argument += 1;
+                        open.start = true;
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Add hints for the kind of value bound by `let` declarations.
+fn let_hints(source: &str, tokens: &[ast::Token], known: &Known<'_>, hints: &mut Vec<Hint>) {
+    for (n, token) in tokens.iter().enumerate() {
+        if !matches!(token.kind, K![let]) {
+            continue;
+        }
+
+        let ident = match (tokens.get(n + 1), tokens.get(n + 2)) {
+            (Some(ident), Some(eq)) if matches!((ident.kind, eq.kind), (K![ident], K![=])) => ident,
+            _ => continue,
+        };
+
+        let init = initializer(&tokens[n + 3..]);
+
+        if let Some(kind) = infer(source, init, known) {
+            hints.push(Hint {
+                offset: ident.span.end.into_usize(),
+                label: format!(": {}", kind),
+                kind: HintKind::Type,
+            });
+        }
+    }
+}
+
+/// Find the tokens making up the initializer of a `let` declaration.
+fn initializer(tokens: &[ast::Token]) -> &[ast::Token] {
+    let mut depth = 0usize;
+
+    for (n, token) in tokens.iter().enumerate() {
+        match token.kind {
+            K!['('] | K!['['] | K!['{'] => depth += 1,
+            K![')'] | K![']'] | K!['}'] => match depth.checked_sub(1) {
+                Some(d) => depth = d,
+                None => return &tokens[..n],
+            },
+            K![;] | K![let] if depth == 0 => return &tokens[..n],
+            _ => (),
+        }
+    }
+
+    tokens
+}
+
+/// Infer the kind of value produced by the given expression.
+fn infer(source: &str, tokens: &[ast::Token], known: &Known<'_>) -> Option<String> {
+    let text = |token: &ast::Token| source.get(token.span.range());
+
+    let (first, rest) = tokens.split_first()?;
+
+    let kind = match first.kind {
+        // NB: template literals are lexed into synthetic tokens spanning the
+        // backticks.
+        _ if text(first)?.starts_with('`') => {
+            if text(tokens.last()?)? != "`" {
+                return None;
+            }
+
+            return Some(String::from("String"));
+        }
+        K![-] => {
+            return match rest {
+                [number] if matches!(number.kind, K![number]) => infer(source, rest, known),
+                _ => None,
+            };
+        }
+        K![number(ast::NumberSource::Text(number))] => {
+            if number.is_fractional {
+                "float"
+            } else {
+                "integer"
+            }
+        }
+        K![str] => "String",
+        K![bytestr] => "Bytes",
+        K![char] => "char",
+        K![byte] => "byte",
+        K![true] | K![false] => "bool",
+        K![|] | K![||] => return Some(String::from("Function")),
+        K!['['] => {
+            if group_end(tokens)? + 1 != tokens.len() {
+                return None;
+            }
+
+            "Vec"
+        }
+        K![#] => {
+            if !matches!(rest.first()?.kind, K!['{']) || group_end(rest)? + 1 != rest.len() {
+                return None;
+            }
+
+            "Object"
+        }
+        K!['('] => {
+            if group_end(tokens)? + 1 != tokens.len() {
+                return None;
+            }
+
+            let inner = &tokens[1..tokens.len() - 1];
+
+            if inner.is_empty() {
+                "unit"
+            } else if has_top_level_comma(inner) {
+                "Tuple"
+            } else {
+                return infer(source, inner, known);
+            }
+        }
+        K![ident] | K![self] | K![Self] | K![super] | K![crate] => {
+            return infer_path(source, tokens, known);
+        }
+        _ => return None,
+    };
+
+    if !rest.is_empty() && !matches!(first.kind, K![#] | K!['['] | K!['(']) {
+        return None;
+    }
+
+    Some(String::from(kind))
+}
+
+/// Infer the kind of value produced by an expression starting with a path,
+/// like constructors, function calls and method calls.
+fn infer_path(source: &str, tokens: &[ast::Token], known: &Known<'_>) -> Option<String> {
+    let mut path = Vec::new();
+    let mut it = tokens.iter().enumerate();
+
+    let rest = loop {
+        let (_, token) = it.next()?;
+        path.push(source.get(token.span.range())?.to_owned());
+
+        match it.next() {
+            Some((_, t)) if matches!(t.kind, K![::]) => continue,
+            Some((n, _)) => break &tokens[n..],
+            None => break &[][..],
+        }
+    };
+
+    match rest {
+        [] => known.constructed(&path),
+        [dot, method, open, ..] if matches!(dot.kind, K![.]) => {
+            if path.len() != 1
+                || !matches!((method.kind, open.kind), (K![ident], K!['(']))
+                || group_end(&rest[2..])? + 3 != rest.len()
+            {
+                return None;
+            }
+
+            known.returned_instance(source.get(method.span.range())?)
+        }
+        [open, ..] if matches!(open.kind, K!['('] | K!['{']) => {
+            if group_end(rest)? + 1 != rest.len() {
+                return None;
+            }
+
+            if let Some(kind) = known.constructed(&path) {
+                return Some(kind);
+            }
+
+            if matches!(open.kind, K!['(']) {
+                return known.returned(&path);
+            }
+
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Find the index of the token closing the group opened by the first token.
+fn group_end(tokens: &[ast::Token]) -> Option<usize> {
+    let mut depth = 0usize;
+
+    for (n, token) in tokens.iter().enumerate() {
+        match token.kind {
+            K!['('] | K!['['] | K!['{'] => depth += 1,
+            K![')'] | K![']'] | K!['}'] => {
+                depth = depth.checked_sub(1)?;
+
+                if depth == 0 {
+                    return Some(n);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    None
+}
+
+/// Test if the given tokens contain a comma which isn't nested in a group.
+fn has_top_level_comma(tokens: &[ast::Token]) -> bool {
+    let mut depth = 0usize;
+
+    for token in tokens {
+        match token.kind {
+            K!['('] | K!['['] | K!['{'] => depth += 1,
+            K![')'] | K![']'] | K!['}'] => depth = depth.saturating_sub(1),
+            K![,] if depth == 0 => return true,
+            _ => (),
+        }
+    }
+
+    false
+}
+
+/// Get the single distinct value of the iterator, if there is one.
+fn unique<I, T>(iter: I) -> Option<T>
+where
+    I: IntoIterator<Item = T>,
+    T: PartialEq,
+{
+    let mut found = None;
+
+    for value in iter {
+        match &found {
+            Some(existing) if *existing != value => return None,
+            _ => found = Some(value),
+        }
+    }
+
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hints, Hint, HintKind, Known};
+    use runestick::{Context, Item};
+
+    fn labels(source: &str, known: &Known<'_>) -> Vec<(HintKind, String)> {
+        hints(source, known)
+            .into_iter()
+            .map(|Hint { kind, label, .. }| (kind, label))
+            .collect()
+    }
+
+    #[test]
+    fn test_let_hints() {
+        let context = Context::with_default_modules().unwrap();
+        let constructors = vec![(Item::with_item(&["Foo"]), Item::with_item(&["Foo"]))];
+
+        let known = Known {
+            unit: None,
+            context: &context,
+            constructors: &constructors,
+        };
+
+        let source = r#"
+        fn main() {
+            let a = 1;
+            let b = -1.5;
+            let c = "hello";
+            let d = [1, 2];
+            let e = #{a: 1};
+            let f = (1, 2);
+            let g = Foo { a: 1 };
+            let h = |a| a + 1;
+            let i = a + 1;
+            let j = `hello {a}`;
+        }
+        "#;
+
+        let expected = [
+            "integer", "float", "String", "Vec", "Object", "Tuple", "Foo", "Function", "String",
+        ];
+
+        let actual = labels(source, &known);
+        assert_eq!(actual.len(), expected.len());
+
+        for ((kind, label), expected) in actual.into_iter().zip(expected.iter()) {
+            assert_eq!(kind, HintKind::Type);
+            assert_eq!(label, format!(": {}", expected));
+        }
+    }
+
+    #[test]
+    fn test_parameter_hints() {
+        let context = Context::with_default_modules().unwrap();
+
+        let source = r#"
+        fn add(a, b) { a + b }
+        pub fn main() { let b = 2; add(1, b); add(|x, y| x, [1, 2]) }
+        "#;
+
+        let mut sources = rune::Sources::new();
+        sources.insert(runestick::Source::new("test", source));
+        let mut diagnostics = rune::Diagnostics::new();
+
+        let unit = rune::load_sources(
+            &context,
+            &rune::Options::default(),
+            &mut sources,
+            &mut diagnostics,
+        )
+        .unwrap();
+
+        let known = Known {
+            unit: Some(&unit),
+            context: &context,
+            constructors: &[],
+        };
+
+        let parameters = labels(source, &known)
+            .into_iter()
+            .filter(|(kind, _)| *kind == HintKind::Parameter)
+            .map(|(_, label)| label)
+            .collect::<Vec<_>>();
+
+        assert_eq!(parameters, vec!["a:", "a:", "b:"]);
+    }
+
+    #[test]
+    fn test_returned() {
+        let mut module = runestick::Module::new();
+        module
+            .function(&["bytes"], || Ok::<_, runestick::VmError>(vec![1u8]))
+            .unwrap()
+            .returns::<Vec<u8>>();
+        module
+            .function(&["string"], |s: &str| s.to_owned())
+            .unwrap()
+            .returns::<String>();
+        module
+            .function(&["dynamic"], || runestick::Value::Unit)
+            .unwrap();
+
+        let mut context = Context::new();
+        context.install(&module).unwrap();
+
+        let known = Known {
+            unit: None,
+            context: &context,
+            constructors: &[],
+        };
+
+        let path = |name: &str| vec![String::from(name)];
+        assert_eq!(known.returned(&path("bytes")).as_deref(), Some("Vec"));
+        assert_eq!(known.returned(&path("string")).as_deref(), Some("String"));
+        assert_eq!(known.returned(&path("dynamic")), None);
+    }
+}
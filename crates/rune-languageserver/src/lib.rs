@@ -44,10 +44,15 @@
 //!
 //! [Rune Language]: https://rune-rs.github.io
 
+mod code_action;
 mod connection;
 pub mod envelope;
+mod ext;
+mod inlay_hint;
 mod server;
+mod signature_help;
 mod state;
+mod workspace;
 
 pub const VERSION: &str = include_str!(concat!(env!("OUT_DIR"), "/version.txt"));
 
@@ -56,6 +61,7 @@ pub use crate::connection::{Input, Output};
 pub use crate::server::Server;
 pub use crate::state::State;
 use anyhow::Result;
+use std::path::PathBuf;
 use tokio::sync::mpsc;
 
 pub fn run(context: runestick::Context, options: rune::Options) -> Result<()> {
@@ -65,9 +71,13 @@ pub fn run(context: runestick::Context, options: rune::Options) -> Result<()> {
 
     let mut server = Server::new(output, rebuild_tx, context, options);
 
-    server.request_handler::<lsp::request::Initialize, _, _>(initialize);
+    server.request_handler::<ext::Initialize, _, _>(initialize);
 
     server.request_handler::<lsp::request::GotoDefinition, _, _>(goto_definition);
+    server.request_handler::<lsp::request::SignatureHelpRequest, _, _>(signature_help);
+    server.request_handler::<lsp::request::CodeActionRequest, _, _>(code_action);
+    server.request_handler::<lsp::request::Formatting, _, _>(formatting);
+    server.request_handler::<ext::InlayHintRequest, _, _>(inlay_hint);
 
     server.notification_handler::<lsp::notification::DidOpenTextDocument, _, _>(
         did_open_text_document,
@@ -81,6 +91,12 @@ pub fn run(context: runestick::Context, options: rune::Options) -> Result<()> {
     server.notification_handler::<lsp::notification::DidSaveTextDocument, _, _>(
         did_save_text_document,
     );
+    server.notification_handler::<lsp::notification::DidChangeWatchedFiles, _, _>(
+        did_change_watched_files,
+    );
+    server.notification_handler::<lsp::notification::DidChangeWorkspaceFolders, _, _>(
+        did_change_workspace_folders,
+    );
     server.notification_handler::<lsp::notification::Initialized, _, _>(initialized);
 
     log::info!("Starting server");
@@ -110,10 +126,28 @@ pub fn run(context: runestick::Context, options: rune::Options) -> Result<()> {
 async fn initialize(
     state: State,
     output: Output,
-    _: lsp::InitializeParams,
-) -> Result<lsp::InitializeResult> {
+    params: lsp::InitializeParams,
+) -> Result<ext::InitializeResult> {
     state.initialize();
 
+    let watch_files = params
+        .capabilities
+        .workspace
+        .as_ref()
+        .and_then(|w| w.did_change_watched_files.as_ref())
+        .and_then(|w| w.dynamic_registration)
+        .unwrap_or_default();
+
+    state.set_watch_files(watch_files);
+
+    {
+        let mut workspace = state.workspace_mut().await;
+
+        for root in roots(&params) {
+            workspace.add(root);
+        }
+    }
+
     output
         .log(lsp::MessageType::Info, "Starting language server")
         .await?;
@@ -126,23 +160,122 @@ async fn initialize(
 
     capabilities.definition_provider = Some(lsp::OneOf::Left(true));
 
+    capabilities.signature_help_provider = Some(lsp::SignatureHelpOptions {
+        trigger_characters: Some(vec![String::from("("), String::from(",")]),
+        retrigger_characters: None,
+        work_done_progress_options: Default::default(),
+    });
+
+    capabilities.code_action_provider = Some(lsp::CodeActionProviderCapability::Options(
+        lsp::CodeActionOptions {
+            code_action_kinds: Some(vec![lsp::CodeActionKind::QUICKFIX]),
+            work_done_progress_options: Default::default(),
+            resolve_provider: None,
+        },
+    ));
+
+    capabilities.document_formatting_provider = Some(lsp::OneOf::Left(true));
+
+    capabilities.workspace = Some(lsp::WorkspaceServerCapabilities {
+        workspace_folders: Some(lsp::WorkspaceFoldersServerCapabilities {
+            supported: Some(true),
+            change_notifications: Some(lsp::OneOf::Left(true)),
+        }),
+        file_operations: None,
+    });
+
     let server_info = lsp::ServerInfo {
         name: String::from("Rune Language Server"),
         version: None,
     };
 
-    Ok(lsp::InitializeResult {
+    let capabilities = ext::ServerCapabilities {
+        base: capabilities,
+        inlay_hint_provider: Some(true),
+    };
+
+    Ok(ext::InitializeResult {
         capabilities,
         server_info: Some(server_info),
     })
 }
 
 /// Handle initialized notification.
-async fn initialized(_: State, _: Output, _: lsp::InitializedParams) -> Result<()> {
+async fn initialized(state: State, output: Output, _: lsp::InitializedParams) -> Result<()> {
     log::info!("Initialized");
+
+    if state.watch_files() {
+        let watchers = [
+            String::from("**/*.rn"),
+            format!("**/{}", workspace::MANIFEST),
+        ]
+        .iter()
+        .map(|glob_pattern| lsp::FileSystemWatcher {
+            glob_pattern: glob_pattern.clone(),
+            kind: None,
+        })
+        .collect();
+
+        let options = lsp::DidChangeWatchedFilesRegistrationOptions { watchers };
+
+        let registration = lsp::Registration {
+            id: String::from("watch-files"),
+            method: String::from(
+                <lsp::notification::DidChangeWatchedFiles as lsp::notification::Notification>::METHOD,
+            ),
+            register_options: Some(serde_json::to_value(options)?),
+        };
+
+        output
+            .request::<lsp::request::RegisterCapability>(lsp::RegistrationParams {
+                registrations: vec![registration],
+            })
+            .await?;
+    }
+
+    // NB: build the entries of the workspace, even if no source is open.
+    state.rebuild_interest().await?;
     Ok(())
 }
 
+/// Collect the workspace roots from the given initialize parameters.
+///
+/// These are the workspace folders, or the root of the workspace for clients
+/// which don't support them, followed by the paths in the `roots`
+/// initialization option. Relative paths in the option are resolved against
+/// the first root.
+fn roots(params: &lsp::InitializeParams) -> Vec<PathBuf> {
+    let mut roots = match &params.workspace_folders {
+        Some(folders) => folders
+            .iter()
+            .filter_map(|folder| folder.uri.to_file_path().ok())
+            .collect(),
+        None => params
+            .root_uri
+            .as_ref()
+            .and_then(|uri| uri.to_file_path().ok())
+            .into_iter()
+            .collect::<Vec<_>>(),
+    };
+
+    let extra = params
+        .initialization_options
+        .as_ref()
+        .and_then(|options| options.get("roots"))
+        .and_then(|roots| roots.as_array());
+
+    for root in extra.into_iter().flatten().filter_map(|root| root.as_str()) {
+        let root = match roots.first() {
+            Some(first) => first.join(root),
+            None => PathBuf::from(root),
+        };
+
+        roots.push(root);
+    }
+
+    roots
+}
+
 /// Handle initialized notification.
 async fn goto_definition(
     state: State,
@@ -159,6 +292,96 @@ async fn goto_definition(
     Ok(position.map(lsp::GotoDefinitionResponse::Scalar))
 }
 
+/// Handle signature help request.
+async fn signature_help(
+    state: State,
+    _: Output,
+    params: lsp::SignatureHelpParams,
+) -> Result<Option<lsp::SignatureHelp>> {
+    Ok(state
+        .signature_help(
+            &params.text_document_position_params.text_document.uri,
+            params.text_document_position_params.position,
+        )
+        .await)
+}
+
+/// Handle code action request.
+async fn code_action(
+    state: State,
+    _: Output,
+    params: lsp::CodeActionParams,
+) -> Result<Option<lsp::CodeActionResponse>> {
+    let actions = state.code_actions(&params.text_document.uri, &params.context.diagnostics);
+    Ok(Some(actions))
+}
+
+/// Handle formatting request.
+async fn formatting(
+    state: State,
+    _: Output,
+    params: lsp::DocumentFormattingParams,
+) -> Result<Option<Vec<lsp::TextEdit>>> {
+    Ok(state.format(&params.text_document.uri).await)
+}
+
+/// Handle inlay hint request.
+async fn inlay_hint(
+    state: State,
+    _: Output,
+    params: ext::InlayHintParams,
+) -> Result<Option<Vec<ext::InlayHint>>> {
+    Ok(state
+        .inlay_hints(&params.text_document.uri, params.range)
+        .await)
+}
+
+/// Handle changes to watched files.
+async fn did_change_watched_files(
+    state: State,
+    _: Output,
+    params: lsp::DidChangeWatchedFilesParams,
+) -> Result<()> {
+    {
+        let mut workspace = state.workspace_mut().await;
+
+        for change in params.changes {
+            if let Ok(path) = change.uri.to_file_path() {
+                workspace.reload(&path);
+            }
+        }
+    }
+
+    state.rebuild_interest().await?;
+    Ok(())
+}
+
+/// Handle changes to the workspace folders.
+async fn did_change_workspace_folders(
+    state: State,
+    _: Output,
+    params: lsp::DidChangeWorkspaceFoldersParams,
+) -> Result<()> {
+    {
+        let mut workspace = state.workspace_mut().await;
+
+        for folder in params.event.removed {
+            if let Ok(path) = folder.uri.to_file_path() {
+                workspace.remove(&path);
+            }
+        }
+
+        for folder in params.event.added {
+            if let Ok(path) = folder.uri.to_file_path() {
+                workspace.add(path);
+            }
+        }
+    }
+
+    state.rebuild_interest().await?;
+    Ok(())
+}
+
 /// Handle open text document.
 async fn did_open_text_document(
     state: State,
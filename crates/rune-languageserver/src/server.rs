@@ -55,6 +55,11 @@ impl Server {
 
         let method = std::mem::take(&mut incoming.method);
 
+        if method.is_empty() {
+            log::trace!("response to request: {:?}", incoming.id);
+            return Ok(());
+        }
+
         // If server is not initialized, reject incoming requests.
         if !self.state.is_initialized() && method != lsp::request::Initialize::METHOD {
             self.output
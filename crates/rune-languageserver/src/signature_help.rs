@@ -0,0 +1,271 @@
+//! Signature help for calls which are being edited.
+
+use rune::ast;
+use rune::K;
+use runestick::debug::DebugArgs;
+use runestick::{ComponentRef, ContextSignature, Item};
+
+/// The function being called at a call site.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Callee {
+    /// A call to a function by path, like `foo::bar(..)`.
+    Path(Vec<String>),
+    /// A call to an instance function, like `value.bar(..)`.
+    Instance(String),
+}
+
+/// A call site which encloses the cursor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CallSite {
+    /// The function being called.
+    pub(crate) callee: Callee,
+    /// The index of the argument the cursor is currently in, as determined by
+    /// the number of commas preceding it in the call.
+    pub(crate) active: usize,
+}
+
+/// A single open delimiter in the source.
+struct Open {
+    /// The call this delimiter opens, if it's the argument list of a call.
+    callee: Option<Callee>,
+    /// The number of top-level commas seen inside of the delimiter.
+    commas: usize,
+}
+
+/// Find the innermost call site which is still open at the end of `source`.
+///
+/// This operates on tokens rather than the parsed AST, since the call being
+/// edited is typically incomplete and does not parse.
+pub(crate) fn call_site(source: &str) -> Option<CallSite> {
+    let mut lexer = rune::Lexer::new(source);
+    let mut tokens = Vec::new();
+    let mut stack = Vec::<Open>::new();
+
+    while let Some(token) = lexer.next().ok()? {
+        match token.kind {
+            K!['('] => {
+                let callee = callee(source, &tokens);
+                stack.push(Open { callee, commas: 0 });
+            }
+            K!['['] | K!['{'] => {
+                stack.push(Open {
+                    callee: None,
+                    commas: 0,
+                });
+            }
+            K![')'] | K![']'] | K!['}'] => {
+                stack.pop();
+            }
+            K![,] => {
+                if let Some(open) = stack.last_mut() {
+                    open.commas += 1;
+                }
+            }
+            _ => (),
+        }
+
+        tokens.push(token);
+    }
+
+    let open = stack.into_iter().rev().find(|open| open.callee.is_some())?;
+
+    Some(CallSite {
+        callee: open.callee?,
+        active: open.commas,
+    })
+}
+
+/// Determine the callee from the tokens preceding an open parenthesis.
+pub(crate) fn callee(source: &str, tokens: &[ast::Token]) -> Option<Callee> {
+    let mut path = Vec::new();
+    let mut it = tokens.iter().rev();
+
+    while let Some(
+        token @ ast::Token {
+            kind: K![ident], ..
+        },
+    ) = it.next()
+    {
+        path.push(source.get(token.span.range())?.to_owned());
+
+        match it.next().map(|t| t.kind) {
+            Some(K![::]) => continue,
+            Some(K![.]) if path.len() == 1 => return Some(Callee::Instance(path.pop()?)),
+            // NB: this is the declaration of a function, not a call.
+            Some(K![fn]) => return None,
+            _ => break,
+        }
+    }
+
+    if path.is_empty() {
+        return None;
+    }
+
+    path.reverse();
+    Some(Callee::Path(path))
+}
+
+/// A signature which can be presented to the user.
+pub(crate) struct Signature {
+    /// The path of the function.
+    pub(crate) path: String,
+    /// Labels of the parameters of the function.
+    pub(crate) parameters: Vec<String>,
+    /// If the first parameter is the instance the function is called on.
+    pub(crate) instance: bool,
+    /// Lines of documentation of the function.
+    pub(crate) docs: Vec<String>,
+}
+
+impl Signature {
+    /// Build a signature from the debug signature of a script function.
+    pub(crate) fn from_debug(path: &Item, args: &DebugArgs) -> Self {
+        let parameters = match args {
+            DebugArgs::EmptyArgs => Vec::new(),
+            DebugArgs::TupleArgs(n) => (0..*n).map(|n| format!("#{}", n)).collect(),
+            DebugArgs::Named(args) => args.clone(),
+        };
+
+        let instance = parameters.first().map(String::as_str) == Some("self");
+
+        Self {
+            path: path.to_string(),
+            parameters,
+            instance,
+            docs: Vec::new(),
+        }
+    }
+
+    /// Build a signature from a native function.
+    pub(crate) fn from_context(signature: &ContextSignature) -> Self {
+        let (path, this) = match signature {
+            ContextSignature::Function { item, .. } => (item.to_string(), None),
+            ContextSignature::Instance {
+                item,
+                name,
+                self_type_info,
+                ..
+            } => (
+                format!("{}::{}", item, name),
+                Some(format!("self: {}", self_type_info)),
+            ),
+        };
+
+        let instance = this.is_some();
+
+        let args = signature.args().unwrap_or_default();
+        let args = args.into_iter().map(|(name, type_info)| match type_info {
+            Some(type_info) => format!("{}: {}", name, type_info),
+            None => name,
+        });
+
+        Self {
+            path,
+            parameters: this.into_iter().chain(args).collect(),
+            instance,
+            docs: signature.meta().docs.clone(),
+        }
+    }
+
+    /// Convert into signature information for the given call site.
+    pub(crate) fn into_lsp(self, call_site: &CallSite) -> lsp::SignatureInformation {
+        let mut label = format!("{}(", self.path);
+        let mut parameters = Vec::with_capacity(self.parameters.len());
+        let mut it = self.parameters.into_iter().peekable();
+
+        while let Some(parameter) = it.next() {
+            let start = utf16_len(&label);
+            label.push_str(&parameter);
+            let end = utf16_len(&label);
+
+            parameters.push(lsp::ParameterInformation {
+                label: lsp::ParameterLabel::LabelOffsets([start, end]),
+                documentation: None,
+            });
+
+            if it.peek().is_some() {
+                label.push_str(", ");
+            }
+        }
+
+        label.push(')');
+
+        let active = match (&call_site.callee, self.instance) {
+            (Callee::Instance(..), true) => call_site.active + 1,
+            _ => call_site.active,
+        };
+
+        let documentation = if self.docs.is_empty() {
+            None
+        } else {
+            Some(lsp::Documentation::String(self.docs.join("\n")))
+        };
+
+        lsp::SignatureInformation {
+            label,
+            documentation,
+            parameters: Some(parameters),
+            active_parameter: Some(active as u32),
+        }
+    }
+}
+
+/// Test if the given item matches the callee.
+pub(crate) fn matches(callee: &Callee, item: &Item) -> bool {
+    match callee {
+        Callee::Path(path) => {
+            let mut it = item.iter();
+
+            for c in path.iter().rev() {
+                match it.next_back() {
+                    Some(ComponentRef::Str(s)) if s == c => (),
+                    _ => return false,
+                }
+            }
+
+            true
+        }
+        Callee::Instance(name) => {
+            matches!(item.last(), Some(ComponentRef::Str(s)) if s == name)
+        }
+    }
+}
+
+/// The length of the string in UTF-16 code units, as used by LSP offsets.
+fn utf16_len(s: &str) -> u32 {
+    s.encode_utf16().count() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{call_site, CallSite, Callee};
+
+    #[test]
+    fn test_call_site() {
+        assert_eq!(
+            call_site("fn main() { foo::bar(1, [2, 3], "),
+            Some(CallSite {
+                callee: Callee::Path(vec![String::from("foo"), String::from("bar")]),
+                active: 2,
+            })
+        );
+
+        assert_eq!(
+            call_site("fn main() { a.push(b(1, 2), "),
+            Some(CallSite {
+                callee: Callee::Instance(String::from("push")),
+                active: 1,
+            })
+        );
+
+        assert_eq!(
+            call_site("fn main() { a.push(b(1, "),
+            Some(CallSite {
+                callee: Callee::Path(vec![String::from("b")]),
+                active: 1,
+            })
+        );
+
+        assert_eq!(call_site("fn main() { a.push(b(1, 2)); "), None);
+    }
+}
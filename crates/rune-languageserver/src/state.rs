@@ -1,6 +1,11 @@
+use crate::code_action;
+use crate::ext;
+use crate::inlay_hint::{self, HintKind};
+use crate::signature_help::{self, Callee, Signature};
+use crate::workspace::Workspace;
 use crate::Output;
 use anyhow::{anyhow, Result};
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use lsp::Url;
 use ropey::Rope;
 use rune::Spanned as _;
@@ -35,6 +40,8 @@ impl State {
                 options,
                 initialized: Default::default(),
                 sources: Default::default(),
+                workspace: Default::default(),
+                watch_files: Default::default(),
             }),
         }
     }
@@ -54,6 +61,21 @@ impl State {
         self.inner.sources.write().await
     }
 
+    /// Access the workspace roots in the current state.
+    pub(crate) async fn workspace_mut(&self) -> RwLockWriteGuard<'_, Workspace> {
+        self.inner.workspace.write().await
+    }
+
+    /// Set if the client supports dynamically registering file watchers.
+    pub fn set_watch_files(&self, watch_files: bool) {
+        self.inner.watch_files.store(watch_files, Ordering::Release);
+    }
+
+    /// Test if file watchers should be registered with the client.
+    pub fn watch_files(&self) -> bool {
+        self.inner.watch_files.load(Ordering::Acquire)
+    }
+
     /// Indicate interest in having the project rebuild.
     ///
     /// Sources that have been modified will be marked as dirty.
@@ -105,131 +127,210 @@ impl State {
         Some(location)
     }
 
-    /// Rebuild the current project.
-    pub async fn rebuild(&self, output: &Output) -> Result<()> {
-        let mut inner = self.inner.sources.write().await;
+    /// Provide signature help for the call enclosing the given uri and LSP
+    /// position.
+    pub async fn signature_help(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+    ) -> Option<lsp::SignatureHelp> {
+        let sources = self.inner.sources.read().await;
 
-        let mut by_url = HashMap::<Url, Vec<lsp::Diagnostic>>::new();
+        let source = sources.get(uri)?;
+        let offset = source.lsp_position_to_offset(position);
+        let offset = source.content.char_to_byte(offset);
+        let text = source.content.slice(..).to_string();
+        let call_site = signature_help::call_site(text.get(..offset)?)?;
 
-        for (url, _) in inner.removed.drain(..) {
-            by_url.insert(url.clone(), Vec::new());
+        log::trace!("signature help for: {:?}", call_site);
+
+        let mut signatures = Vec::new();
+
+        if let Some(debug_info) = source.unit.as_ref().and_then(|unit| unit.debug_info()) {
+            for signature in debug_info.functions.values() {
+                if signature_help::matches(&call_site.callee, &signature.path) {
+                    signatures.push(Signature::from_debug(&signature.path, &signature.args));
+                }
+            }
         }
 
-        let mut builds = Vec::new();
+        for (_, signature) in self.inner.context.iter_functions() {
+            let matches = match (&call_site.callee, signature) {
+                (Callee::Path(..), runestick::ContextSignature::Function { item, .. }) => {
+                    signature_help::matches(&call_site.callee, item)
+                }
+                (Callee::Instance(name), runestick::ContextSignature::Instance { name: n, .. }) => {
+                    name == n
+                }
+                _ => false,
+            };
 
-        let sources = std::mem::take(&mut inner.sources);
-        let source_loader = Rc::new(SourceLoader::new(sources));
+            if matches {
+                signatures.push(Signature::from_context(signature));
+            }
+        }
 
-        for (url, source) in &inner.sources {
-            log::trace!("build: {}", url);
+        if signatures.is_empty() {
+            return None;
+        }
 
-            by_url.insert(url.clone(), Default::default());
+        signatures.sort_by(|a, b| a.path.cmp(&b.path));
 
-            let mut sources = rune::Sources::new();
+        let signatures = signatures
+            .into_iter()
+            .map(|s| s.into_lsp(&call_site))
+            .collect();
 
-            let mut input = runestick::Source::new(url.to_string(), source.to_string());
-            *input.path_mut() = url.to_file_path().ok();
+        Some(lsp::SignatureHelp {
+            signatures,
+            active_signature: Some(0),
+            active_parameter: None,
+        })
+    }
 
-            sources.insert(input);
+    /// Collect quick fixes for the given diagnostics.
+    pub fn code_actions(
+        &self,
+        uri: &Url,
+        diagnostics: &[lsp::Diagnostic],
+    ) -> Vec<lsp::CodeActionOrCommand> {
+        code_action::code_actions(uri, diagnostics)
+    }
 
-            let mut diagnostics = rune::Diagnostics::new();
-            let visitor = Rc::new(Visitor::new(Index::default()));
+    /// Format the source at the given uri, producing an edit which replaces
+    /// the whole document.
+    pub async fn format(&self, uri: &Url) -> Option<Vec<lsp::TextEdit>> {
+        let sources = self.inner.sources.read().await;
+        let source = sources.get(uri)?;
+        let text = source.content.to_string();
 
-            let result = rune::load_sources_with_visitor(
-                &self.inner.context,
-                &self.inner.options,
-                &mut sources,
-                &mut diagnostics,
-                visitor.clone(),
-                source_loader.clone(),
-            );
+        let formatted = match rune::format_source(&text) {
+            Ok(formatted) => formatted,
+            Err(error) => {
+                log::trace!("failed to format `{}`: {}", uri, error);
+                return None;
+            }
+        };
 
-            if let Err(rune::LoadSourcesError) = result {
-                for diagnostic in diagnostics.diagnostics() {
-                    match diagnostic {
-                        rune::Diagnostic::Error(error) => {
-                            let source_id = error.source_id();
+        if formatted == text {
+            return Some(Vec::new());
+        }
 
-                            match error.kind() {
-                                rune::ErrorKind::ParseError(error) => {
-                                    report(
-                                        &sources,
-                                        &mut by_url,
-                                        error.span(),
-                                        source_id,
-                                        error,
-                                        display_to_error,
-                                    );
-                                }
-                                rune::ErrorKind::CompileError(error) => {
-                                    report(
-                                        &sources,
-                                        &mut by_url,
-                                        error.span(),
-                                        source_id,
-                                        error,
-                                        display_to_error,
-                                    );
-                                }
-                                rune::ErrorKind::QueryError(error) => {
-                                    report(
-                                        &sources,
-                                        &mut by_url,
-                                        error.span(),
-                                        source_id,
-                                        error,
-                                        display_to_error,
-                                    );
-                                }
-                                rune::ErrorKind::LinkError(error) => match error {
-                                    rune::LinkerError::MissingFunction { hash, spans } => {
-                                        for (span, _) in spans {
-                                            let diagnostics =
-                                                by_url.entry(url.clone()).or_default();
-
-                                            let range = source.span_to_lsp_range(*span);
-
-                                            diagnostics.push(display_to_error(
-                                                range,
-                                                format!("missing function with hash `{}`", hash),
-                                            ));
-                                        }
-                                    }
-                                },
-                                rune::ErrorKind::Internal(message) => {
-                                    let diagnostics = by_url.entry(url.clone()).or_default();
+        let range = source.span_to_lsp_range(Span::new(0, text.len()));
+        Some(vec![lsp::TextEdit::new(range, formatted)])
+    }
 
-                                    let range = lsp::Range::default();
-                                    diagnostics.push(display_to_error(range, message));
-                                }
-                                rune::ErrorKind::BuildError(error) => {
-                                    let diagnostics = by_url.entry(url.clone()).or_default();
+    /// Compute inlay hints for the given range of the source at the given
+    /// uri.
+    pub(crate) async fn inlay_hints(
+        &self,
+        uri: &Url,
+        range: lsp::Range,
+    ) -> Option<Vec<ext::InlayHint>> {
+        let sources = self.inner.sources.read().await;
+        let source = sources.get(uri)?;
+        let text = source.content.to_string();
+
+        let start = source
+            .content
+            .char_to_byte(source.lsp_position_to_offset(range.start));
+        let end = source
+            .content
+            .char_to_byte(source.lsp_position_to_offset(range.end));
+
+        let known = inlay_hint::Known {
+            unit: source.unit.as_deref(),
+            context: &self.inner.context,
+            constructors: &source.index.constructors,
+        };
 
-                                    let range = lsp::Range::default();
-                                    diagnostics.push(display_to_error(range, error));
-                                }
-                            }
-                        }
-                        rune::Diagnostic::Warning(warning) => {
-                            report(
-                                &sources,
-                                &mut by_url,
-                                warning.span(),
-                                warning.source_id(),
-                                warning.kind(),
-                                display_to_warning,
-                            );
+        let hints = inlay_hint::hints(&text, &known)
+            .into_iter()
+            .filter(|hint| hint.offset >= start && hint.offset <= end)
+            .map(|hint| {
+                let (kind, padding_left, padding_right) = match hint.kind {
+                    HintKind::Parameter => (ext::InlayHintKind::PARAMETER, None, Some(true)),
+                    HintKind::Type => (ext::InlayHintKind::TYPE, None, None),
+                };
+
+                ext::InlayHint {
+                    position: source.offset_to_lsp_position(hint.offset),
+                    label: hint.label,
+                    kind: Some(kind),
+                    padding_left,
+                    padding_right,
+                }
+            })
+            .collect();
+
+        Some(hints)
+    }
+
+    /// Rebuild the current project.
+    ///
+    /// This builds every entry declared in the manifests of the workspace
+    /// roots, followed by the open sources which weren't loaded as modules of
+    /// any of them.
+    pub async fn rebuild(&self, output: &Output) -> Result<()> {
+        let workspace = self.inner.workspace.read().await;
+        let mut inner = self.inner.sources.write().await;
+
+        let mut by_url = HashMap::<Url, Vec<lsp::Diagnostic>>::new();
+
+        for (url, _) in inner.removed.drain(..) {
+            by_url.insert(url.clone(), Vec::new());
+        }
+
+        for url in inner.published.drain() {
+            by_url.insert(url, Vec::new());
+        }
+
+        for (manifest, error) in workspace.errors() {
+            let position = error
+                .position
+                .map(|(line, col)| lsp::Position::new(line as u32, col as u32))
+                .unwrap_or_default();
+
+            let range = lsp::Range::new(position, position);
+            let diagnostics = by_url.entry(manifest).or_default();
+            diagnostics.push(display_to_error(range, &error.message));
+        }
+
+        let mut builds = Vec::new();
+
+        let sources = std::mem::take(&mut inner.sources);
+        let source_loader = Rc::new(SourceLoader::new(sources));
+
+        for (manifest, url) in workspace.entries() {
+            let input = match source_loader.open(url) {
+                Some(input) => input,
+                None => {
+                    let result = url
+                        .to_file_path()
+                        .map_err(|()| anyhow!("not a file"))
+                        .and_then(|path| Ok(runestick::Source::from_path(&path)?));
+
+                    match result {
+                        Ok(input) => input,
+                        Err(error) => {
+                            let diagnostics = by_url.entry(manifest).or_default();
+
+                            diagnostics.push(display_to_error(
+                                lsp::Range::default(),
+                                format!("failed to load entry `{}`: {}", url, error),
+                            ));
+
+                            continue;
                         }
                     }
                 }
-            }
-
-            let visitor = match Rc::try_unwrap(visitor) {
-                Ok(visitor) => visitor,
-                Err(..) => panic!("visitor should be uniquely held"),
             };
 
-            builds.push((url.clone(), sources, visitor.into_index()));
+            builds.push(self.build(url, input, &source_loader, &mut by_url));
+        }
+
+        for (url, input) in source_loader.unloaded() {
+            builds.push(self.build(&url, input, &source_loader, &mut by_url));
         }
 
         let source_loader = match Rc::try_unwrap(source_loader) {
@@ -239,14 +340,26 @@ impl State {
 
         inner.sources = source_loader.into_sources();
 
-        for (url, build_sources, index) in builds {
-            if let Some(source) = inner.sources.get_mut(&url) {
-                source.index = index;
-                source.build_sources = Some(build_sources);
+        for build in builds {
+            for (url, index) in build.indexes {
+                if let Some(source) = inner.sources.get_mut(&url) {
+                    source.index = index;
+                    source.build_sources = Some(build.sources.clone());
+
+                    // Keep the last successfully built unit around, since it is
+                    // still useful while the source is being edited.
+                    if let Some(unit) = &build.unit {
+                        source.unit = Some(unit.clone());
+                    }
+                }
             }
         }
 
         for (url, diagnostics) in by_url {
+            if !diagnostics.is_empty() {
+                inner.published.insert(url.clone());
+            }
+
             let diagnostics = lsp::PublishDiagnosticsParams {
                 uri: url.clone(),
                 diagnostics,
@@ -260,6 +373,176 @@ impl State {
 
         Ok(())
     }
+
+    /// Build a single entry, collecting its diagnostics and the diagnostics of
+    /// every module it loads by url.
+    fn build(
+        &self,
+        url: &Url,
+        input: runestick::Source,
+        source_loader: &Rc<SourceLoader>,
+        by_url: &mut HashMap<Url, Vec<lsp::Diagnostic>>,
+    ) -> Build {
+        log::trace!("build: {}", url);
+
+        by_url.entry(url.clone()).or_default();
+
+        let mut sources = rune::Sources::new();
+        sources.insert(input);
+
+        let mut diagnostics = rune::Diagnostics::new();
+        let visitor = Rc::new(Visitor::new());
+
+        let result = rune::load_sources_with_visitor(
+            &self.inner.context,
+            &self.inner.options,
+            &mut sources,
+            &mut diagnostics,
+            visitor.clone(),
+            source_loader.clone(),
+        );
+
+        for diagnostic in diagnostics.diagnostics() {
+            match diagnostic {
+                rune::Diagnostic::Error(error) => {
+                    let source_id = error.source_id();
+
+                    match error.kind() {
+                        rune::ErrorKind::ParseError(error) => {
+                            report(
+                                &sources,
+                                by_url,
+                                error.span(),
+                                source_id,
+                                error,
+                                display_to_error,
+                            );
+                        }
+                        rune::ErrorKind::CompileError(error) => {
+                            let diagnostic = report(
+                                &sources,
+                                by_url,
+                                error.span(),
+                                source_id,
+                                error,
+                                display_to_error,
+                            );
+
+                            if let (
+                                Some(diagnostic),
+                                rune::CompileErrorKind::MissingItem { .. },
+                                Some(source),
+                            ) = (diagnostic, error.kind(), sources.get(source_id))
+                            {
+                                let items = visitor.items.borrow();
+
+                                let candidates = self
+                                    .inner
+                                    .context
+                                    .iter_functions()
+                                    .filter_map(|(_, signature)| match signature {
+                                        runestick::ContextSignature::Function { item, .. } => {
+                                            Some(item)
+                                        }
+                                        _ => None,
+                                    })
+                                    .chain(self.inner.context.iter_types().map(|(_, t)| &t.item))
+                                    .chain(items.iter());
+
+                                let fixes = code_action::missing_item_fixes(
+                                    source,
+                                    error.span(),
+                                    candidates,
+                                );
+                                diagnostic.data = code_action::to_data(fixes);
+                            }
+                        }
+                        rune::ErrorKind::QueryError(error) => {
+                            report(
+                                &sources,
+                                by_url,
+                                error.span(),
+                                source_id,
+                                error,
+                                display_to_error,
+                            );
+                        }
+                        rune::ErrorKind::LinkError(error) => match error {
+                            rune::LinkerError::MissingFunction { hash, spans } => {
+                                for (span, source_id) in spans {
+                                    report(
+                                        &sources,
+                                        by_url,
+                                        *span,
+                                        *source_id,
+                                        format!("missing function with hash `{}`", hash),
+                                        display_to_error,
+                                    );
+                                }
+                            }
+                        },
+                        rune::ErrorKind::Internal(message) => {
+                            let diagnostics = by_url.entry(url.clone()).or_default();
+
+                            let range = lsp::Range::default();
+                            diagnostics.push(display_to_error(range, message));
+                        }
+                        rune::ErrorKind::BuildError(error) => {
+                            let diagnostics = by_url.entry(url.clone()).or_default();
+
+                            let range = lsp::Range::default();
+                            diagnostics.push(display_to_error(range, error));
+                        }
+                    }
+                }
+                rune::Diagnostic::Warning(warning) => {
+                    let diagnostic = report(
+                        &sources,
+                        by_url,
+                        warning.span(),
+                        warning.source_id(),
+                        warning.kind(),
+                        display_to_warning,
+                    );
+
+                    if let (Some(diagnostic), Some(source)) =
+                        (diagnostic, sources.get(warning.source_id()))
+                    {
+                        let fixes = code_action::warning_fixes(source, warning.kind());
+                        diagnostic.data = code_action::to_data(fixes);
+                    }
+                }
+            }
+        }
+
+        let visitor = match Rc::try_unwrap(visitor) {
+            Ok(visitor) => visitor,
+            Err(..) => panic!("visitor should be uniquely held"),
+        };
+
+        let mut indexes = Vec::new();
+        let mut source_id = 0;
+
+        while let Some(source) = sources.get(source_id) {
+            if let Some(url) = source
+                .path()
+                .and_then(|path| Url::from_file_path(path).ok())
+            {
+                // NB: make sure diagnostics are cleared for modules that no
+                // longer have any.
+                by_url.entry(url.clone()).or_default();
+                indexes.push((url, visitor.index(source_id)));
+            }
+
+            source_id += 1;
+        }
+
+        Build {
+            sources: Arc::new(sources),
+            indexes,
+            unit: result.ok().map(Arc::new),
+        }
+    }
 }
 
 struct Inner {
@@ -274,6 +557,10 @@ struct Inner {
     initialized: AtomicBool,
     /// Sources used in the project.
     sources: RwLock<Sources>,
+    /// The roots of the workspace.
+    workspace: RwLock<Workspace>,
+    /// If the client supports dynamically registering file watchers.
+    watch_files: AtomicBool,
 }
 
 /// A collection of open sources.
@@ -283,6 +570,8 @@ pub struct Sources {
     sources: HashMap<Url, Source>,
     /// A source that has been removed.
     removed: Vec<(Url, Source)>,
+    /// Urls which diagnostics were last published for.
+    published: HashSet<Url>,
 }
 
 impl Sources {
@@ -292,6 +581,7 @@ impl Sources {
             content: Rope::from(text),
             index: Default::default(),
             build_sources: None,
+            unit: None,
         };
 
         self.sources.insert(url, source)
@@ -323,7 +613,10 @@ pub struct Source {
     index: Index,
     /// Loaded Rune sources for this source file. Will be present after the
     /// source file has been built.
-    build_sources: Option<rune::Sources>,
+    build_sources: Option<Arc<rune::Sources>>,
+    /// The last unit which was successfully built from this source, or from
+    /// the entry it was loaded as a module by.
+    unit: Option<Arc<runestick::Unit>>,
 }
 
 impl Source {
@@ -395,7 +688,7 @@ impl fmt::Display for Source {
 }
 
 /// Convert the given span into an lsp range.
-fn span_to_lsp_range(source: &runestick::Source, span: Span) -> Option<lsp::Range> {
+pub(crate) fn span_to_lsp_range(source: &runestick::Source, span: Span) -> Option<lsp::Range> {
     let (line, character) = source.position_to_utf16cu_line_char(span.start.into_usize())?;
     let start = lsp::Position::new(line as u32, character as u32);
     let (line, character) = source.position_to_utf16cu_line_char(span.end.into_usize())?;
@@ -433,37 +726,27 @@ fn rope_utf16_position(rope: &Rope, position: lsp::Position) -> Result<usize> {
 }
 
 /// Convert the given span and error into an error diagnostic.
-fn report<E, R>(
+///
+/// Returns the reported diagnostic, if it could be reported.
+fn report<'a, E, R>(
     sources: &rune::Sources,
-    by_url: &mut HashMap<Url, Vec<lsp::Diagnostic>>,
+    by_url: &'a mut HashMap<Url, Vec<lsp::Diagnostic>>,
     span: Span,
     source_id: usize,
     error: E,
     report: R,
-) where
+) -> Option<&'a mut lsp::Diagnostic>
+where
     E: fmt::Display,
     R: Fn(lsp::Range, E) -> lsp::Diagnostic,
 {
-    let source = match sources.get(source_id) {
-        Some(source) => &*source,
-        None => return,
-    };
-
-    let url = match source.path() {
-        Some(path) => match Url::from_file_path(path) {
-            Ok(url) => url,
-            Err(()) => return,
-        },
-        None => return,
-    };
-
-    let range = match span_to_lsp_range(&*source, span) {
-        Some(range) => range,
-        None => return,
-    };
+    let source = sources.get(source_id)?;
+    let url = Url::from_file_path(source.path()?).ok()?;
+    let range = span_to_lsp_range(source, span)?;
 
     let diagnostics = by_url.entry(url).or_default();
     diagnostics.push(report(range, error));
+    diagnostics.last_mut()
 }
 
 /// Convert the given span and error into an error diagnostic.
@@ -508,6 +791,9 @@ where
 pub struct Index {
     /// Spans mapping to their corresponding definitions.
     definitions: BTreeMap<Span, Definition>,
+    /// Structs and variants which have been registered while building, and
+    /// the type they construct.
+    constructors: Vec<(Item, Item)>,
 }
 
 #[derive(Debug, Clone)]
@@ -542,30 +828,82 @@ pub enum DefinitionKind {
     Module,
 }
 
+/// The result of building a single entry.
+struct Build {
+    /// The sources loaded by the build.
+    sources: Arc<rune::Sources>,
+    /// Indexes for every loaded source which has a url.
+    indexes: Vec<(Url, Index)>,
+    /// The unit, if it was successfully built.
+    unit: Option<Arc<runestick::Unit>>,
+}
+
 struct Visitor {
-    index: RefCell<Index>,
+    /// Items which have been registered.
+    items: RefCell<Vec<Item>>,
+    /// Structs and variants which have been registered, and the type they
+    /// construct.
+    constructors: RefCell<Vec<(Item, Item)>>,
+    /// Definitions by the source they are used in.
+    definitions: RefCell<HashMap<SourceId, BTreeMap<Span, Definition>>>,
 }
 
 impl Visitor {
     /// Construct a new visitor.
-    pub fn new(index: Index) -> Self {
+    pub fn new() -> Self {
         Self {
-            index: RefCell::new(index),
+            items: RefCell::new(Vec::new()),
+            constructors: RefCell::new(Vec::new()),
+            definitions: RefCell::new(HashMap::new()),
         }
     }
 
-    /// Convert visitor back into an index.
-    pub fn into_index(self) -> Index {
-        self.index.into_inner()
+    /// Construct the index for the given source.
+    pub fn index(&self, source_id: SourceId) -> Index {
+        Index {
+            definitions: self
+                .definitions
+                .borrow_mut()
+                .remove(&source_id)
+                .unwrap_or_default(),
+            constructors: self.constructors.borrow().clone(),
+        }
+    }
+
+    /// Insert a definition used in the given source.
+    fn insert(&self, source_id: SourceId, span: Span, definition: Definition) {
+        let mut definitions = self.definitions.borrow_mut();
+
+        if let Some(d) = definitions
+            .entry(source_id)
+            .or_default()
+            .insert(span, definition)
+        {
+            log::warn!("replaced definition: {:?}", d.kind)
+        }
     }
 }
 
 impl rune::CompileVisitor for Visitor {
-    fn visit_meta(&self, source_id: SourceId, meta: &CompileMeta, span: Span) {
-        if source_id != 0 {
-            return;
-        }
+    fn register_meta(&self, meta: &CompileMeta) {
+        self.items.borrow_mut().push(meta.item.item.clone());
+
+        let constructed = match &meta.kind {
+            CompileMetaKind::UnitStruct { .. }
+            | CompileMetaKind::TupleStruct { .. }
+            | CompileMetaKind::Struct { .. } => &meta.item.item,
+            CompileMetaKind::UnitVariant { enum_item, .. }
+            | CompileMetaKind::TupleVariant { enum_item, .. }
+            | CompileMetaKind::StructVariant { enum_item, .. } => enum_item,
+            _ => return,
+        };
 
+        self.constructors
+            .borrow_mut()
+            .push((meta.item.item.clone(), constructed.clone()));
+    }
+
+    fn visit_meta(&self, source_id: SourceId, meta: &CompileMeta, span: Span) {
         let source = match meta.source.as_ref() {
             Some(source) => source,
             None => return,
@@ -588,16 +926,10 @@ impl rune::CompileVisitor for Visitor {
             source: source.clone(),
         };
 
-        if let Some(d) = self.index.borrow_mut().definitions.insert(span, definition) {
-            log::warn!("replaced definition: {:?}", d.kind)
-        }
+        self.insert(source_id, span, definition);
     }
 
     fn visit_variable_use(&self, source_id: SourceId, var_span: Span, span: Span) {
-        if source_id != 0 {
-            return;
-        }
-
         let definition = Definition {
             kind: DefinitionKind::Local,
             source: CompileSource {
@@ -607,9 +939,7 @@ impl rune::CompileVisitor for Visitor {
             },
         };
 
-        if let Some(d) = self.index.borrow_mut().definitions.insert(span, definition) {
-            log::warn!("replaced definition: {:?}", d.kind)
-        }
+        self.insert(source_id, span, definition);
     }
 
     fn visit_mod(&self, source_id: SourceId, span: Span) {
@@ -626,14 +956,14 @@ impl rune::CompileVisitor for Visitor {
             },
         };
 
-        if let Some(d) = self.index.borrow_mut().definitions.insert(span, definition) {
-            log::warn!("replaced definition: {:?}", d.kind)
-        }
+        self.insert(source_id, span, definition);
     }
 }
 
 struct SourceLoader {
     sources: RefCell<HashMap<Url, Source>>,
+    /// Open sources which have been loaded by a build.
+    loaded: RefCell<HashSet<Url>>,
     base: rune::FileSourceLoader,
 }
 
@@ -642,6 +972,7 @@ impl SourceLoader {
     pub fn new(sources: HashMap<Url, Source>) -> Self {
         Self {
             sources: RefCell::new(sources),
+            loaded: RefCell::new(HashSet::new()),
             base: rune::FileSourceLoader::new(),
         }
     }
@@ -651,23 +982,44 @@ impl SourceLoader {
         self.sources.into_inner()
     }
 
-    /// Generate a collection of URl candidates.
-    fn candidates(root: &Path, item: &Item) -> Option<[Url; 2]> {
-        let mut base = root.to_owned();
+    /// Load the open source at the given url, marking it as loaded.
+    fn open(&self, url: &Url) -> Option<runestick::Source> {
+        let sources = self.sources.borrow();
+        let source = sources.get(url)?;
 
-        let mut it = item.iter().peekable();
-        let mut last = None;
+        let mut input = runestick::Source::new(url, source.to_string());
+        *input.path_mut() = url.to_file_path().ok();
 
-        while let Some(c) = it.next() {
-            if it.peek().is_none() {
-                last = match c {
-                    ComponentRef::Str(string) => Some(string),
-                    _ => return None,
-                };
+        self.loaded.borrow_mut().insert(url.clone());
+        Some(input)
+    }
 
-                break;
-            }
+    /// Load all open sources which haven't been loaded yet.
+    fn unloaded(&self) -> Vec<(Url, runestick::Source)> {
+        let urls = self
+            .sources
+            .borrow()
+            .keys()
+            .filter(|url| !self.loaded.borrow().contains(*url))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        urls.into_iter()
+            .filter_map(|url| {
+                let input = self.open(&url)?;
+                Some((url, input))
+            })
+            .collect()
+    }
 
+    /// Generate a collection of URl candidates, in the same order as they are
+    /// tried when loading from the filesystem.
+    fn candidates(root: &Path, item: &Item) -> Option<[Url; 2]> {
+        // NB: the root is the path of the entry, and modules are loaded
+        // relative to the directory it's in.
+        let mut base = root.parent()?.to_owned();
+
+        for c in item {
             if let ComponentRef::Str(string) = c {
                 base.push(string);
             } else {
@@ -675,18 +1027,8 @@ impl SourceLoader {
             }
         }
 
-        let last = last?;
-
-        let mut a = base.clone();
-        a.push(&format!("{}.rn", last));
-
-        let mut b = base;
-        b.push(last);
-        b.push("mod.rn");
-
-        let a = Url::from_file_path(&a).ok()?;
-        let b = Url::from_file_path(&b).ok()?;
-
+        let a = Url::from_file_path(base.join("mod.rn")).ok()?;
+        let b = Url::from_file_path(base.with_extension("rn")).ok()?;
         Some([a, b])
     }
 }
@@ -702,8 +1044,8 @@ impl rune::SourceLoader for SourceLoader {
 
         if let Some(candidates) = Self::candidates(root, item) {
             for url in candidates.iter() {
-                if let Some(s) = self.sources.borrow().get(url) {
-                    return Ok(runestick::Source::new(url.to_string(), s.to_string()));
+                if let Some(source) = self.open(url) {
+                    return Ok(source);
                 }
             }
         }
@@ -711,3 +1053,26 @@ impl rune::SourceLoader for SourceLoader {
         self.base.load(root, item, span)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::SourceLoader;
+    use lsp::Url;
+    use runestick::Item;
+    use std::path::Path;
+
+    #[test]
+    fn test_candidates() {
+        let root = if cfg!(windows) {
+            Path::new("C:\\project\\main.rn")
+        } else {
+            Path::new("/project/main.rn")
+        };
+
+        let [a, b] = SourceLoader::candidates(root, &Item::with_item(&["foo", "bar"])).unwrap();
+
+        let root = Url::from_file_path(root).unwrap();
+        assert_eq!(a, root.join("foo/bar/mod.rn").unwrap());
+        assert_eq!(b, root.join("foo/bar.rn").unwrap());
+    }
+}
@@ -0,0 +1,209 @@
+//! Workspace roots, and the manifests declaring the entry points to build in
+//! them.
+//!
+//! A root may contain a `Rune.toml` manifest, like:
+//!
+//! ```toml
+//! [workspace]
+//! entries = ["main.rn", "scripts/tool.rn"]
+//! ```
+//!
+//! Every entry is built when the project is rebuilt regardless of whether it's
+//! open or not, together with all modules it loads.
+
+use lsp::Url;
+use serde::Deserialize;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The name of the manifest file in a workspace root.
+pub(crate) const MANIFEST: &str = "Rune.toml";
+
+/// The contents of a manifest.
+#[derive(Debug, Default, Deserialize)]
+struct Manifest {
+    #[serde(default)]
+    workspace: ManifestWorkspace,
+}
+
+/// The `[workspace]` section of a manifest.
+#[derive(Debug, Default, Deserialize)]
+struct ManifestWorkspace {
+    /// Entry points, relative to the root.
+    #[serde(default)]
+    entries: Vec<PathBuf>,
+}
+
+/// An error raised when loading a manifest.
+#[derive(Debug)]
+pub(crate) struct ManifestError {
+    /// The zero-based line and column of the error, if known.
+    pub(crate) position: Option<(usize, usize)>,
+    /// The message of the error.
+    pub(crate) message: String,
+}
+
+/// A single workspace root.
+#[derive(Debug)]
+pub(crate) struct Root {
+    /// The path to the root.
+    path: PathBuf,
+    /// Entries declared in the manifest of the root.
+    entries: Vec<Url>,
+    /// An error raised when loading the manifest.
+    error: Option<ManifestError>,
+}
+
+impl Root {
+    /// Load the root at the given path, including its manifest if present.
+    fn load(path: PathBuf) -> Self {
+        let mut root = Self {
+            path,
+            entries: Vec::new(),
+            error: None,
+        };
+
+        root.reload();
+        root
+    }
+
+    /// The path to the manifest of the root.
+    fn manifest_path(&self) -> PathBuf {
+        self.path.join(MANIFEST)
+    }
+
+    /// Reload the manifest of the root.
+    fn reload(&mut self) {
+        self.entries.clear();
+        self.error = None;
+
+        let text = match fs::read_to_string(self.manifest_path()) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return,
+            Err(error) => {
+                self.error = Some(ManifestError {
+                    position: None,
+                    message: error.to_string(),
+                });
+
+                return;
+            }
+        };
+
+        match parse_entries(&self.path, &text) {
+            Ok(entries) => self.entries = entries,
+            Err(error) => self.error = Some(error),
+        }
+    }
+}
+
+/// The roots of the workspace.
+#[derive(Debug, Default)]
+pub(crate) struct Workspace {
+    roots: Vec<Root>,
+}
+
+impl Workspace {
+    /// Add a root to the workspace, unless it's already present.
+    pub(crate) fn add(&mut self, path: PathBuf) {
+        if self.roots.iter().any(|root| root.path == path) {
+            return;
+        }
+
+        log::trace!("add root: {}", path.display());
+        self.roots.push(Root::load(path));
+    }
+
+    /// Remove the root at the given path.
+    pub(crate) fn remove(&mut self, path: &Path) {
+        self.roots.retain(|root| root.path != path);
+    }
+
+    /// Reload the manifest at the given path, returning `true` if it belonged
+    /// to one of the roots.
+    pub(crate) fn reload(&mut self, manifest: &Path) -> bool {
+        let mut reloaded = false;
+
+        for root in &mut self.roots {
+            if root.manifest_path() == manifest {
+                root.reload();
+                reloaded = true;
+            }
+        }
+
+        reloaded
+    }
+
+    /// Iterate over all entries in the workspace, together with the url of
+    /// the manifest which declared them.
+    pub(crate) fn entries(&self) -> impl Iterator<Item = (Url, &Url)> + '_ {
+        self.roots.iter().flat_map(|root| {
+            let manifest = Url::from_file_path(root.manifest_path()).ok();
+
+            root.entries
+                .iter()
+                .filter_map(move |entry| Some((manifest.clone()?, entry)))
+        })
+    }
+
+    /// Iterate over errors raised when loading manifests, together with the
+    /// url of the manifest.
+    pub(crate) fn errors(&self) -> impl Iterator<Item = (Url, &ManifestError)> + '_ {
+        self.roots.iter().filter_map(|root| {
+            let error = root.error.as_ref()?;
+            let manifest = Url::from_file_path(root.manifest_path()).ok()?;
+            Some((manifest, error))
+        })
+    }
+}
+
+/// Parse the entries of the manifest in the given root.
+fn parse_entries(root: &Path, text: &str) -> Result<Vec<Url>, ManifestError> {
+    let manifest: Manifest = toml::from_str(text).map_err(|error| ManifestError {
+        position: error.line_col(),
+        message: error.to_string(),
+    })?;
+
+    let mut entries = Vec::new();
+
+    for entry in manifest.workspace.entries {
+        let path = root.join(&entry);
+
+        match Url::from_file_path(&path) {
+            Ok(url) => entries.push(url),
+            Err(()) => {
+                return Err(ManifestError {
+                    position: None,
+                    message: format!("bad entry `{}`", entry.display()),
+                })
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_entries;
+    use std::path::Path;
+
+    #[test]
+    fn test_parse_entries() {
+        let root = if cfg!(windows) {
+            Path::new("C:\\project")
+        } else {
+            Path::new("/project")
+        };
+
+        let entries = parse_entries(root, "[workspace]\nentries = [\"main.rn\"]\n").unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].path().ends_with("/project/main.rn"));
+
+        assert!(parse_entries(root, "").unwrap().is_empty());
+
+        let error = parse_entries(root, "[workspace]\nentries = 42\n").unwrap_err();
+        assert_eq!(error.position.map(|(line, _)| line), Some(1));
+    }
+}
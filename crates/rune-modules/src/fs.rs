@@ -25,20 +25,615 @@
 //! ```rust,ignore
 //! fn main() {
 //!     let file = fs::read_to_string("file.txt").await?;
-//!     println(`{file}`);
+//!     println(`${file}`);
+//!
+//!     fs::create_dir_all("out").await?;
+//!     fs::write("out/file.txt", file).await?;
+//!
+//!     let entries = fs::read_dir("out").await?;
+//!
+//!     while let Some(entry) = entries.next().await {
+//!         let entry = entry?;
+//!         let metadata = entry.metadata().await?;
+//!         println(`${entry.path()}: ${metadata.len()}`);
+//!     }
+//!
+//!     let file = fs::File::open("out/file.txt").await?;
+//!
+//!     while let Some(line) = file.read_line().await? {
+//!         println(line);
+//!     }
 //! }
 //! ```
+//!
+//! All operations which can fail return a `Result` with an [fs::Error][Error]
+//! as its error, which can be displayed and has a `kind` describing the kind
+//! of error, like `"NotFound"`.
+//!
+//! The entries of a directory read through `read_dir` are a `Stream`, which
+//! produces a `Result` for each entry read.
+//!
+//! ## Sandboxing
+//!
+//! The module constructed through [module] has access to the whole filesystem.
+//! To restrict which paths scripts can access, construct it with a [Policy]
+//! through [module_with_policy] instead:
+//!
+//! ```rust
+//! use rune_modules::fs::{Access, Policy};
+//!
+//! # fn main() -> runestick::Result<()> {
+//! let policy = Policy::new()
+//!     .allow("data", Access::Read)
+//!     .allow("out", Access::ReadWrite);
+//!
+//! let mut context = runestick::Context::with_default_modules()?;
+//! context.install(&rune_modules::fs::module_with_policy(policy)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Operations which are not permitted by the policy fail with an error which
+//! has the kind `"Denied"`, and for which `is_denied()` returns `true`.
 
+use runestick::{Any, Bytes, ContextError, Module, Protocol, Stream, ToValue as _, Value, VmError};
+use std::fmt;
+use std::fmt::Write as _;
 use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
 use tokio::fs;
+use tokio::io::{AsyncBufReadExt as _, AsyncReadExt as _, AsyncWriteExt as _, BufStream};
+
+/// Construct the `fs` module, with unrestricted access to the filesystem.
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    module_with_policy(Policy::unrestricted())
+}
+
+/// Construct the `fs` module, where access to the filesystem is restricted by
+/// the given [Policy].
+pub fn module_with_policy(policy: Policy) -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("fs");
+    let policy = Arc::new(policy);
+
+    module.ty::<Error>()?;
+    module.ty::<Metadata>()?;
+    module.ty::<DirEntry>()?;
+    module.ty::<File>()?;
+
+    module.async_function(&["read"], {
+        let policy = policy.clone();
+        move |path: String| read(policy.clone(), path)
+    })?;
+    module.async_function(&["read_to_string"], {
+        let policy = policy.clone();
+        move |path: String| read_to_string(policy.clone(), path)
+    })?;
+    module.async_function(&["write"], {
+        let policy = policy.clone();
+        move |path: String, contents: Value| write(policy.clone(), path, contents)
+    })?;
+    module.async_function(&["append"], {
+        let policy = policy.clone();
+        move |path: String, contents: Value| append(policy.clone(), path, contents)
+    })?;
+    module.async_function(&["create_dir_all"], {
+        let policy = policy.clone();
+        move |path: String| create_dir_all(policy.clone(), path)
+    })?;
+    module.async_function(&["remove_file"], {
+        let policy = policy.clone();
+        move |path: String| remove_file(policy.clone(), path)
+    })?;
+    module.async_function(&["remove_dir_all"], {
+        let policy = policy.clone();
+        move |path: String| remove_dir_all(policy.clone(), path)
+    })?;
+    module.async_function(&["rename"], {
+        let policy = policy.clone();
+        move |from: String, to: String| rename(policy.clone(), from, to)
+    })?;
+    module.async_function(&["copy"], {
+        let policy = policy.clone();
+        move |from: String, to: String| copy(policy.clone(), from, to)
+    })?;
+    module.async_function(&["metadata"], {
+        let policy = policy.clone();
+        move |path: String| metadata(policy.clone(), path)
+    })?;
+    module.async_function(&["read_dir"], {
+        let policy = policy.clone();
+        move |path: String| read_dir(policy.clone(), path)
+    })?;
+    module.async_function(&["File", "open"], {
+        let policy = policy.clone();
+        move |path: String| File::open(policy.clone(), path)
+    })?;
+    module.async_function(&["File", "create"], {
+        let policy = policy.clone();
+        move |path: String| File::create(policy.clone(), path)
+    })?;
 
-/// Construct the `fs` module.
-pub fn module(_stdio: bool) -> Result<runestick::Module, runestick::ContextError> {
-    let mut module = runestick::Module::with_crate("fs");
-    module.async_function(&["read_to_string"], read_to_string)?;
+    module.inst_fn("kind", Error::kind)?;
+    module.inst_fn("is_denied", Error::is_denied)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, Error::display)?;
+    module.inst_fn(Protocol::STRING_DEBUG, Error::debug)?;
+
+    module.inst_fn("len", Metadata::len)?;
+    module.inst_fn("is_dir", Metadata::is_dir)?;
+    module.inst_fn("is_file", Metadata::is_file)?;
+    module.inst_fn("modified", Metadata::modified)?;
+
+    module.inst_fn("path", DirEntry::path)?;
+    module.inst_fn("file_name", DirEntry::file_name)?;
+    module.async_inst_fn("metadata", DirEntry::metadata)?;
+
+    module.async_inst_fn("read_line", File::read_line)?;
+    module.async_inst_fn("read_to_string", File::read_to_string)?;
+    module.async_inst_fn("write", File::write)?;
+    module.async_inst_fn("flush", File::flush)?;
     Ok(module)
 }
 
-async fn read_to_string(path: &str) -> io::Result<String> {
-    fs::read_to_string(path).await
+/// The kind of access to a path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    /// Reading files, metadata and directory listings.
+    Read,
+    /// Creating, modifying and removing files and directories.
+    Write,
+    /// Both reading and writing.
+    ReadWrite,
+}
+
+impl Access {
+    /// Test if this access permits the given access.
+    fn permits(self, access: Access) -> bool {
+        matches!(
+            (self, access),
+            (Access::ReadWrite, _) | (Access::Read, Access::Read) | (Access::Write, Access::Write)
+        )
+    }
+}
+
+impl fmt::Display for Access {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Access::Read => write!(f, "read"),
+            Access::Write => write!(f, "write"),
+            Access::ReadWrite => write!(f, "read and write"),
+        }
+    }
+}
+
+/// A policy restricting which paths the `fs` module can access.
+///
+/// Paths are resolved to absolute paths before being checked, with symbolic
+/// links in their existing parts followed, so that a script can't escape an
+/// allowed directory through `..` components or links. Relative paths are
+/// resolved against the current directory, both when added to the policy and
+/// when checked.
+///
+/// Note that this is a best-effort restriction on what scripts can ask for,
+/// and not a replacement for sandboxing the process itself.
+#[derive(Debug, Clone)]
+pub struct Policy {
+    /// Allowed path prefixes and the access permitted to them, or `None` if
+    /// access is unrestricted.
+    rules: Option<Vec<(PathBuf, Access)>>,
+}
+
+impl Policy {
+    /// Construct a policy which denies access to all paths.
+    pub fn new() -> Self {
+        Self {
+            rules: Some(Vec::new()),
+        }
+    }
+
+    /// Construct a policy which permits access to all paths.
+    pub fn unrestricted() -> Self {
+        Self { rules: None }
+    }
+
+    /// Permit the given access to the path and everything below it.
+    ///
+    /// This has no effect on an unrestricted policy.
+    pub fn allow<P>(mut self, path: P, access: Access) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        if let Some(rules) = &mut self.rules {
+            rules.push((resolve(path.as_ref()), access));
+        }
+
+        self
+    }
+
+    /// Check that the given access to a path is permitted.
+    fn check(&self, path: &str, access: Access) -> Result<(), Error> {
+        let rules = match &self.rules {
+            Some(rules) => rules,
+            None => return Ok(()),
+        };
+
+        let resolved = resolve(Path::new(path));
+
+        let permitted = rules
+            .iter()
+            .any(|(prefix, allowed)| resolved.starts_with(prefix) && allowed.permits(access));
+
+        if permitted {
+            Ok(())
+        } else {
+            Err(Error::denied(path, access))
+        }
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Resolve the given path into an absolute path, following symbolic links in
+/// the part of it which exists.
+fn resolve(path: &Path) -> PathBuf {
+    let path = match std::env::current_dir() {
+        Ok(current_dir) => current_dir.join(path),
+        Err(..) => path.to_owned(),
+    };
+
+    let components = path.components().collect::<Vec<_>>();
+
+    for n in (1..=components.len()).rev() {
+        let existing = components[..n].iter().collect::<PathBuf>();
+
+        if let Ok(mut resolved) = std::fs::canonicalize(existing) {
+            // NB: components which don't exist can't be links, so it's fine
+            // to resolve the remaining ones lexically.
+            for component in &components[n..] {
+                match component {
+                    Component::ParentDir => {
+                        resolved.pop();
+                    }
+                    Component::CurDir => (),
+                    component => resolved.push(component),
+                }
+            }
+
+            return resolved;
+        }
+    }
+
+    path
+}
+
+/// An error raised by a filesystem operation.
+#[derive(Debug, Any)]
+pub struct Error {
+    inner: ErrorKind,
+}
+
+#[derive(Debug)]
+enum ErrorKind {
+    Io(io::Error),
+    Denied { path: String, access: Access },
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self {
+            inner: ErrorKind::Io(error),
+        }
+    }
+}
+
+impl Error {
+    /// Construct an error for access denied by the policy.
+    fn denied(path: &str, access: Access) -> Self {
+        Self {
+            inner: ErrorKind::Denied {
+                path: path.to_owned(),
+                access,
+            },
+        }
+    }
+
+    /// The kind of the error, like `"NotFound"` or `"PermissionDenied"`.
+    ///
+    /// Access which was denied by the policy of the module has the kind
+    /// `"Denied"`.
+    fn kind(&self) -> String {
+        match &self.inner {
+            ErrorKind::Io(error) => format!("{:?}", error.kind()),
+            ErrorKind::Denied { .. } => String::from("Denied"),
+        }
+    }
+
+    /// Test if the error was raised because access was denied by the policy
+    /// of the module.
+    fn is_denied(&self) -> bool {
+        matches!(self.inner, ErrorKind::Denied { .. })
+    }
+
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        match &self.inner {
+            ErrorKind::Io(error) => write!(buf, "{}", error),
+            ErrorKind::Denied { path, access } => {
+                write!(buf, "access denied: cannot {} `{}`", access, path)
+            }
+        }
+    }
+
+    fn debug(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{:?}", self.inner)
+    }
+}
+
+/// Metadata about a file or directory.
+#[derive(Debug, Any)]
+struct Metadata {
+    inner: std::fs::Metadata,
+}
+
+impl Metadata {
+    /// The size of the file in bytes.
+    fn len(&self) -> u64 {
+        self.inner.len()
+    }
+
+    /// Test if the metadata is for a directory.
+    fn is_dir(&self) -> bool {
+        self.inner.is_dir()
+    }
+
+    /// Test if the metadata is for a regular file.
+    fn is_file(&self) -> bool {
+        self.inner.is_file()
+    }
+
+    /// The last modification time, as seconds since the Unix epoch.
+    ///
+    /// Returns `None` if it's not available on the current platform.
+    fn modified(&self) -> Option<f64> {
+        let modified = self.inner.modified().ok()?;
+        let duration = modified.duration_since(UNIX_EPOCH).ok()?;
+        Some(duration.as_secs_f64())
+    }
+}
+
+/// An entry in a directory.
+#[derive(Debug, Any)]
+struct DirEntry {
+    inner: fs::DirEntry,
+}
+
+impl DirEntry {
+    /// The full path of the entry.
+    fn path(&self) -> String {
+        self.inner.path().to_string_lossy().into_owned()
+    }
+
+    /// The file name of the entry.
+    fn file_name(&self) -> String {
+        self.inner.file_name().to_string_lossy().into_owned()
+    }
+
+    /// Read the metadata of the entry.
+    async fn metadata(&self) -> Result<Metadata, Error> {
+        let inner = self.inner.metadata().await?;
+        Ok(Metadata { inner })
+    }
+}
+
+/// An open file, with buffered reading and writing.
+#[derive(Debug, Any)]
+struct File {
+    inner: BufStream<fs::File>,
+}
+
+impl File {
+    /// Open an existing file for reading.
+    async fn open(policy: Arc<Policy>, path: String) -> Result<Self, Error> {
+        policy.check(&path, Access::Read)?;
+        let file = fs::File::open(path).await?;
+
+        Ok(Self {
+            inner: BufStream::new(file),
+        })
+    }
+
+    /// Create a file for writing, truncating it if it already exists.
+    async fn create(policy: Arc<Policy>, path: String) -> Result<Self, Error> {
+        policy.check(&path, Access::Write)?;
+        let file = fs::File::create(path).await?;
+
+        Ok(Self {
+            inner: BufStream::new(file),
+        })
+    }
+
+    /// Read the next line from the file, without the trailing newline.
+    ///
+    /// Returns `None` once the end of the file has been reached.
+    async fn read_line(&mut self) -> Result<Option<String>, Error> {
+        let mut line = String::new();
+
+        if self.inner.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+
+        if line.ends_with('\n') {
+            line.pop();
+
+            if line.ends_with('\r') {
+                line.pop();
+            }
+        }
+
+        Ok(Some(line))
+    }
+
+    /// Read the remainder of the file into a string.
+    async fn read_to_string(&mut self) -> Result<String, Error> {
+        let mut string = String::new();
+        self.inner.read_to_string(&mut string).await?;
+        Ok(string)
+    }
+
+    /// Write the given string or bytes to the file.
+    ///
+    /// Writes are buffered until the file is flushed.
+    async fn write(&mut self, contents: Value) -> Result<Result<(), Error>, VmError> {
+        let contents = contents_to_vec(contents)?;
+        Ok(self.inner.write_all(&contents).await.map_err(Error::from))
+    }
+
+    /// Flush buffered writes to the file.
+    async fn flush(&mut self) -> Result<(), Error> {
+        self.inner.flush().await?;
+        Ok(())
+    }
+}
+
+/// Read the contents of the file at the given path as bytes.
+async fn read(policy: Arc<Policy>, path: String) -> Result<Bytes, Error> {
+    policy.check(&path, Access::Read)?;
+    Ok(Bytes::from_vec(fs::read(path).await?))
+}
+
+/// Read the contents of the file at the given path as a string.
+async fn read_to_string(policy: Arc<Policy>, path: String) -> Result<String, Error> {
+    policy.check(&path, Access::Read)?;
+    Ok(fs::read_to_string(path).await?)
+}
+
+/// Write the given string or bytes to the file at the given path, replacing
+/// its contents.
+async fn write(
+    policy: Arc<Policy>,
+    path: String,
+    contents: Value,
+) -> Result<Result<(), Error>, VmError> {
+    let contents = contents_to_vec(contents)?;
+
+    if let Err(error) = policy.check(&path, Access::Write) {
+        return Ok(Err(error));
+    }
+
+    Ok(fs::write(path, contents).await.map_err(Error::from))
+}
+
+/// Append the given string or bytes to the file at the given path, creating
+/// it if it doesn't exist.
+async fn append(
+    policy: Arc<Policy>,
+    path: String,
+    contents: Value,
+) -> Result<Result<(), Error>, VmError> {
+    let contents = contents_to_vec(contents)?;
+
+    if let Err(error) = policy.check(&path, Access::Write) {
+        return Ok(Err(error));
+    }
+
+    let file = fs::OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(path)
+        .await;
+
+    let mut file = match file {
+        Ok(file) => file,
+        Err(error) => return Ok(Err(Error::from(error))),
+    };
+
+    Ok(write_all(&mut file, &contents).await)
+}
+
+/// Recursively create a directory and all of its missing parents.
+async fn create_dir_all(policy: Arc<Policy>, path: String) -> Result<(), Error> {
+    policy.check(&path, Access::Write)?;
+    Ok(fs::create_dir_all(path).await?)
+}
+
+/// Remove the file at the given path.
+async fn remove_file(policy: Arc<Policy>, path: String) -> Result<(), Error> {
+    policy.check(&path, Access::Write)?;
+    Ok(fs::remove_file(path).await?)
+}
+
+/// Remove the directory at the given path, after removing all of its
+/// contents.
+async fn remove_dir_all(policy: Arc<Policy>, path: String) -> Result<(), Error> {
+    policy.check(&path, Access::Write)?;
+    Ok(fs::remove_dir_all(path).await?)
+}
+
+/// Rename a file or directory.
+async fn rename(policy: Arc<Policy>, from: String, to: String) -> Result<(), Error> {
+    policy.check(&from, Access::Write)?;
+    policy.check(&to, Access::Write)?;
+    Ok(fs::rename(from, to).await?)
+}
+
+/// Copy the contents of a file to another, returning the number of bytes
+/// copied.
+async fn copy(policy: Arc<Policy>, from: String, to: String) -> Result<u64, Error> {
+    policy.check(&from, Access::Read)?;
+    policy.check(&to, Access::Write)?;
+    Ok(fs::copy(from, to).await?)
+}
+
+/// Read the metadata of the file or directory at the given path.
+async fn metadata(policy: Arc<Policy>, path: String) -> Result<Metadata, Error> {
+    policy.check(&path, Access::Read)?;
+    let inner = fs::metadata(path).await?;
+    Ok(Metadata { inner })
+}
+
+/// Read the entries of the directory at the given path as a stream.
+///
+/// The stream ends after the first error, which it produces as its last value.
+async fn read_dir(policy: Arc<Policy>, path: String) -> Result<Stream, Error> {
+    policy.check(&path, Access::Read)?;
+    let entries = fs::read_dir(path).await?;
+
+    Ok(Stream::from_stream(futures_util::stream::unfold(
+        Some(entries),
+        |entries| async move {
+            let mut entries = entries?;
+
+            let (entry, entries) = match entries.next_entry().await {
+                Ok(Some(inner)) => (Ok(DirEntry { inner }), Some(entries)),
+                Ok(None) => return None,
+                Err(error) => (Err(Error::from(error)), None),
+            };
+
+            Some((entry.to_value(), entries))
+        },
+    )))
+}
+
+/// Write all of the given contents and flush.
+async fn write_all<W>(writer: &mut W, contents: &[u8]) -> Result<(), Error>
+where
+    W: tokio::io::AsyncWrite + Unpin,
+{
+    writer.write_all(contents).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Convert the given string or bytes into a vector of bytes to write.
+fn contents_to_vec(value: Value) -> Result<Vec<u8>, VmError> {
+    Ok(match value {
+        Value::String(string) => string.borrow_ref()?.as_bytes().to_vec(),
+        Value::StaticString(string) => string.as_bytes().to_vec(),
+        Value::Bytes(bytes) => bytes.borrow_ref()?.to_vec(),
+        actual => return Err(VmError::expected::<String>(actual.type_info()?)),
+    })
 }
@@ -49,14 +49,81 @@
 //!     dbg(response);
 //! }
 //! ```
+//!
+//! Requests can be built up with query parameters, headers, authentication,
+//! a timeout and a body:
+//!
+//! ```rust,ignore
+//! async fn main() {
+//!     let client = http::Client::new();
+//!
+//!     let response = client.put("https://example.com/items/1")
+//!         .query(#{"verbose": "true"})
+//!         .bearer_auth("secret")
+//!         .timeout(5.0)
+//!         .json(#{"name": "rune"})
+//!         .send()
+//!         .await?
+//!         .error_for_status()?;
+//!
+//!     dbg(response.headers()["content-type"]);
+//!
+//!     let chunks = response.bytes_stream();
+//!
+//!     while let Some(chunk) = chunks.next().await {
+//!         dbg(chunk?);
+//!     }
+//! }
+//! ```
+//!
+//! Errors can be classified through `kind()`, which is one of `"Builder"`,
+//! `"Request"`, `"Redirect"`, `"Status"`, `"Body"`, `"Decode"`, `"Timeout"`,
+//! `"Connect"`, `"Denied"` or `"Other"`.
+//!
+//! ## Sandboxing
+//!
+//! The module constructed through [module] can send any request to any host.
+//! To restrict this, construct it with a [Policy] through [module_with_policy]
+//! instead:
+//!
+//! ```rust
+//! use rune_modules::http::Policy;
+//!
+//! # fn main() -> runestick::Result<()> {
+//! let policy = Policy::new()
+//!     .allow_host("worldtimeapi.org")
+//!     .allow_method("GET");
+//!
+//! let mut context = runestick::Context::with_default_modules()?;
+//! context.install(&rune_modules::http::module_with_policy(policy)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Requests which are not permitted by the policy, including redirects to
+//! hosts which are not permitted, fail with an error for which `is_denied()`
+//! returns `true`.
 
-use runestick::{Any, Bytes, Value, Protocol, Module, ContextError};
+use runestick::{Any, Bytes, ContextError, Module, Object, Protocol, Stream, ToValue, Value, VmError};
 use std::fmt;
 use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The maximum number of redirects followed, same as the default in
+/// [reqwest].
+const MAX_REDIRECTS: usize = 10;
 
-/// Construct the `http` module.
+/// Construct the `http` module, which can send any request to any host.
 pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    module_with_policy(Policy::unrestricted())
+}
+
+/// Construct the `http` module, where the requests which can be sent are
+/// restricted by the given [Policy].
+pub fn module_with_policy(policy: Policy) -> Result<Module, ContextError> {
     let mut module = Module::with_crate("http");
+    let policy = Arc::new(policy);
 
     module.ty::<Client>()?;
     module.ty::<Response>()?;
@@ -64,37 +131,293 @@ pub fn module(_stdio: bool) -> Result<Module, ContextError> {
     module.ty::<StatusCode>()?;
     module.ty::<Error>()?;
 
-    module.function(&["Client", "new"], Client::new)?;
-    module.async_function(&["get"], get)?;
+    module.function(&["Client", "new"], {
+        let policy = policy.clone();
+        move || Client::new(policy.clone())
+    })?;
+
+    module.async_function(&["get"], move |url: String| get(policy.clone(), url))?;
 
     module.async_inst_fn("get", Client::get)?;
     module.async_inst_fn("post", Client::post)?;
+    module.async_inst_fn("put", Client::put)?;
+    module.async_inst_fn("delete", Client::delete)?;
+    module.async_inst_fn("patch", Client::patch)?;
+    module.async_inst_fn("head", Client::head)?;
+    module.async_inst_fn("request", Client::request)?;
 
     module.async_inst_fn("text", Response::text)?;
     module.async_inst_fn("json", Response::json)?;
+    module.async_inst_fn("bytes", Response::bytes)?;
+    module.inst_fn("bytes_stream", Response::bytes_stream)?;
     module.inst_fn("status", Response::status)?;
+    module.inst_fn("headers", Response::headers)?;
+    module.inst_fn("header", Response::header)?;
+    module.inst_fn("error_for_status", Response::error_for_status)?;
 
     module.async_inst_fn("send", RequestBuilder::send)?;
     module.inst_fn("header", RequestBuilder::header)?;
+    module.inst_fn("query", RequestBuilder::query)?;
+    module.inst_fn("basic_auth", RequestBuilder::basic_auth)?;
+    module.inst_fn("bearer_auth", RequestBuilder::bearer_auth)?;
+    module.inst_fn("timeout", RequestBuilder::timeout)?;
     module.async_inst_fn("body_bytes", RequestBuilder::body_bytes)?;
+    module.inst_fn("body_string", RequestBuilder::body_string)?;
+    module.inst_fn("json", RequestBuilder::json)?;
+    module.inst_fn("form", RequestBuilder::form)?;
 
     module.inst_fn(Protocol::STRING_DISPLAY, StatusCode::display)?;
+    module.inst_fn("as_u16", StatusCode::as_u16)?;
+    module.inst_fn("is_success", StatusCode::is_success)?;
+    module.inst_fn("is_client_error", StatusCode::is_client_error)?;
+    module.inst_fn("is_server_error", StatusCode::is_server_error)?;
+
+    module.inst_fn("kind", Error::kind)?;
+    module.inst_fn("status", Error::status)?;
+    module.inst_fn("is_denied", Error::is_denied)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, Error::display)?;
+    module.inst_fn(Protocol::STRING_DEBUG, Error::debug)?;
     Ok(module)
 }
 
+/// A policy restricting which requests the `http` module can send.
+#[derive(Debug, Clone)]
+pub struct Policy {
+    /// Hosts which requests can be sent to, or `None` if any host can.
+    hosts: Option<Vec<String>>,
+    /// Methods which can be used, or `None` if any method can.
+    methods: Option<Vec<String>>,
+}
+
+impl Policy {
+    /// Construct a policy which doesn't permit any requests.
+    pub fn new() -> Self {
+        Self {
+            hosts: Some(Vec::new()),
+            methods: Some(Vec::new()),
+        }
+    }
+
+    /// Construct a policy which permits any request to any host.
+    pub fn unrestricted() -> Self {
+        Self {
+            hosts: None,
+            methods: None,
+        }
+    }
+
+    /// Permit sending requests to the given host, like `example.com`.
+    ///
+    /// Hosts are compared case-insensitively, and subdomains of the host are
+    /// not permitted unless added separately.
+    pub fn allow_host<S>(mut self, host: S) -> Self
+    where
+        S: AsRef<str>,
+    {
+        if let Some(hosts) = &mut self.hosts {
+            hosts.push(host.as_ref().to_ascii_lowercase());
+        }
+
+        self
+    }
+
+    /// Permit sending requests with the given method, like `GET`.
+    pub fn allow_method<S>(mut self, method: S) -> Self
+    where
+        S: AsRef<str>,
+    {
+        if let Some(methods) = &mut self.methods {
+            methods.push(method.as_ref().to_ascii_uppercase());
+        }
+
+        self
+    }
+
+    /// Test if the host of the given url is permitted.
+    fn permits_host(&self, url: &reqwest::Url) -> bool {
+        let hosts = match &self.hosts {
+            Some(hosts) => hosts,
+            None => return true,
+        };
+
+        match url.host_str() {
+            Some(host) => hosts.iter().any(|h| h.eq_ignore_ascii_case(host)),
+            None => false,
+        }
+    }
+
+    /// Check that sending a request with the given method to the given url is
+    /// permitted.
+    fn check(&self, method: &reqwest::Method, url: &str) -> Result<(), Error> {
+        if self.hosts.is_none() && self.methods.is_none() {
+            return Ok(());
+        }
+
+        let permits_method = match &self.methods {
+            Some(methods) => methods.iter().any(|m| m == method.as_str()),
+            None => true,
+        };
+
+        let permitted = match reqwest::Url::parse(url) {
+            Ok(parsed) => permits_method && self.permits_host(&parsed),
+            Err(..) => false,
+        };
+
+        if permitted {
+            Ok(())
+        } else {
+            Err(Error {
+                inner: ErrorKind::Denied {
+                    method: method.to_string(),
+                    url: url.to_owned(),
+                },
+            })
+        }
+    }
+
+    /// Build a redirect policy which only follows redirects to permitted
+    /// hosts.
+    fn redirect(self: &Arc<Self>) -> reqwest::redirect::Policy {
+        if self.hosts.is_none() {
+            return reqwest::redirect::Policy::default();
+        }
+
+        let policy = self.clone();
+
+        reqwest::redirect::Policy::custom(move |attempt| {
+            if attempt.previous().len() > MAX_REDIRECTS {
+                attempt.error("too many redirects")
+            } else if policy.permits_host(attempt.url()) {
+                attempt.follow()
+            } else {
+                let error = DeniedRedirect(attempt.url().clone());
+                attempt.error(error)
+            }
+        })
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An error raised by an http operation.
 #[derive(Debug, Any)]
 pub struct Error {
-    inner: reqwest::Error,
+    inner: ErrorKind,
+}
+
+#[derive(Debug)]
+enum ErrorKind {
+    Reqwest(reqwest::Error),
+    Denied { method: String, url: String },
 }
 
 impl From<reqwest::Error> for Error {
-    fn from(inner: reqwest::Error) -> Self {
-        Self { inner }
+    fn from(error: reqwest::Error) -> Self {
+        Self {
+            inner: ErrorKind::Reqwest(error),
+        }
     }
 }
 
+impl Error {
+    /// The kind of the error.
+    fn kind(&self) -> String {
+        let error = match &self.inner {
+            ErrorKind::Reqwest(error) if !self.is_denied() => error,
+            _ => return String::from("Denied"),
+        };
+
+        let kind = if error.is_timeout() {
+            "Timeout"
+        } else if error.is_connect() {
+            "Connect"
+        } else if error.is_builder() {
+            "Builder"
+        } else if error.is_redirect() {
+            "Redirect"
+        } else if error.is_status() {
+            "Status"
+        } else if error.is_decode() {
+            "Decode"
+        } else if error.is_body() {
+            "Body"
+        } else if error.is_request() {
+            "Request"
+        } else {
+            "Other"
+        };
+
+        String::from(kind)
+    }
+
+    /// The status code of the response which caused the error, if the error
+    /// was raised by `error_for_status`.
+    fn status(&self) -> Option<StatusCode> {
+        match &self.inner {
+            ErrorKind::Reqwest(error) => Some(StatusCode {
+                inner: error.status()?,
+            }),
+            ErrorKind::Denied { .. } => None,
+        }
+    }
+
+    /// Test if the error was raised because the request was denied by the
+    /// policy of the module.
+    fn is_denied(&self) -> bool {
+        match &self.inner {
+            ErrorKind::Reqwest(error) => error.is_redirect() && is_denied_redirect(error),
+            ErrorKind::Denied { .. } => true,
+        }
+    }
+
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        match &self.inner {
+            ErrorKind::Reqwest(error) => write!(buf, "{}", error),
+            ErrorKind::Denied { method, url } => {
+                write!(buf, "access denied: cannot send `{}` request to `{}`", method, url)
+            }
+        }
+    }
+
+    fn debug(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{:?}", self.inner)
+    }
+}
+
+/// The error raised when a redirect is denied by the policy.
+#[derive(Debug)]
+struct DeniedRedirect(reqwest::Url);
+
+impl fmt::Display for DeniedRedirect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "access denied: cannot redirect to `{}`", self.0)
+    }
+}
+
+impl std::error::Error for DeniedRedirect {}
+
+/// Test if the redirect error was raised because the policy denied it.
+fn is_denied_redirect(error: &reqwest::Error) -> bool {
+    let mut source = std::error::Error::source(error);
+
+    while let Some(error) = source {
+        if error.is::<DeniedRedirect>() {
+            return true;
+        }
+
+        source = error.source();
+    }
+
+    false
+}
+
 #[derive(Debug, Any)]
 struct Client {
+    policy: Arc<Policy>,
     client: reqwest::Client,
 }
 
@@ -112,6 +435,26 @@ impl StatusCode {
     fn display(&self, buf: &mut String) -> fmt::Result {
         write!(buf, "{}", self.inner)
     }
+
+    /// The status code as a number.
+    fn as_u16(&self) -> u16 {
+        self.inner.as_u16()
+    }
+
+    /// Test if the status code is in the 200-299 range.
+    fn is_success(&self) -> bool {
+        self.inner.is_success()
+    }
+
+    /// Test if the status code is in the 400-499 range.
+    fn is_client_error(&self) -> bool {
+        self.inner.is_client_error()
+    }
+
+    /// Test if the status code is in the 500-599 range.
+    fn is_server_error(&self) -> bool {
+        self.inner.is_server_error()
+    }
 }
 
 impl Response {
@@ -125,12 +468,73 @@ impl Response {
         Ok(text)
     }
 
+    /// Read the whole body of the response as bytes.
+    async fn bytes(self) -> Result<Bytes, Error> {
+        let bytes = self.response.bytes().await?;
+        Ok(Bytes::from_vec(bytes.to_vec()))
+    }
+
+    /// Stream the body of the response as chunks of bytes, as they arrive.
+    ///
+    /// The stream ends after the first error, which it produces as its last
+    /// value.
+    fn bytes_stream(self) -> Stream {
+        let response = Some(self.response);
+
+        Stream::from_stream(futures_util::stream::unfold(response, |response| async move {
+            let mut response = response?;
+
+            let (chunk, response) = match response.chunk().await {
+                Ok(Some(chunk)) => (Ok(Bytes::from_vec(chunk.to_vec())), Some(response)),
+                Ok(None) => return None,
+                Err(error) => (Err(Error::from(error)), None),
+            };
+
+            Some((chunk.to_value(), response))
+        }))
+    }
+
     /// Get the status code of the response.
     fn status(&self) -> StatusCode {
         let inner = self.response.status();
 
         StatusCode { inner }
     }
+
+    /// Get the headers of the response as an object, with lowercase names.
+    ///
+    /// Headers which occur multiple times have their values joined by `", "`.
+    fn headers(&self) -> Result<Object, VmError> {
+        let mut object = Object::new();
+
+        for name in self.response.headers().keys() {
+            if let Some(value) = self.header(name.as_str()) {
+                object.insert(name.as_str().to_owned(), value.to_value()?);
+            }
+        }
+
+        Ok(object)
+    }
+
+    /// Get the value of the given header, if it's present.
+    ///
+    /// Headers which occur multiple times have their values joined by `", "`.
+    fn header(&self, name: &str) -> Option<String> {
+        let mut values = self.response.headers().get_all(name).iter().peekable();
+        values.peek()?;
+
+        let values = values
+            .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned())
+            .collect::<Vec<_>>();
+
+        Some(values.join(", "))
+    }
+
+    /// Turn a response with a client or server error status into an error.
+    fn error_for_status(self) -> Result<Self, Error> {
+        let response = self.response.error_for_status()?;
+        Ok(Self { response })
+    }
 }
 
 #[derive(Debug, Any)]
@@ -152,6 +556,37 @@ impl RequestBuilder {
         }
     }
 
+    /// Add the fields of the given object as query parameters.
+    fn query(self, query: Value) -> Self {
+        Self {
+            request: self.request.query(&query),
+        }
+    }
+
+    /// Use basic authentication for the request.
+    fn basic_auth(self, username: &str, password: Option<String>) -> Self {
+        Self {
+            request: self.request.basic_auth(username, password),
+        }
+    }
+
+    /// Use bearer authentication for the request.
+    fn bearer_auth(self, token: &str) -> Self {
+        Self {
+            request: self.request.bearer_auth(token),
+        }
+    }
+
+    /// Set a timeout in seconds for the request, which applies from when the
+    /// request starts connecting until the response body has been read.
+    fn timeout(self, seconds: f64) -> Result<Self, VmError> {
+        let timeout = Duration::try_from_secs_f64(seconds).map_err(VmError::panic)?;
+
+        Ok(Self {
+            request: self.request.timeout(timeout),
+        })
+    }
+
     /// Set the request body from bytes.
     async fn body_bytes(self, bytes: Bytes) -> Result<Self, Error> {
         let bytes = bytes.into_vec();
@@ -160,31 +595,94 @@ impl RequestBuilder {
             request: self.request.body(bytes),
         })
     }
-}
 
-impl Client {
-    fn new() -> Self {
+    /// Set the request body from a string.
+    fn body_string(self, body: String) -> Self {
         Self {
-            client: reqwest::Client::new(),
+            request: self.request.body(body),
         }
     }
 
+    /// Set the request body to the given value serialized as JSON.
+    fn json(self, body: Value) -> Self {
+        Self {
+            request: self.request.json(&body),
+        }
+    }
+
+    /// Set the request body to the fields of the given object as a url
+    /// encoded form.
+    fn form(self, body: Value) -> Self {
+        Self {
+            request: self.request.form(&body),
+        }
+    }
+}
+
+impl Client {
+    fn new(policy: Arc<Policy>) -> Self {
+        // NB: this only fails if the TLS backend can't be initialized, in
+        // which case `reqwest::Client::new` panics as well.
+        let client = reqwest::Client::builder()
+            .redirect(policy.redirect())
+            .build()
+            .expect("failed to construct http client");
+
+        Self { policy, client }
+    }
+
+    /// Construct a builder for a request with the given method.
+    fn builder(&self, method: reqwest::Method, url: &str) -> Result<RequestBuilder, Error> {
+        self.policy.check(&method, url)?;
+        let request = self.client.request(method, url);
+        Ok(RequestBuilder { request })
+    }
+
     /// Construct a builder to GET the given URL.
     async fn get(&self, url: &str) -> Result<RequestBuilder, Error> {
-        let request = self.client.get(url);
-        Ok(RequestBuilder { request })
+        self.builder(reqwest::Method::GET, url)
     }
 
     /// Construct a builder to POST to the given URL.
     async fn post(&self, url: &str) -> Result<RequestBuilder, Error> {
-        let request = self.client.post(url);
-        Ok(RequestBuilder { request })
+        self.builder(reqwest::Method::POST, url)
+    }
+
+    /// Construct a builder to PUT to the given URL.
+    async fn put(&self, url: &str) -> Result<RequestBuilder, Error> {
+        self.builder(reqwest::Method::PUT, url)
+    }
+
+    /// Construct a builder to DELETE the given URL.
+    async fn delete(&self, url: &str) -> Result<RequestBuilder, Error> {
+        self.builder(reqwest::Method::DELETE, url)
+    }
+
+    /// Construct a builder to PATCH the given URL.
+    async fn patch(&self, url: &str) -> Result<RequestBuilder, Error> {
+        self.builder(reqwest::Method::PATCH, url)
+    }
+
+    /// Construct a builder to send a HEAD request to the given URL.
+    async fn head(&self, url: &str) -> Result<RequestBuilder, Error> {
+        self.builder(reqwest::Method::HEAD, url)
+    }
+
+    /// Construct a builder for a request with the given method, like
+    /// `"OPTIONS"`.
+    async fn request(&self, method: &str, url: &str) -> Result<Result<RequestBuilder, Error>, VmError> {
+        let method = match reqwest::Method::from_bytes(method.to_ascii_uppercase().as_bytes()) {
+            Ok(method) => method,
+            Err(..) => return Err(VmError::panic(format!("invalid method `{}`", method))),
+        };
+
+        Ok(self.builder(method, url))
     }
 }
 
 /// Shorthand for generating a get request.
-async fn get(url: &str) -> Result<Response, Error> {
-    Ok(Response {
-        response: reqwest::get(url).await?,
-    })
+async fn get(policy: Arc<Policy>, url: String) -> Result<Response, Error> {
+    let client = Client::new(policy);
+    let request = client.builder(reqwest::Method::GET, &url)?;
+    request.send().await
 }
@@ -0,0 +1,618 @@
+//! The native `http_server` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = {version = "0.9.0", features = ["http-server"]}
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! # fn main() -> runestick::Result<()> {
+//! let mut context = runestick::Context::with_default_modules()?;
+//! context.install(&rune_modules::http_server::module(true)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use http_server::{Response, Server};
+//!
+//! pub async fn main() {
+//!     let server = Server::new();
+//!     server.get("/hello/:name", hello);
+//!     server.post("/echo", echo);
+//!     server.serve("127.0.0.1:8080").await?;
+//! }
+//!
+//! fn hello(request) {
+//!     `Hello ${request.param("name").unwrap()}!`
+//! }
+//!
+//! async fn echo(request) {
+//!     let body = request.json()?;
+//!     Response::json(#{"echo": body})?.header("x-echo", "yes")
+//! }
+//! ```
+//!
+//! Handlers are called with a `Request` and can return a `Response`, a string
+//! which is sent as plain text, or a `Result` of either. Errors are sent as a
+//! `500 Internal Server Error`.
+//!
+//! Path patterns consist of literal segments, `:name` segments which match any
+//! single segment, and a final `*name` segment which matches the rest of the
+//! path. Matched segments are available through `Request::param`.
+//!
+//! `Server::serve` takes either an address to bind, or a [Listener] which is
+//! already bound, which is useful to serve on a port picked by the operating
+//! system:
+//!
+//! ```rust,ignore
+//! let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+//! let addr = listener.local_addr()?;
+//! let execution = vm.send_execute(&["main"], (Listener::from_std(listener),))?;
+//! ```
+//!
+//! Requests are served from within the future returned by `Server::serve`.
+//! Connections are driven by that future rather than spawned as separate
+//! tasks, so it can be executed on any kind of runtime.
+
+use runestick::{
+    Any, Bytes, ContextError, FromValue, Function, Module, Object, Shared, ToValue, Value, VmError,
+};
+use std::convert::Infallible;
+use std::fmt;
+use std::fmt::Write as _;
+use std::future::Future;
+use std::io;
+use std::rc::Rc;
+
+use futures_util::stream::{FuturesUnordered, StreamExt as _};
+use hyper::server::conn::Http;
+use hyper::service::service_fn;
+use hyper::Body;
+use tokio::net::TcpListener;
+
+/// Construct the `http_server` module.
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("http_server");
+
+    module.ty::<Server>()?;
+    module.ty::<Listener>()?;
+    module.ty::<Request>()?;
+    module.ty::<Response>()?;
+    module.ty::<Error>()?;
+
+    module.function(&["Server", "new"], Server::new)?;
+    module.inst_fn("route", Server::route)?;
+    module.inst_fn("get", Server::get)?;
+    module.inst_fn("post", Server::post)?;
+    module.inst_fn("put", Server::put)?;
+    module.inst_fn("delete", Server::delete)?;
+    module.async_inst_fn("serve", Server::serve)?;
+
+    module.inst_fn("method", Request::method)?;
+    module.inst_fn("path", Request::path)?;
+    module.inst_fn("param", Request::param)?;
+    module.inst_fn("query", Request::query)?;
+    module.inst_fn("header", Request::header)?;
+    module.inst_fn("body", Request::body)?;
+    module.inst_fn("text", Request::text)?;
+    module.inst_fn("json", Request::json)?;
+
+    module.function(&["Response", "new"], Response::new)?;
+    module.function(&["Response", "text"], Response::text)?;
+    module.function(&["Response", "json"], Response::json)?;
+    module.function(&["Response", "bytes"], Response::bytes)?;
+    module.inst_fn("status", Response::status)?;
+    module.inst_fn("header", Response::header)?;
+
+    module.inst_fn(runestick::Protocol::STRING_DISPLAY, Error::display)?;
+    module.inst_fn(runestick::Protocol::STRING_DEBUG, Error::debug)?;
+    Ok(module)
+}
+
+/// An error raised by the server.
+#[derive(Debug, Any)]
+pub struct Error {
+    inner: ErrorKind,
+}
+
+#[derive(Debug)]
+enum ErrorKind {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Utf8(std::string::FromUtf8Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self {
+            inner: ErrorKind::Io(error),
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Self {
+            inner: ErrorKind::Json(error),
+        }
+    }
+}
+
+impl From<std::string::FromUtf8Error> for Error {
+    fn from(error: std::string::FromUtf8Error) -> Self {
+        Self {
+            inner: ErrorKind::Utf8(error),
+        }
+    }
+}
+
+impl Error {
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        match &self.inner {
+            ErrorKind::Io(error) => write!(buf, "{}", error),
+            ErrorKind::Json(error) => write!(buf, "{}", error),
+            ErrorKind::Utf8(error) => write!(buf, "{}", error),
+        }
+    }
+
+    fn debug(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{:?}", self.inner)
+    }
+}
+
+/// A listener which is already bound, which can be passed to a script to be
+/// served through `Server::serve`.
+#[derive(Debug, Any)]
+pub struct Listener {
+    inner: std::net::TcpListener,
+}
+
+impl Listener {
+    /// Construct a listener from one bound through the standard library.
+    pub fn from_std(listener: std::net::TcpListener) -> Self {
+        Self { inner: listener }
+    }
+}
+
+/// A segment in the path pattern of a route.
+#[derive(Debug, Clone)]
+enum Segment {
+    /// A segment which must match exactly.
+    Literal(String),
+    /// A single segment captured under the given name.
+    Param(String),
+    /// The remainder of the path captured under the given name.
+    Rest(String),
+}
+
+/// A route to a handler.
+#[derive(Clone)]
+struct Route {
+    /// The method of the route, or `None` if it matches any method.
+    method: Option<hyper::Method>,
+    segments: Vec<Segment>,
+    handler: Shared<Function>,
+}
+
+impl Route {
+    /// Match the given path segments against the route, returning the
+    /// captured parameters if it matches.
+    fn matches(&self, path: &[&str]) -> Option<Vec<(String, String)>> {
+        let mut params = Vec::new();
+        let mut it = path.iter();
+
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(literal) => {
+                    if it.next()? != literal {
+                        return None;
+                    }
+                }
+                Segment::Param(name) => {
+                    params.push((name.clone(), (*it.next()?).to_owned()));
+                }
+                Segment::Rest(name) => {
+                    let rest = it.by_ref().copied().collect::<Vec<_>>().join("/");
+                    params.push((name.clone(), rest));
+                }
+            }
+        }
+
+        if it.next().is_some() {
+            return None;
+        }
+
+        Some(params)
+    }
+}
+
+/// An HTTP server which routes requests to handlers.
+#[derive(Any)]
+struct Server {
+    routes: Vec<Route>,
+}
+
+impl Server {
+    /// Construct a new server without any routes.
+    fn new() -> Self {
+        Self { routes: Vec::new() }
+    }
+
+    /// Route requests with the given method and path pattern to the given
+    /// handler. The method `"*"` matches any method.
+    fn route(&mut self, method: &str, path: &str, handler: Shared<Function>) -> Result<(), VmError> {
+        let method = match method {
+            "*" => None,
+            method => match hyper::Method::from_bytes(method.to_ascii_uppercase().as_bytes()) {
+                Ok(method) => Some(method),
+                Err(..) => return Err(VmError::panic(format!("invalid method `{}`", method))),
+            },
+        };
+
+        let mut segments = Vec::new();
+
+        for segment in split_path(path) {
+            if let Some(name) = segment.strip_prefix(':') {
+                segments.push(Segment::Param(name.to_owned()));
+            } else if let Some(name) = segment.strip_prefix('*') {
+                segments.push(Segment::Rest(name.to_owned()));
+            } else {
+                segments.push(Segment::Literal(segment.to_owned()));
+            }
+        }
+
+        if let Some(n) = segments.iter().position(|s| matches!(s, Segment::Rest(..))) {
+            if n + 1 != segments.len() {
+                return Err(VmError::panic(format!(
+                    "`*` segment must be last in path `{}`",
+                    path
+                )));
+            }
+        }
+
+        self.routes.push(Route {
+            method,
+            segments,
+            handler,
+        });
+
+        Ok(())
+    }
+
+    /// Route `GET` requests to the given handler.
+    fn get(&mut self, path: &str, handler: Shared<Function>) -> Result<(), VmError> {
+        self.route("GET", path, handler)
+    }
+
+    /// Route `POST` requests to the given handler.
+    fn post(&mut self, path: &str, handler: Shared<Function>) -> Result<(), VmError> {
+        self.route("POST", path, handler)
+    }
+
+    /// Route `PUT` requests to the given handler.
+    fn put(&mut self, path: &str, handler: Shared<Function>) -> Result<(), VmError> {
+        self.route("PUT", path, handler)
+    }
+
+    /// Route `DELETE` requests to the given handler.
+    fn delete(&mut self, path: &str, handler: Shared<Function>) -> Result<(), VmError> {
+        self.route("DELETE", path, handler)
+    }
+
+    /// Listen on the given address, or on an already bound [Listener], and
+    /// serve requests until an error occurs.
+    ///
+    /// Connections are served concurrently, with each request handled by a
+    /// separate call to its handler.
+    async fn serve(&mut self, addr: Value) -> Result<Result<(), Error>, VmError> {
+        let listener = match addr {
+            Value::String(addr) => {
+                let addr = addr.borrow_ref()?.clone();
+                TcpListener::bind(addr.as_str()).await
+            }
+            Value::StaticString(addr) => TcpListener::bind(addr.as_str()).await,
+            addr => {
+                let listener = Listener::from_value(addr)?;
+
+                listener
+                    .inner
+                    .set_nonblocking(true)
+                    .and_then(|()| TcpListener::from_std(listener.inner))
+            }
+        };
+
+        let listener = match listener {
+            Ok(listener) => listener,
+            Err(error) => return Ok(Err(Error::from(error))),
+        };
+
+        Ok(self.serve_listener(listener).await)
+    }
+
+    /// Serve requests on the given listener.
+    async fn serve_listener(&self, listener: TcpListener) -> Result<(), Error> {
+        let routes = Rc::new(self.routes.clone());
+        let mut connections = FuturesUnordered::new();
+
+        let mut http = Http::new().with_executor(NoExecutor);
+        http.http1_only(true);
+
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => {
+                    let (stream, _) = accepted?;
+                    let routes = routes.clone();
+
+                    let service = service_fn(move |request| {
+                        let routes = routes.clone();
+                        async move { Ok::<_, Infallible>(handle(&routes, request).await) }
+                    });
+
+                    connections.push(http.serve_connection(stream, service));
+                }
+                // NB: errors in individual connections, like clients
+                // disconnecting, are not errors of the server.
+                Some(..) = connections.next(), if !connections.is_empty() => (),
+            }
+        }
+    }
+}
+
+/// Executor used by connections, which never spawns any tasks.
+///
+/// Connections hold on to values which are not `Send`, so they can't use the
+/// default executor of hyper. HTTP/1 connections never spawn tasks, and they
+/// are all driven by the future of the server itself.
+#[derive(Clone, Copy)]
+struct NoExecutor;
+
+impl<F> hyper::rt::Executor<F> for NoExecutor
+where
+    F: 'static + Future,
+{
+    fn execute(&self, _: F) {
+        unreachable!("http1 connections don't spawn tasks")
+    }
+}
+
+/// Split a path into its non-empty segments.
+fn split_path(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|s| !s.is_empty())
+}
+
+/// Handle a single request.
+async fn handle(routes: &[Route], request: hyper::Request<Body>) -> hyper::Response<Body> {
+    let path = request.uri().path().to_owned();
+    let segments = split_path(&path).collect::<Vec<_>>();
+
+    let mut path_matched = false;
+    let mut found = None;
+
+    for route in routes {
+        if let Some(params) = route.matches(&segments) {
+            path_matched = true;
+
+            let method_matched = match &route.method {
+                Some(method) => method == request.method(),
+                None => true,
+            };
+
+            if method_matched {
+                found = Some((route.handler.clone(), params));
+                break;
+            }
+        }
+    }
+
+    let (handler, params) = match found {
+        Some(found) => found,
+        None if path_matched => return plain(405, String::from("method not allowed")),
+        None => return plain(404, String::from("not found")),
+    };
+
+    let (parts, body) = request.into_parts();
+
+    let body = match hyper::body::to_bytes(body).await {
+        Ok(body) => body.to_vec(),
+        Err(error) => return plain(400, error.to_string()),
+    };
+
+    let request = Request {
+        parts,
+        params,
+        body,
+    };
+
+    match call(&handler, request).await {
+        Ok(response) => response,
+        Err(error) => plain(500, format!("internal server error: {}", error)),
+    }
+}
+
+/// Call the handler with the given request and convert what it returns into
+/// a response.
+async fn call(handler: &Shared<Function>, request: Request) -> Result<hyper::Response<Body>, VmError> {
+    let value = handler.borrow_ref()?.call::<_, Value>((request,))?;
+
+    let value = match value {
+        Value::Future(future) => {
+            let future = future.take()?;
+            future.await?
+        }
+        value => value,
+    };
+
+    into_response(value)
+}
+
+/// Convert a value returned by a handler into a response.
+fn into_response(value: Value) -> Result<hyper::Response<Body>, VmError> {
+    match value {
+        Value::Result(result) => match result.take()? {
+            Ok(value) => into_response(value),
+            Err(error) => Err(VmError::panic(format!("handler errored: {:?}", error))),
+        },
+        Value::String(string) => Ok(plain(200, string.take()?)),
+        Value::StaticString(string) => Ok(plain(200, string.as_ref().to_string())),
+        value => Ok(Response::from_value(value)?.into_hyper()),
+    }
+}
+
+/// Construct a plain text response.
+fn plain(status: u16, text: String) -> hyper::Response<Body> {
+    Response::text(text).status(status).into_hyper()
+}
+
+/// An incoming request.
+#[derive(Any)]
+struct Request {
+    parts: hyper::http::request::Parts,
+    params: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    /// The method of the request, like `"GET"`.
+    fn method(&self) -> String {
+        self.parts.method.to_string()
+    }
+
+    /// The path of the request, without the query string.
+    fn path(&self) -> String {
+        self.parts.uri.path().to_owned()
+    }
+
+    /// The value of a parameter captured by the path pattern of the route.
+    fn param(&self, name: &str) -> Option<String> {
+        let (_, value) = self.params.iter().find(|(n, _)| n == name)?;
+        Some(value.clone())
+    }
+
+    /// The query parameters of the request as an object.
+    fn query(&self) -> Result<Object, VmError> {
+        let query = self.parts.uri.query().unwrap_or_default();
+
+        let pairs = serde_urlencoded::from_str::<Vec<(String, String)>>(query)
+            .map_err(|error| VmError::panic(error.to_string()))?;
+
+        let mut object = Object::new();
+
+        for (key, value) in pairs {
+            object.insert(key, value.to_value()?);
+        }
+
+        Ok(object)
+    }
+
+    /// The value of the given header, if it's present.
+    fn header(&self, name: &str) -> Option<String> {
+        let value = self.parts.headers.get(name)?;
+        Some(String::from_utf8_lossy(value.as_bytes()).into_owned())
+    }
+
+    /// The body of the request as bytes.
+    fn body(&self) -> Bytes {
+        Bytes::from_vec(self.body.clone())
+    }
+
+    /// The body of the request as a string.
+    fn text(&self) -> Result<String, Error> {
+        Ok(String::from_utf8(self.body.clone())?)
+    }
+
+    /// The body of the request parsed as JSON.
+    fn json(&self) -> Result<Value, Error> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+}
+
+/// A response to send to a client.
+#[derive(Any)]
+struct Response {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Response {
+    /// Construct an empty `200 OK` response.
+    fn new() -> Self {
+        Self {
+            status: 200,
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    /// Construct a plain text response.
+    fn text(text: String) -> Self {
+        Self::new()
+            .header("content-type", "text/plain; charset=utf-8")
+            .with_body(text.into_bytes())
+    }
+
+    /// Construct a response with the given value serialized as JSON.
+    fn json(value: Value) -> Result<Self, Error> {
+        let body = serde_json::to_vec(&value)?;
+        Ok(Self::new()
+            .header("content-type", "application/json")
+            .with_body(body))
+    }
+
+    /// Construct a response with the given bytes as its body.
+    fn bytes(bytes: Bytes) -> Self {
+        Self::new()
+            .header("content-type", "application/octet-stream")
+            .with_body(bytes.into_vec())
+    }
+
+    /// Set the status code of the response.
+    fn status(self, status: u16) -> Self {
+        Self { status, ..self }
+    }
+
+    /// Set a header in the response, replacing any existing value.
+    fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.retain(|(n, _)| !n.eq_ignore_ascii_case(name));
+        self.headers.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    fn with_body(self, body: Vec<u8>) -> Self {
+        Self { body, ..self }
+    }
+
+    /// Convert into a hyper response.
+    ///
+    /// Invalid status codes and headers are replaced with an error response,
+    /// since they can't be reported back to the handler.
+    fn into_hyper(self) -> hyper::Response<Body> {
+        let mut builder = hyper::Response::builder().status(self.status);
+
+        for (name, value) in &self.headers {
+            builder = builder.header(name.as_str(), value.as_str());
+        }
+
+        match builder.body(Body::from(self.body)) {
+            Ok(response) => response,
+            Err(error) => {
+                let mut response = hyper::Response::new(Body::from(format!(
+                    "internal server error: {}",
+                    error
+                )));
+                *response.status_mut() = hyper::StatusCode::INTERNAL_SERVER_ERROR;
+                response
+            }
+        }
+    }
+}
@@ -27,6 +27,7 @@ use rune::{quote, Parser, TokenStream};
 pub fn module(_stdio: bool) -> Result<runestick::Module, runestick::ContextError> {
     let mut module = runestick::Module::with_crate_item("std", &["io"]);
     module.macro_(&["println"], println_macro)?;
+    module.macro_(&["eprintln"], eprintln_macro)?;
     Ok(module)
 }
 
@@ -37,3 +38,11 @@ pub(crate) fn println_macro(stream: &TokenStream) -> runestick::Result<TokenStre
     let expanded = args.expand()?;
     Ok(quote!(std::io::println(#expanded)).into_token_stream())
 }
+
+/// Implementation for the `eprintln!` macro.
+pub(crate) fn eprintln_macro(stream: &TokenStream) -> runestick::Result<TokenStream> {
+    let mut p = Parser::from_token_stream(stream);
+    let args = p.parse_all::<macros::FormatArgs>()?;
+    let expanded = args.expand()?;
+    Ok(quote!(std::io::eprintln(#expanded)).into_token_stream())
+}
@@ -50,11 +50,13 @@
 //! * [fmt]
 //! * [fs]
 //! * [http]
+//! * [http_server]
 //! * [io]
 //! * [json]
 //! * [macros]
 //! * [process]
 //! * [rand]
+//! * [regex]
 //! * [signal]
 //! * [test]
 //! * [time]
@@ -68,11 +70,13 @@
 //! * `fs` for the [fs module][fs]
 //! * `full` includes all modules.
 //! * `http` for the [http module][http]
+//! * `http-server` for the [http_server module][http_server]
 //! * `io` for the [io module][io]
 //! * `json` for the [json module][json]
 //! * `macros` for the [macros module][macros]
 //! * `process` for the [process module][process]
 //! * `rand` for the [rand module][rand]
+//! * `regex` for the [regex module][regex]
 //! * `signal` for the [signal module][signal]
 //! * `test` for the [test module][test]
 //! * `time` for the [time module][time]
@@ -83,11 +87,13 @@
 //! [fmt]: https://docs.rs/rune-modules/0/rune_modules/fmt/
 //! [fs]: https://docs.rs/rune-modules/0/rune_modules/fs/
 //! [http]: https://docs.rs/rune-modules/0/rune_modules/http/
+//! [http_server]: https://docs.rs/rune-modules/0/rune_modules/http_server/
 //! [io]: https://docs.rs/rune-modules/0/rune_modules/io/
 //! [json]: https://docs.rs/rune-modules/0/rune_modules/json/
 //! [macros]: https://docs.rs/rune-modules/0/rune_modules/macros/
 //! [process]: https://docs.rs/rune-modules/0/rune_modules/process/
 //! [rand]: https://docs.rs/rune-modules/0/rune_modules/rand/
+//! [regex]: https://docs.rs/rune-modules/0/rune_modules/regex/
 //! [signal]: https://docs.rs/rune-modules/0/rune_modules/signal/
 //! [test]: https://docs.rs/rune-modules/0/rune_modules/test/
 //! [time]: https://docs.rs/rune-modules/0/rune_modules/time/
@@ -100,6 +106,9 @@
 #[cfg(feature = "experiments")]
 pub mod experiments;
 
+#[cfg(feature = "time")]
+pub mod time;
+
 macro_rules! modules {
     ($($ident:ident, $name:literal),* $(,)?) => {
         $(
@@ -107,13 +116,12 @@ macro_rules! modules {
             pub mod $ident;
         )*
 
-        /// Construct a a default context runestick context with all enabled
-        /// modules provided based on the [default runestick
-        /// context](runestick::Context::with_default_modules).
-        pub fn with_config(stdio: bool) -> Result<runestick::Context, runestick::ContextError> {
-            #[allow(unused_mut)]
-            let mut context = runestick::Context::with_config(stdio)?;
-
+        /// Install all enabled modules, except for the `time` module which
+        /// depends on the clock being used.
+        fn install_modules(
+            #[allow(unused)] context: &mut runestick::Context,
+            #[allow(unused)] stdio: bool,
+        ) -> Result<(), runestick::ContextError> {
             $(
                 #[cfg(feature = $name)]
                 {
@@ -121,14 +129,46 @@ macro_rules! modules {
                 }
             )*
 
-            Ok(context)
+            Ok(())
         }
+    }
+}
 
-        /// Construct a a default context runestick context with default config.
-        pub fn default_context() -> Result<runestick::Context, runestick::ContextError> {
-            with_config(true)
-        }
+/// Construct a a default context runestick context with all enabled
+/// modules provided based on the [default runestick
+/// context](runestick::Context::with_default_modules).
+pub fn with_config(stdio: bool) -> Result<runestick::Context, runestick::ContextError> {
+    let mut context = runestick::Context::with_config(stdio)?;
+    install_modules(&mut context, stdio)?;
+
+    #[cfg(feature = "time")]
+    {
+        context.install(&self::time::module(stdio)?)?;
     }
+
+    Ok(context)
+}
+
+/// Construct a context like [with_config], but where the `time` module uses
+/// the given [time::Clock].
+///
+/// With the `time-virtual` feature, this can be used with a [virtual
+/// clock](time::Clock::virtual_at) to test timer-driven scripts
+/// deterministically.
+#[cfg(feature = "time")]
+pub fn with_clock(
+    stdio: bool,
+    clock: time::Clock,
+) -> Result<runestick::Context, runestick::ContextError> {
+    let mut context = runestick::Context::with_config(stdio)?;
+    install_modules(&mut context, stdio)?;
+    context.install(&self::time::module_with_clock(clock)?)?;
+    Ok(context)
+}
+
+/// Construct a a default context runestick context with default config.
+pub fn default_context() -> Result<runestick::Context, runestick::ContextError> {
+    with_config(true)
 }
 
 modules! {
@@ -136,13 +176,14 @@ modules! {
     fmt, "fmt",
     fs, "fs",
     http, "http",
+    http_server, "http-server",
     io, "io",
     json, "json",
     macros, "macros",
     process, "process",
     rand, "rand",
+    regex, "regex",
     signal, "signal",
     test, "test",
-    time, "time",
     toml, "toml",
 }
@@ -23,47 +23,280 @@
 //! Use it in Rune:
 //!
 //! ```rust,ignore
-//! use process::Command;
+//! use process::{Command, Stdio};
 //!
-//! fn main() {
-//!     let command = Command::new("ls");
-//!     command.run().await;
+//! async fn main() {
+//!     let command = Command::new("grep");
+//!     command.arg("rune");
+//!     command.env("LC_ALL", "C");
+//!     command.current_dir("src");
+//!     command.stdin(Stdio::piped());
+//!     command.stdout(Stdio::piped());
+//!
+//!     let child = command.spawn()?;
+//!
+//!     let stdin = child.stdin().unwrap();
+//!     stdin.write("rune\nrust\n").await?;
+//!     stdin.close();
+//!
+//!     let lines = child.stdout_lines().unwrap();
+//!
+//!     while let Some(line) = lines.next().await {
+//!         println(line?);
+//!     }
+//!
+//!     match child.wait_timeout(10.0).await? {
+//!         Some(status) => println(`exited with ${status}`),
+//!         None => child.kill().await?,
+//!     }
 //! }
 //! ```
+//!
+//! Output streamed through `stdout_lines` and `stderr_lines` is a `Stream`,
+//! which produces a `Result` for each line read.
+//!
+//! ## Sandboxing
+//!
+//! The module constructed through [module] can run any command, and commands
+//! inherit the whole environment of the current process. To restrict this,
+//! construct it with a [Policy] through [module_with_policy] instead:
+//!
+//! ```rust
+//! use rune_modules::process::Policy;
+//!
+//! # fn main() -> runestick::Result<()> {
+//! let policy = Policy::new().allow_command("git").allow_env("PATH");
+//!
+//! let mut context = runestick::Context::with_default_modules()?;
+//! context.install(&rune_modules::process::module_with_policy(policy)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Spawning a command which is not permitted by the policy fails with an
+//! error for which `is_denied()` returns `true`.
 
-use runestick::{Any, Bytes, Shared, Value, VmError, Protocol, Module, ContextError};
+use runestick::{Any, Bytes, Shared, Stream, ToValue, Value, VmError, Protocol, Module, ContextError};
 use std::fmt;
+use std::fmt::Write as _;
 use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt as _, AsyncRead, AsyncWriteExt as _, BufReader};
 use tokio::process;
 
-/// Construct the `process` module.
+/// Construct the `process` module, which can run any command.
 pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    module_with_policy(Policy::unrestricted())
+}
+
+/// Construct the `process` module, where the commands which can be run and
+/// the environment they see are restricted by the given [Policy].
+pub fn module_with_policy(policy: Policy) -> Result<Module, ContextError> {
     let mut module = Module::with_crate("process");
+    let policy = Arc::new(policy);
+
     module.ty::<Command>()?;
     module.ty::<Child>()?;
+    module.ty::<ChildStdin>()?;
+    module.ty::<Stdio>()?;
     module.ty::<ExitStatus>()?;
     module.ty::<Output>()?;
+    module.ty::<Error>()?;
 
-    module.function(&["Command", "new"], Command::new)?;
+    module.function(&["Command", "new"], move |command: &str| {
+        Command::new(policy.clone(), command)
+    })?;
     module.inst_fn("spawn", Command::spawn)?;
     module.inst_fn("arg", Command::arg)?;
     module.inst_fn("args", Command::args)?;
+    module.inst_fn("env", Command::env)?;
+    module.inst_fn("env_remove", Command::env_remove)?;
+    module.inst_fn("env_clear", Command::env_clear)?;
+    module.inst_fn("current_dir", Command::current_dir)?;
+    module.inst_fn("stdin", Command::stdin)?;
+    module.inst_fn("stdout", Command::stdout)?;
+    module.inst_fn("stderr", Command::stderr)?;
+
+    module.function(&["Stdio", "piped"], Stdio::piped)?;
+    module.function(&["Stdio", "null"], Stdio::null)?;
+    module.function(&["Stdio", "inherit"], Stdio::inherit)?;
+
+    module.inst_fn("id", Child::id)?;
+    module.inst_fn("stdin", Child::stdin)?;
+    module.inst_fn("stdout_lines", Child::stdout_lines)?;
+    module.inst_fn("stderr_lines", Child::stderr_lines)?;
+    module.async_inst_fn("kill", Child::kill)?;
+    module.async_inst_fn("wait", Child::wait)?;
+    module.async_inst_fn("wait_timeout", Child::wait_timeout)?;
     module.async_inst_fn("wait_with_output", Child::wait_with_output)?;
+
+    module.async_inst_fn("write", ChildStdin::write)?;
+    module.inst_fn("close", ChildStdin::close)?;
+
     module.inst_fn(Protocol::STRING_DISPLAY, ExitStatus::display)?;
     module.inst_fn("code", ExitStatus::code)?;
+    module.inst_fn("success", ExitStatus::success)?;
+    module.inst_fn("signal", ExitStatus::signal)?;
+
+    module.inst_fn("is_denied", Error::is_denied)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, Error::display)?;
+    module.inst_fn(Protocol::STRING_DEBUG, Error::debug)?;
     Ok(module)
 }
 
+/// A policy restricting which commands the `process` module can run, and
+/// which environment variables are passed through to them.
+#[derive(Debug, Clone)]
+pub struct Policy {
+    /// Commands which can be run, or `None` if any command can be run.
+    commands: Option<Vec<String>>,
+    /// Environment variables which are passed through to commands, or `None`
+    /// if the whole environment is.
+    env: Option<Vec<String>>,
+}
+
+impl Policy {
+    /// Construct a policy which doesn't permit running any commands, and
+    /// doesn't pass through any environment variables.
+    pub fn new() -> Self {
+        Self {
+            commands: Some(Vec::new()),
+            env: Some(Vec::new()),
+        }
+    }
+
+    /// Construct a policy which permits running any command, with the whole
+    /// environment of the current process.
+    pub fn unrestricted() -> Self {
+        Self {
+            commands: None,
+            env: None,
+        }
+    }
+
+    /// Permit running the given command.
+    ///
+    /// The command is compared to the one a script is running exactly, so
+    /// permitting `git` doesn't permit `/usr/bin/git` or the other way
+    /// around.
+    pub fn allow_command<S>(mut self, command: S) -> Self
+    where
+        S: Into<String>,
+    {
+        if let Some(commands) = &mut self.commands {
+            commands.push(command.into());
+        }
+
+        self
+    }
+
+    /// Pass the given environment variable through to commands, if it's set
+    /// in the current process.
+    pub fn allow_env<S>(mut self, name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        if let Some(env) = &mut self.env {
+            env.push(name.into());
+        }
+
+        self
+    }
+
+    /// Check that running the given command is permitted.
+    fn check(&self, command: &str) -> Result<(), Error> {
+        match &self.commands {
+            Some(commands) if !commands.iter().any(|c| c == command) => Err(Error {
+                inner: ErrorKind::Denied {
+                    command: command.to_owned(),
+                },
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Restrict the environment of the given command.
+    fn apply_env(&self, command: &mut process::Command) {
+        if let Some(env) = &self.env {
+            command.env_clear();
+
+            for name in env {
+                if let Some(value) = std::env::var_os(name) {
+                    command.env(name, value);
+                }
+            }
+        }
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An error raised when running a command.
+#[derive(Debug, Any)]
+pub struct Error {
+    inner: ErrorKind,
+}
+
+#[derive(Debug)]
+enum ErrorKind {
+    Io(io::Error),
+    Denied { command: String },
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self {
+            inner: ErrorKind::Io(error),
+        }
+    }
+}
+
+impl Error {
+    /// Test if the error was raised because running the command was denied
+    /// by the policy of the module.
+    fn is_denied(&self) -> bool {
+        matches!(self.inner, ErrorKind::Denied { .. })
+    }
+
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        match &self.inner {
+            ErrorKind::Io(error) => write!(buf, "{}", error),
+            ErrorKind::Denied { command } => {
+                write!(buf, "access denied: cannot run `{}`", command)
+            }
+        }
+    }
+
+    fn debug(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{:?}", self.inner)
+    }
+}
+
 #[derive(Any)]
 struct Command {
+    policy: Arc<Policy>,
+    command: String,
     inner: process::Command,
 }
 
 impl Command {
     /// Construct a new command.
-    fn new(command: &str) -> Self {
+    ///
+    /// The environment of the command is restricted by the policy up front,
+    /// so that the script can still modify it.
+    fn new(policy: Arc<Policy>, command: &str) -> Self {
+        let mut inner = process::Command::new(command);
+        policy.apply_env(&mut inner);
+
         Self {
-            inner: process::Command::new(command),
+            policy,
+            command: command.to_owned(),
+            inner,
         }
     }
 
@@ -91,14 +324,95 @@ impl Command {
         self.inner.arg(arg);
     }
 
+    /// Set an environment variable.
+    fn env(&mut self, key: &str, value: &str) {
+        self.inner.env(key, value);
+    }
+
+    /// Remove an environment variable.
+    fn env_remove(&mut self, key: &str) {
+        self.inner.env_remove(key);
+    }
+
+    /// Clear all environment variables.
+    fn env_clear(&mut self) {
+        self.inner.env_clear();
+    }
+
+    /// Set the working directory of the command.
+    fn current_dir(&mut self, dir: &str) {
+        self.inner.current_dir(dir);
+    }
+
+    /// Configure the stdin of the command.
+    fn stdin(&mut self, stdio: &Stdio) {
+        self.inner.stdin(stdio.to_std());
+    }
+
+    /// Configure the stdout of the command.
+    fn stdout(&mut self, stdio: &Stdio) {
+        self.inner.stdout(stdio.to_std());
+    }
+
+    /// Configure the stderr of the command.
+    fn stderr(&mut self, stdio: &Stdio) {
+        self.inner.stderr(stdio.to_std());
+    }
+
     /// Spawn the command.
-    fn spawn(mut self) -> io::Result<Child> {
+    fn spawn(mut self) -> Result<Child, Error> {
+        self.policy.check(&self.command)?;
+
         Ok(Child {
             inner: Some(self.inner.spawn()?),
         })
     }
 }
 
+/// How a stdio stream of a command is configured.
+#[derive(Debug, Clone, Copy, Any)]
+struct Stdio {
+    kind: StdioKind,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum StdioKind {
+    /// A pipe is arranged between the command and the script.
+    Piped,
+    /// The stream is ignored.
+    Null,
+    /// The stream is inherited from the current process.
+    Inherit,
+}
+
+impl Stdio {
+    fn piped() -> Self {
+        Self {
+            kind: StdioKind::Piped,
+        }
+    }
+
+    fn null() -> Self {
+        Self {
+            kind: StdioKind::Null,
+        }
+    }
+
+    fn inherit() -> Self {
+        Self {
+            kind: StdioKind::Inherit,
+        }
+    }
+
+    fn to_std(self) -> std::process::Stdio {
+        match self.kind {
+            StdioKind::Piped => std::process::Stdio::piped(),
+            StdioKind::Null => std::process::Stdio::null(),
+            StdioKind::Inherit => std::process::Stdio::inherit(),
+        }
+    }
+}
+
 #[derive(Any)]
 struct Child {
     // we use an option to avoid a panic if we try to complete the child process
@@ -109,9 +423,65 @@ struct Child {
 }
 
 impl Child {
+    /// Access the child, or panic if it has already been completed.
+    fn inner(&mut self) -> Result<&mut process::Child, VmError> {
+        self.inner
+            .as_mut()
+            .ok_or_else(|| VmError::panic("already completed"))
+    }
+
+    /// The OS-assigned process identifier of the child, or `None` if it has
+    /// exited.
+    fn id(&mut self) -> Result<Option<u32>, VmError> {
+        Ok(self.inner()?.id())
+    }
+
+    /// Take the handle for writing to the stdin of the child, if it was
+    /// piped.
+    fn stdin(&mut self) -> Result<Option<ChildStdin>, VmError> {
+        let inner = self.inner()?.stdin.take();
+        Ok(inner.map(|inner| ChildStdin { inner: Some(inner) }))
+    }
+
+    /// Take the stdout of the child as a stream of lines, if it was piped.
+    fn stdout_lines(&mut self) -> Result<Option<Stream>, VmError> {
+        Ok(self.inner()?.stdout.take().map(lines))
+    }
+
+    /// Take the stderr of the child as a stream of lines, if it was piped.
+    fn stderr_lines(&mut self) -> Result<Option<Stream>, VmError> {
+        Ok(self.inner()?.stderr.take().map(lines))
+    }
+
+    /// Forcibly kill the child and wait for it to exit.
+    async fn kill(&mut self) -> Result<Result<(), Error>, VmError> {
+        Ok(self.inner()?.kill().await.map_err(Error::from))
+    }
+
+    /// Wait for the child to exit.
+    async fn wait(&mut self) -> Result<Result<ExitStatus, Error>, VmError> {
+        let status = self.inner()?.wait().await;
+        Ok(status.map(|status| ExitStatus { status }).map_err(Error::from))
+    }
+
+    /// Wait for the child to exit for at most the given number of seconds.
+    ///
+    /// Returns `None` if the child is still running once the timeout has
+    /// elapsed, in which case it can be killed with `kill`.
+    async fn wait_timeout(&mut self, seconds: f64) -> Result<Result<Option<ExitStatus>, Error>, VmError> {
+        let timeout = Duration::try_from_secs_f64(seconds).map_err(VmError::panic)?;
+        let inner = self.inner()?;
+
+        match tokio::time::timeout(timeout, inner.wait()).await {
+            Ok(Ok(status)) => Ok(Ok(Some(ExitStatus { status }))),
+            Ok(Err(error)) => Ok(Err(Error::from(error))),
+            Err(..) => Ok(Ok(None)),
+        }
+    }
+
     // Returns a future that will resolve to an Output, containing the exit
     // status, stdout, and stderr of the child process.
-    async fn wait_with_output(self) -> Result<io::Result<Output>, VmError> {
+    async fn wait_with_output(self) -> Result<Result<Output, Error>, VmError> {
         let inner = match self.inner {
             Some(inner) => inner,
             None => {
@@ -121,7 +491,7 @@ impl Child {
 
         let output = match inner.wait_with_output().await {
             Ok(output) => output,
-            Err(error) => return Ok(Err(error)),
+            Err(error) => return Ok(Err(Error::from(error))),
         };
 
         Ok(Ok(Output {
@@ -132,6 +502,58 @@ impl Child {
     }
 }
 
+/// Construct a stream of the lines read from the given reader.
+///
+/// The stream ends after the first error, which it produces as its last value.
+fn lines<R>(reader: R) -> Stream
+where
+    R: 'static + AsyncRead + Unpin,
+{
+    let lines = BufReader::new(reader).lines();
+
+    Stream::from_stream(futures_util::stream::unfold(Some(lines), |lines| async move {
+        let mut lines = lines?;
+
+        let (line, lines) = match lines.next_line().await {
+            Ok(Some(line)) => (Ok(line), Some(lines)),
+            Ok(None) => return None,
+            Err(error) => (Err(Error::from(error)), None),
+        };
+
+        Some((line.to_value(), lines))
+    }))
+}
+
+/// A handle for writing to the stdin of a child.
+#[derive(Any)]
+struct ChildStdin {
+    inner: Option<process::ChildStdin>,
+}
+
+impl ChildStdin {
+    /// Write the given string or bytes to the stdin of the child.
+    async fn write(&mut self, contents: Value) -> Result<Result<(), Error>, VmError> {
+        let contents = match contents {
+            Value::String(string) => string.borrow_ref()?.as_bytes().to_vec(),
+            Value::StaticString(string) => string.as_bytes().to_vec(),
+            Value::Bytes(bytes) => bytes.borrow_ref()?.to_vec(),
+            actual => return Err(VmError::expected::<String>(actual.type_info()?)),
+        };
+
+        let inner = match &mut self.inner {
+            Some(inner) => inner,
+            None => return Err(VmError::panic("stdin is closed")),
+        };
+
+        Ok(inner.write_all(&contents).await.map_err(Error::from))
+    }
+
+    /// Close the stdin of the child, signalling the end of its input.
+    fn close(&mut self) {
+        self.inner = None;
+    }
+}
+
 #[derive(Any)]
 struct Output {
     #[rune(get)]
@@ -149,11 +571,31 @@ struct ExitStatus {
 
 impl ExitStatus {
     fn display(&self, buf: &mut String) -> fmt::Result {
-        use std::fmt::Write as _;
         write!(buf, "{}", self.status)
     }
 
     fn code(&self) -> Option<i32> {
         self.status.code()
     }
+
+    /// Test if the child exited successfully.
+    fn success(&self) -> bool {
+        self.status.success()
+    }
+
+    /// The signal which terminated the child, if any.
+    ///
+    /// This is always `None` on platforms other than Unix.
+    fn signal(&self) -> Option<i32> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt as _;
+            self.status.signal()
+        }
+
+        #[cfg(not(unix))]
+        {
+            None
+        }
+    }
 }
@@ -0,0 +1,278 @@
+//! The native `regex` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = {version = "0.9.0", features = ["regex"]}
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! # fn main() -> runestick::Result<()> {
+//! let mut context = runestick::Context::with_default_modules()?;
+//! context.install(&rune_modules::regex::module(true)?)?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use regex::Regex;
+//!
+//! fn main() {
+//!     let re = Regex::new(r"(?P<year>\d{4})-(?P<month>\d{2})")?;
+//!
+//!     if let Some(captures) = re.captures("released 2021-03") {
+//!         println(`year: ${captures["year"]}, month: ${captures["month"]}`);
+//!     }
+//!
+//!     let years = re.find_iter("2020-01 and 2021-02").map(|m| m.as_str()).collect_vec();
+//!     let shouting = re.replace_all("2021-03", |captures| captures["year"].to_uppercase());
+//! }
+//! ```
+
+use runestick::{
+    Any, ContextError, FromValue, Function, Iterator, Module, Object, Protocol, ToValue, Value, VmError,
+};
+use std::fmt;
+use std::fmt::Write as _;
+
+/// Construct the `regex` module.
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut module = Module::with_crate("regex");
+
+    module.ty::<Regex>()?;
+    module.ty::<Match>()?;
+    module.ty::<Error>()?;
+
+    module.function(&["Regex", "new"], Regex::new)?;
+    module.inst_fn("is_match", Regex::is_match)?;
+    module.inst_fn("find", Regex::find)?;
+    module.inst_fn("find_iter", Regex::find_iter)?;
+    module.inst_fn("captures", Regex::captures)?;
+    module.inst_fn("captures_iter", Regex::captures_iter)?;
+    module.inst_fn("replace", Regex::replace)?;
+    module.inst_fn("replace_all", Regex::replace_all)?;
+    module.inst_fn("split", Regex::split)?;
+    module.inst_fn("as_str", Regex::as_str)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, Regex::display)?;
+
+    module.inst_fn("start", Match::start)?;
+    module.inst_fn("end", Match::end)?;
+    module.inst_fn("as_str", Match::as_str)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, Match::display)?;
+
+    module.inst_fn(Protocol::STRING_DISPLAY, Error::display)?;
+    module.inst_fn(Protocol::STRING_DEBUG, Error::debug)?;
+    Ok(module)
+}
+
+/// An error raised when compiling a regular expression.
+#[derive(Debug, Any)]
+pub struct Error {
+    inner: regex::Error,
+}
+
+impl From<regex::Error> for Error {
+    fn from(inner: regex::Error) -> Self {
+        Self { inner }
+    }
+}
+
+impl Error {
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{}", self.inner)
+    }
+
+    fn debug(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{:?}", self.inner)
+    }
+}
+
+/// A compiled regular expression.
+#[derive(Debug, Any)]
+pub struct Regex {
+    inner: regex::Regex,
+}
+
+impl Regex {
+    /// Compile a regular expression.
+    fn new(pattern: &str) -> Result<Self, Error> {
+        Ok(Self {
+            inner: regex::Regex::new(pattern)?,
+        })
+    }
+
+    /// Test if the regular expression matches anywhere in the given text.
+    fn is_match(&self, text: &str) -> bool {
+        self.inner.is_match(text)
+    }
+
+    /// Find the leftmost first match in the given text.
+    fn find(&self, text: &str) -> Option<Match> {
+        self.inner.find(text).map(Match::new)
+    }
+
+    /// Iterate over all non-overlapping matches in the given text.
+    fn find_iter(&self, text: &str) -> Iterator {
+        let matches = self.inner.find_iter(text).map(Match::new).collect::<Vec<_>>();
+        Iterator::from("regex::FindIter", matches.into_iter())
+    }
+
+    /// Get the capture groups of the leftmost first match in the given text.
+    ///
+    /// Groups are stored in an object under their index, like `"0"` for the
+    /// whole match, and named groups are also stored under their name.
+    fn captures(&self, text: &str) -> Result<Option<Object>, VmError> {
+        match self.inner.captures(text) {
+            Some(captures) => Ok(Some(self.captures_object(&captures)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Iterate over the capture groups of all non-overlapping matches in the
+    /// given text.
+    fn captures_iter(&self, text: &str) -> Result<Iterator, VmError> {
+        let mut objects = Vec::new();
+
+        for captures in self.inner.captures_iter(text) {
+            objects.push(self.captures_object(&captures)?);
+        }
+
+        Ok(Iterator::from("regex::CapturesIter", objects.into_iter()))
+    }
+
+    /// Replace the leftmost first match in the given text.
+    ///
+    /// See [Regex::replace_all] for how the replacement is specified.
+    fn replace(&self, text: &str, replacement: Value) -> Result<String, VmError> {
+        self.replace_n(text, 1, replacement)
+    }
+
+    /// Replace all non-overlapping matches in the given text.
+    ///
+    /// The replacement is either a string, where `$name` and `$1` refer to
+    /// capture groups, or a function which is called with the capture groups
+    /// of each match and returns the string to replace it with.
+    fn replace_all(&self, text: &str, replacement: Value) -> Result<String, VmError> {
+        self.replace_n(text, 0, replacement)
+    }
+
+    /// Split the given text by matches of the regular expression.
+    fn split(&self, text: &str) -> Vec<String> {
+        self.inner.split(text).map(String::from).collect()
+    }
+
+    /// The pattern the regular expression was compiled from.
+    fn as_str(&self) -> String {
+        self.inner.as_str().to_owned()
+    }
+
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{}", self.inner)
+    }
+
+    /// Replace up to `limit` matches, where a limit of `0` replaces all of
+    /// them.
+    fn replace_n(&self, text: &str, limit: usize, replacement: Value) -> Result<String, VmError> {
+        let replacement = match replacement {
+            Value::Function(function) => function,
+            replacement => {
+                let replacement = String::from_value(replacement)?;
+                return Ok(self
+                    .inner
+                    .replacen(text, limit, replacement.as_str())
+                    .into_owned());
+            }
+        };
+
+        let function = replacement.borrow_ref()?;
+        let mut output = String::with_capacity(text.len());
+        let mut last = 0;
+
+        for (n, captures) in self.inner.captures_iter(text).enumerate() {
+            if limit > 0 && n >= limit {
+                break;
+            }
+
+            // NB: group 0 always participates in a match.
+            let m = captures.get(0).expect("group 0 is always present");
+            let object = self.captures_object(&captures)?;
+            let replaced = Function::call::<_, String>(&function, (object,))?;
+
+            output.push_str(&text[last..m.start()]);
+            output.push_str(&replaced);
+            last = m.end();
+        }
+
+        output.push_str(&text[last..]);
+        Ok(output)
+    }
+
+    /// Convert capture groups into an object.
+    ///
+    /// Every group which participated in the match is stored under its index,
+    /// like `"0"` for the whole match, and named groups are additionally
+    /// stored under their name.
+    fn captures_object(&self, captures: &regex::Captures<'_>) -> Result<Object, VmError> {
+        let mut object = Object::new();
+
+        for (index, name) in self.inner.capture_names().enumerate() {
+            let group = match captures.get(index) {
+                Some(group) => group.as_str().to_owned(),
+                None => continue,
+            };
+
+            object.insert(index.to_string(), group.clone().to_value()?);
+
+            if let Some(name) = name {
+                object.insert(name.to_owned(), group.to_value()?);
+            }
+        }
+
+        Ok(object)
+    }
+}
+
+/// A single match of a regular expression.
+#[derive(Debug, Any)]
+pub struct Match {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+impl Match {
+    fn new(m: regex::Match<'_>) -> Self {
+        Self {
+            start: m.start(),
+            end: m.end(),
+            text: m.as_str().to_owned(),
+        }
+    }
+
+    /// The byte offset where the match starts.
+    fn start(&self) -> usize {
+        self.start
+    }
+
+    /// The byte offset where the match ends.
+    fn end(&self) -> usize {
+        self.end
+    }
+
+    /// The matched text.
+    fn as_str(&self) -> String {
+        self.text.clone()
+    }
+
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        buf.write_str(&self.text)
+    }
+}
@@ -23,39 +23,656 @@
 //! Use it in Rune:
 //!
 //! ```rust,ignore
-//! use time;
+//! use time::{Duration, Instant, SystemTime};
 //!
 //! fn main() {
-//!     time::sleep(time::Duration::from_secs(10)).await;
-//!     println("Message after 10 seconds!");
+//!     let start = Instant::now();
+//!     time::sleep(Duration::from_millis(100)).await;
+//!     println(`Slept for ${start.elapsed()}`);
+//!
+//!     let ticks = time::interval(Duration::from_secs(1));
+//!     ticks.next().await;
+//!
+//!     match time::timeout(async { 42 }, Duration::from_secs(1)).await {
+//!         Ok(value) => println(`Got ${value}`),
+//!         Err(error) => println(`Timed out: ${error}`),
+//!     }
+//!
+//!     let now = SystemTime::now();
+//!     println(`It's ${now.to_rfc3339()}, or ${now.unix_timestamp()} since the epoch`);
+//!     let then = SystemTime::parse_rfc3339("2021-03-01T12:00:00+01:00")?;
 //! }
 //! ```
+//!
+//! ## Virtual time
+//!
+//! With the `time-virtual` feature enabled, the module can be constructed with
+//! a virtual [Clock] through
+//! [module_with_clock] or [crate::with_clock]. Once started, a virtual clock
+//! pauses the time of the tokio runtime it's running in, and time only moves
+//! forward when it's advanced or when the runtime has nothing else to do but
+//! wait for a timer. This makes it possible to test scripts which sleep, use
+//! intervals or timeouts deterministically and without waiting.
+//!
+//! ```rust,no_run
+//! # #[cfg(not(feature = "time-virtual"))] fn main() {}
+//! # #[cfg(feature = "time-virtual")]
+//! # fn main() -> runestick::Result<()> {
+//! use rune_modules::time::Clock;
+//!
+//! let clock = Clock::virtual_at(std::time::UNIX_EPOCH);
+//! let context = rune_modules::with_clock(true, clock.clone())?;
+//!
+//! let runtime = tokio::runtime::Builder::new_current_thread()
+//!     .enable_all()
+//!     .build()?;
+//!
+//! runtime.block_on(async move {
+//!     clock.start();
+//!     // Execute scripts which use `time::advance` and timers.
+//! });
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The feature enables the `test-util` feature of tokio, which shouldn't be
+//! enabled in production builds.
+//!
+//! Scripts can advance a started virtual clock themselves:
+//!
+//! ```rust,ignore
+//! time::advance(Duration::from_secs(60)).await?;
+//! ```
 
-use runestick::{Any, ContextError, Module};
+use runestick::{
+    Any, ContextError, FromValue, Module, Protocol, Stream, ToValue, Value, VmError, VmErrorKind,
+};
+use std::cmp;
+use std::convert::TryFrom as _;
+use std::fmt;
+use std::fmt::Write as _;
+#[cfg(feature = "time-virtual")]
+use std::sync::{Arc, Mutex};
+use std::time;
 
 /// Construct the `time` module.
 pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    module_with_clock(Clock::system())
+}
+
+/// Construct the `time` module using the given [Clock].
+pub fn module_with_clock(clock: Clock) -> Result<Module, ContextError> {
     let mut module = Module::with_crate("time");
+
+    module.ty::<Duration>()?;
+    module.ty::<Instant>()?;
+    module.ty::<SystemTime>()?;
+    module.ty::<Error>()?;
+
     module.function(&["Duration", "from_secs"], Duration::from_secs)?;
+    module.function(&["Duration", "from_millis"], Duration::from_millis)?;
+    module.function(&["Duration", "from_micros"], Duration::from_micros)?;
+    module.function(&["Duration", "from_nanos"], Duration::from_nanos)?;
+    module.function(&["Duration", "from_secs_f64"], Duration::from_secs_f64)?;
+    module.inst_fn("as_secs", Duration::as_secs)?;
+    module.inst_fn("as_millis", Duration::as_millis)?;
+    module.inst_fn("as_micros", Duration::as_micros)?;
+    module.inst_fn("as_nanos", Duration::as_nanos)?;
+    module.inst_fn("as_secs_f64", Duration::as_secs_f64)?;
+    module.inst_fn("subsec_millis", Duration::subsec_millis)?;
+    module.inst_fn("subsec_nanos", Duration::subsec_nanos)?;
+    module.inst_fn(Protocol::ADD, Duration::add)?;
+    module.inst_fn(Protocol::ADD_ASSIGN, Duration::add_assign)?;
+    module.inst_fn(Protocol::SUB, Duration::sub)?;
+    module.inst_fn(Protocol::SUB_ASSIGN, Duration::sub_assign)?;
+    module.inst_fn(Protocol::MUL, Duration::mul)?;
+    module.inst_fn(Protocol::DIV, Duration::div)?;
+    module.inst_fn(Protocol::EQ, Duration::eq)?;
+    module.inst_fn(Protocol::CMP, Duration::cmp)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, Duration::display)?;
+    module.inst_fn(Protocol::STRING_DEBUG, Duration::display)?;
+
+    module.function(&["Instant", "now"], Instant::now)?;
+    module.inst_fn("elapsed", Instant::elapsed)?;
+    module.inst_fn("duration_since", Instant::duration_since)?;
+    module.inst_fn(Protocol::ADD, Instant::add)?;
+    module.inst_fn(Protocol::SUB, Instant::sub)?;
+    module.inst_fn(Protocol::EQ, Instant::eq)?;
+    module.inst_fn(Protocol::CMP, Instant::cmp)?;
+    module.inst_fn(Protocol::STRING_DEBUG, Instant::debug)?;
+
+    module.function(&["SystemTime", "now"], {
+        let clock = clock.clone();
+        move || SystemTime::new(clock.system_time())
+    })?;
+    module.function(&["SystemTime", "unix_epoch"], SystemTime::unix_epoch)?;
+    module.function(
+        &["SystemTime", "from_unix_timestamp"],
+        SystemTime::from_unix_timestamp,
+    )?;
+    module.function(
+        &["SystemTime", "from_unix_timestamp_millis"],
+        SystemTime::from_unix_timestamp_millis,
+    )?;
+    module.function(&["SystemTime", "parse_rfc3339"], SystemTime::parse_rfc3339)?;
+    module.inst_fn("unix_timestamp", SystemTime::unix_timestamp)?;
+    module.inst_fn("unix_timestamp_millis", SystemTime::unix_timestamp_millis)?;
+    module.inst_fn("duration_since", SystemTime::duration_since)?;
+    module.inst_fn("elapsed", {
+        let clock = clock.clone();
+        move |this: &SystemTime| this.elapsed(&clock)
+    })?;
+    module.inst_fn("to_rfc3339", SystemTime::to_rfc3339)?;
+    module.inst_fn(Protocol::ADD, SystemTime::add)?;
+    module.inst_fn(Protocol::SUB, SystemTime::sub)?;
+    module.inst_fn(Protocol::EQ, SystemTime::eq)?;
+    module.inst_fn(Protocol::CMP, SystemTime::cmp)?;
+    module.inst_fn(Protocol::STRING_DISPLAY, SystemTime::display)?;
+    module.inst_fn(Protocol::STRING_DEBUG, SystemTime::display)?;
+
+    module.inst_fn(Protocol::STRING_DISPLAY, Error::display)?;
+    module.inst_fn(Protocol::STRING_DEBUG, Error::debug)?;
+
     module.async_function(&["sleep"], sleep)?;
+    module.function(&["interval"], interval)?;
+    module.async_function(&["timeout"], timeout)?;
+    module.async_function(&["advance"], move |duration: Duration| {
+        let clock = clock.clone();
+        async move { clock.advance_checked(duration.inner).await }
+    })?;
     Ok(module)
 }
 
+/// The clock used by the `time` module.
+///
+/// This is either the [system clock](Clock::system), or with the
+/// `time-virtual` feature a virtual clock which only moves forward when the
+/// time of the tokio runtime is advanced.
+#[derive(Debug, Clone)]
+pub struct Clock {
+    #[cfg(feature = "time-virtual")]
+    inner: Option<Arc<Virtual>>,
+}
+
+#[cfg(feature = "time-virtual")]
+#[derive(Debug)]
+struct Virtual {
+    /// The wall-clock time of the clock when it was started.
+    epoch: time::SystemTime,
+    /// The instant at which the clock was started.
+    start: Mutex<Option<tokio::time::Instant>>,
+}
+
+impl Clock {
+    /// Construct a clock which follows the system time.
+    pub fn system() -> Self {
+        Self {
+            #[cfg(feature = "time-virtual")]
+            inner: None,
+        }
+    }
+
+    /// Construct a virtual clock, where the wall-clock time is `epoch` when
+    /// the clock is [started](Clock::start).
+    #[cfg(feature = "time-virtual")]
+    pub fn virtual_at(epoch: time::SystemTime) -> Self {
+        Self {
+            inner: Some(Arc::new(Virtual {
+                epoch,
+                start: Mutex::new(None),
+            })),
+        }
+    }
+
+    /// Test if this is a virtual clock.
+    pub fn is_virtual(&self) -> bool {
+        #[cfg(feature = "time-virtual")]
+        {
+            self.inner.is_some()
+        }
+
+        #[cfg(not(feature = "time-virtual"))]
+        {
+            false
+        }
+    }
+
+    /// Start a virtual clock by pausing the time of the current tokio
+    /// runtime, resetting the wall-clock time to the epoch of the clock.
+    ///
+    /// A virtual clock can be started again in a new runtime. This does
+    /// nothing for the system clock.
+    ///
+    /// # Panics
+    ///
+    /// This panics if called outside of a current-thread tokio runtime, or if
+    /// the time of the runtime is already paused.
+    pub fn start(&self) {
+        #[cfg(feature = "time-virtual")]
+        if let Some(inner) = &self.inner {
+            tokio::time::pause();
+            *inner.start.lock().expect("clock lock poisoned") = Some(tokio::time::Instant::now());
+        }
+    }
+
+    /// The current wall-clock time according to this clock.
+    ///
+    /// This is the epoch of a virtual clock until it's started.
+    pub fn system_time(&self) -> time::SystemTime {
+        #[cfg(feature = "time-virtual")]
+        if let Some(inner) = &self.inner {
+            return match *inner.start.lock().expect("clock lock poisoned") {
+                Some(start) => inner.epoch + start.elapsed(),
+                None => inner.epoch,
+            };
+        }
+
+        time::SystemTime::now()
+    }
+
+    /// Advance a started virtual clock by the given duration, firing any
+    /// timers which expire in the meantime.
+    ///
+    /// # Panics
+    ///
+    /// This panics if this is not a virtual clock which has been started in
+    /// the current runtime.
+    #[cfg(feature = "time-virtual")]
+    pub async fn advance(&self, duration: time::Duration) {
+        if let Err(error) = self.advance_checked(duration).await {
+            panic!("{}", error);
+        }
+    }
+
+    async fn advance_checked(&self, duration: time::Duration) -> Result<(), VmError> {
+        #[cfg(feature = "time-virtual")]
+        if let Some(inner) = &self.inner {
+            if inner.start.lock().expect("clock lock poisoned").is_none() {
+                return Err(VmError::panic("virtual clock has not been started"));
+            }
+
+            tokio::time::advance(duration).await;
+            return Ok(());
+        }
+
+        let _ = duration;
+        Err(VmError::panic(
+            "time can only be advanced with a virtual clock",
+        ))
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::system()
+    }
+}
+
+/// An error raised by the time module.
+#[derive(Debug, Any)]
+pub struct Error {
+    inner: ErrorKind,
+}
+
+#[derive(Debug)]
+enum ErrorKind {
+    Timeout,
+    Parse(chrono::ParseError),
+    SystemTime(time::SystemTimeError),
+}
+
+impl From<chrono::ParseError> for Error {
+    fn from(error: chrono::ParseError) -> Self {
+        Self {
+            inner: ErrorKind::Parse(error),
+        }
+    }
+}
+
+impl From<time::SystemTimeError> for Error {
+    fn from(error: time::SystemTimeError) -> Self {
+        Self {
+            inner: ErrorKind::SystemTime(error),
+        }
+    }
+}
+
+impl Error {
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        match &self.inner {
+            ErrorKind::Timeout => write!(buf, "deadline has elapsed"),
+            ErrorKind::Parse(error) => write!(buf, "{}", error),
+            ErrorKind::SystemTime(error) => write!(buf, "{}", error),
+        }
+    }
+
+    fn debug(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{:?}", self.inner)
+    }
+}
+
+/// A span of time.
 #[derive(Debug, Clone, Copy, Any)]
 struct Duration {
-    inner: tokio::time::Duration,
+    inner: time::Duration,
 }
 
 impl Duration {
+    fn new(inner: time::Duration) -> Self {
+        Self { inner }
+    }
+
     /// Construct a duration from seconds.
     fn from_secs(secs: u64) -> Self {
+        Self::new(time::Duration::from_secs(secs))
+    }
+
+    /// Construct a duration from milliseconds.
+    fn from_millis(millis: u64) -> Self {
+        Self::new(time::Duration::from_millis(millis))
+    }
+
+    /// Construct a duration from microseconds.
+    fn from_micros(micros: u64) -> Self {
+        Self::new(time::Duration::from_micros(micros))
+    }
+
+    /// Construct a duration from nanoseconds.
+    fn from_nanos(nanos: u64) -> Self {
+        Self::new(time::Duration::from_nanos(nanos))
+    }
+
+    /// Construct a duration from fractional seconds.
+    fn from_secs_f64(secs: f64) -> Result<Self, VmError> {
+        let inner = time::Duration::try_from_secs_f64(secs).map_err(VmError::panic)?;
+        Ok(Self::new(inner))
+    }
+
+    fn as_secs(&self) -> u64 {
+        self.inner.as_secs()
+    }
+
+    fn as_millis(&self) -> Result<u64, VmError> {
+        checked_u64(self.inner.as_millis())
+    }
+
+    fn as_micros(&self) -> Result<u64, VmError> {
+        checked_u64(self.inner.as_micros())
+    }
+
+    fn as_nanos(&self) -> Result<u64, VmError> {
+        checked_u64(self.inner.as_nanos())
+    }
+
+    fn as_secs_f64(&self) -> f64 {
+        self.inner.as_secs_f64()
+    }
+
+    fn subsec_millis(&self) -> u32 {
+        self.inner.subsec_millis()
+    }
+
+    fn subsec_nanos(&self) -> u32 {
+        self.inner.subsec_nanos()
+    }
+
+    fn add(&self, other: &Duration) -> Result<Self, VmError> {
+        let inner = self.inner.checked_add(other.inner).ok_or_else(overflow)?;
+        Ok(Self::new(inner))
+    }
+
+    fn add_assign(&mut self, other: &Duration) -> Result<(), VmError> {
+        *self = self.add(other)?;
+        Ok(())
+    }
+
+    fn sub(&self, other: &Duration) -> Result<Self, VmError> {
+        let inner = self.inner.checked_sub(other.inner).ok_or_else(underflow)?;
+        Ok(Self::new(inner))
+    }
+
+    fn sub_assign(&mut self, other: &Duration) -> Result<(), VmError> {
+        *self = self.sub(other)?;
+        Ok(())
+    }
+
+    fn mul(&self, n: u32) -> Result<Self, VmError> {
+        let inner = self.inner.checked_mul(n).ok_or_else(overflow)?;
+        Ok(Self::new(inner))
+    }
+
+    fn div(&self, n: u32) -> Result<Self, VmError> {
+        let inner = self
+            .inner
+            .checked_div(n)
+            .ok_or_else(|| VmError::from(VmErrorKind::DivideByZero))?;
+        Ok(Self::new(inner))
+    }
+
+    fn eq(&self, other: &Duration) -> bool {
+        self.inner == other.inner
+    }
+
+    fn cmp(&self, other: &Duration) -> cmp::Ordering {
+        self.inner.cmp(&other.inner)
+    }
+
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{:?}", self.inner)
+    }
+}
+
+/// A monotonic point in time.
+///
+/// This follows the time of the tokio runtime, so it's affected by
+/// [tokio::time::pause] and [tokio::time::advance].
+#[derive(Debug, Clone, Copy, Any)]
+struct Instant {
+    inner: tokio::time::Instant,
+}
+
+impl Instant {
+    /// Get the current instant.
+    fn now() -> Self {
         Self {
-            inner: tokio::time::Duration::from_secs(secs),
+            inner: tokio::time::Instant::now(),
         }
     }
+
+    /// The time elapsed since the instant, or zero if it's in the future.
+    fn elapsed(&self) -> Duration {
+        Duration::new(self.inner.elapsed())
+    }
+
+    /// The time elapsed from another instant to this one, or zero if the
+    /// other instant is later.
+    fn duration_since(&self, earlier: &Instant) -> Duration {
+        Duration::new(self.inner.saturating_duration_since(earlier.inner))
+    }
+
+    fn add(&self, duration: &Duration) -> Result<Self, VmError> {
+        let inner = self
+            .inner
+            .checked_add(duration.inner)
+            .ok_or_else(overflow)?;
+        Ok(Self { inner })
+    }
+
+    fn sub(&self, duration: &Duration) -> Result<Self, VmError> {
+        let inner = self
+            .inner
+            .checked_sub(duration.inner)
+            .ok_or_else(underflow)?;
+        Ok(Self { inner })
+    }
+
+    fn eq(&self, other: &Instant) -> bool {
+        self.inner == other.inner
+    }
+
+    fn cmp(&self, other: &Instant) -> cmp::Ordering {
+        self.inner.cmp(&other.inner)
+    }
+
+    fn debug(&self, buf: &mut String) -> fmt::Result {
+        write!(buf, "{:?}", self.inner)
+    }
+}
+
+/// A point in wall-clock time.
+#[derive(Debug, Clone, Copy, Any)]
+struct SystemTime {
+    inner: time::SystemTime,
+}
+
+impl SystemTime {
+    fn new(inner: time::SystemTime) -> Self {
+        Self { inner }
+    }
+
+    /// The unix epoch, `1970-01-01T00:00:00Z`.
+    fn unix_epoch() -> Self {
+        Self::new(time::UNIX_EPOCH)
+    }
+
+    /// Construct a system time from seconds relative to the unix epoch.
+    fn from_unix_timestamp(secs: i64) -> Result<Self, VmError> {
+        Self::from_unix_offset(secs, time::Duration::from_secs)
+    }
+
+    /// Construct a system time from milliseconds relative to the unix epoch.
+    fn from_unix_timestamp_millis(millis: i64) -> Result<Self, VmError> {
+        Self::from_unix_offset(millis, time::Duration::from_millis)
+    }
+
+    fn from_unix_offset(offset: i64, duration: fn(u64) -> time::Duration) -> Result<Self, VmError> {
+        let magnitude = duration(offset.unsigned_abs());
+
+        let inner = if offset < 0 {
+            time::UNIX_EPOCH.checked_sub(magnitude)
+        } else {
+            time::UNIX_EPOCH.checked_add(magnitude)
+        };
+
+        Ok(Self::new(inner.ok_or_else(overflow)?))
+    }
+
+    /// Parse an RFC 3339 timestamp, like `2021-03-01T12:00:00+01:00`.
+    fn parse_rfc3339(string: &str) -> Result<Self, Error> {
+        let date_time = chrono::DateTime::parse_from_rfc3339(string)?;
+        Ok(Self::new(date_time.into()))
+    }
+
+    /// Seconds since the unix epoch, negative if the time is before it.
+    fn unix_timestamp(&self) -> Result<i64, VmError> {
+        self.unix_offset(|d| d.as_secs() as i128)
+    }
+
+    /// Milliseconds since the unix epoch, negative if the time is before it.
+    fn unix_timestamp_millis(&self) -> Result<i64, VmError> {
+        self.unix_offset(|d| d.as_millis() as i128)
+    }
+
+    fn unix_offset(&self, unit: fn(time::Duration) -> i128) -> Result<i64, VmError> {
+        let offset = match self.inner.duration_since(time::UNIX_EPOCH) {
+            Ok(duration) => unit(duration),
+            Err(error) => -unit(error.duration()),
+        };
+
+        i64::try_from(offset).map_err(|_| overflow())
+    }
+
+    /// The time elapsed from an earlier system time to this one.
+    fn duration_since(&self, earlier: &SystemTime) -> Result<Duration, Error> {
+        Ok(Duration::new(self.inner.duration_since(earlier.inner)?))
+    }
+
+    /// The time elapsed since this system time.
+    fn elapsed(&self, clock: &Clock) -> Result<Duration, Error> {
+        Ok(Duration::new(
+            clock.system_time().duration_since(self.inner)?,
+        ))
+    }
+
+    /// Format the system time as an RFC 3339 timestamp in UTC.
+    #[allow(clippy::wrong_self_convention)]
+    fn to_rfc3339(&self) -> String {
+        let date_time = chrono::DateTime::<chrono::Utc>::from(self.inner);
+        date_time.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+    }
+
+    fn add(&self, duration: &Duration) -> Result<Self, VmError> {
+        let inner = self
+            .inner
+            .checked_add(duration.inner)
+            .ok_or_else(overflow)?;
+        Ok(Self::new(inner))
+    }
+
+    fn sub(&self, duration: &Duration) -> Result<Self, VmError> {
+        let inner = self
+            .inner
+            .checked_sub(duration.inner)
+            .ok_or_else(underflow)?;
+        Ok(Self::new(inner))
+    }
+
+    fn eq(&self, other: &SystemTime) -> bool {
+        self.inner == other.inner
+    }
+
+    fn cmp(&self, other: &SystemTime) -> cmp::Ordering {
+        self.inner.cmp(&other.inner)
+    }
+
+    fn display(&self, buf: &mut String) -> fmt::Result {
+        buf.write_str(&self.to_rfc3339())
+    }
 }
 
-/// Convert any value to a json string.
+/// Sleep for the given duration.
 async fn sleep(duration: &Duration) {
     tokio::time::sleep(duration.inner).await;
 }
+
+/// Construct a stream which produces an `Instant` every `period`, starting
+/// immediately.
+fn interval(period: &Duration) -> Result<Stream, VmError> {
+    if period.inner == time::Duration::from_secs(0) {
+        return Err(VmError::panic("interval period must be non-zero"));
+    }
+
+    let interval = tokio::time::interval(period.inner);
+
+    let stream = futures_util::stream::unfold(interval, |mut interval| async move {
+        let inner = interval.tick().await;
+        Some((Instant { inner }.to_value(), interval))
+    });
+
+    Ok(Stream::from_stream(stream))
+}
+
+/// Wait for the given future to complete, or error if it takes longer than
+/// the given duration.
+async fn timeout(future: Value, duration: Duration) -> Result<Result<Value, Error>, VmError> {
+    let future = runestick::Future::from_value(future)?;
+
+    match tokio::time::timeout(duration.inner, future).await {
+        Ok(value) => Ok(Ok(value?)),
+        Err(..) => Ok(Err(Error {
+            inner: ErrorKind::Timeout,
+        })),
+    }
+}
+
+fn checked_u64(value: u128) -> Result<u64, VmError> {
+    u64::try_from(value).map_err(|_| overflow())
+}
+
+fn overflow() -> VmError {
+    VmError::from(VmErrorKind::Overflow)
+}
+
+fn underflow() -> VmError {
+    VmError::from(VmErrorKind::Underflow)
+}
@@ -1,14 +1,15 @@
-use runestick::{ContextError, Module, Panic, Stack, Value, VmError};
-use std::io::Write as _;
+use runestick::modules::io::{self, Stream};
+use runestick::{ContextError, Module};
 
 /// Provide a bunch of `std` functions which does something appropriate to the
 /// wasm context.
+///
+/// Output written to both stdout and stderr is captured into the same buffer.
 pub fn module() -> Result<Module, ContextError> {
-    let mut module = Module::with_crate_item("std", &["io"]);
-    module.function(&["print"], print_impl)?;
-    module.function(&["println"], println_impl)?;
-    module.raw_fn(&["dbg"], dbg_impl)?;
-    Ok(module)
+    io::output_module(|_: Stream, bytes: &[u8]| {
+        OUT.lock().extend_from_slice(bytes);
+        Ok(())
+    })
 }
 
 lazy_static::lazy_static! {
@@ -22,22 +23,3 @@ pub fn drain_output() -> Option<String> {
     let o = std::mem::take(&mut *o);
     String::from_utf8(o).ok()
 }
-
-fn print_impl(m: &str) -> Result<(), Panic> {
-    write!(OUT.lock(), "{}", m).map_err(Panic::custom)
-}
-
-fn println_impl(m: &str) -> Result<(), Panic> {
-    writeln!(OUT.lock(), "{}", m).map_err(Panic::custom)
-}
-
-fn dbg_impl(stack: &mut Stack, args: usize) -> Result<(), VmError> {
-    let mut o = OUT.lock();
-
-    for value in stack.drain_stack_top(args)? {
-        writeln!(o, "{:?}", value).map_err(VmError::panic)?;
-    }
-
-    stack.push(Value::Unit);
-    Ok(())
-}
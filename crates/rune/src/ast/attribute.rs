@@ -35,6 +35,16 @@ pub struct Attribute {
 /// ```
 impl Parse for Attribute {
     fn parse(p: &mut Parser<'_>) -> Result<Self, ParseError> {
+        let doc_comments = p.peeker().set_doc_comments(true);
+        let result = Self::parse_visible(p);
+        p.peeker().set_doc_comments(doc_comments);
+        result
+    }
+}
+
+impl Attribute {
+    /// Parse an attribute while doc comments are visible.
+    fn parse_visible(p: &mut Parser<'_>) -> Result<Self, ParseError> {
         let hash = p.parse()?;
         let style = p.parse()?;
         let open = p.parse()?;
@@ -73,15 +83,32 @@ impl Parse for Attribute {
             close,
         })
     }
+
+    /// Test if the attribute is a doc comment like `/// ...` or `//! ...`,
+    /// which the lexer turns into a `#[doc = "..."]` attribute.
+    pub fn is_doc_comment(&self) -> bool {
+        matches!(
+            &self.path.first,
+            ast::PathSegment::Ident(ast::Ident {
+                source: ast::StringSource::BuiltIn(ast::BuiltIn::Doc),
+                ..
+            })
+        )
+    }
 }
 
 impl Peek for Attribute {
     fn peek(p: &mut Peeker<'_>) -> bool {
-        match (p.nth(0), p.nth(1)) {
+        let doc_comments = p.set_doc_comments(true);
+
+        let result = match (p.nth(0), p.nth(1)) {
             (K![#], K![!]) => true,
             (K![#], K!['[']) => true,
             _ => false,
-        }
+        };
+
+        p.set_doc_comments(doc_comments);
+        result
     }
 }
 
@@ -136,10 +163,15 @@ pub struct OuterAttribute;
 
 impl Peek for OuterAttribute {
     fn peek(p: &mut Peeker<'_>) -> bool {
-        match (p.nth(0), p.nth(1)) {
+        let doc_comments = p.set_doc_comments(true);
+
+        let result = match (p.nth(0), p.nth(1)) {
             (K![#], K![!]) => true,
             _ => false,
-        }
+        };
+
+        p.set_doc_comments(doc_comments);
+        result
     }
 }
 
@@ -114,6 +114,9 @@ impl Parse for File {
             path = p.parse()?;
         }
 
+        // NB: doc comments at the end of the file have nothing to document.
+        item_attributes.retain(|a| !a.is_doc_comment());
+
         // meta without items. maybe use different error kind?
         if let Some(span) = item_attributes.option_span() {
             return Err(ParseError::unsupported(span, "attributes"));
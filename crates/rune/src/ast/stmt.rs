@@ -85,6 +85,10 @@ impl Parse for Stmt {
             Self::Expr(expr, p.parse()?)
         };
 
+        // NB: doc comments on expressions are ignored, like they are on local
+        // declarations.
+        attributes.retain(|a| !a.is_doc_comment());
+
         if let Some(span) = attributes.option_span() {
             return Err(ParseError::unsupported(span, "attributes"));
         }
@@ -135,6 +139,7 @@ impl Parse for ItemOrExpr {
         }
 
         let expr = ast::Expr::parse_with_meta(p, &mut attributes, path, ast::expr::Callable(true))?;
+        attributes.retain(|a| !a.is_doc_comment());
 
         if let Some(span) = attributes.option_span() {
             return Err(ParseError::unsupported(span, "attributes"));
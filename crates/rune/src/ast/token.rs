@@ -320,6 +320,8 @@ pub enum BuiltIn {
     BuiltIn,
     /// `literal`.
     Literal,
+    /// `doc`.
+    Doc,
 }
 
 impl BuiltIn {
@@ -330,6 +332,7 @@ impl BuiltIn {
             Self::Format => "formatspec",
             Self::BuiltIn => "builtin",
             Self::Literal => "literal",
+            Self::Doc => "doc",
         }
     }
 }
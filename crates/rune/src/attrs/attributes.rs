@@ -88,6 +88,48 @@ impl Attributes {
         }
     }
 
+    /// Parse all attributes with the given type.
+    ///
+    /// Returns the parsed elements and the spans they were parsed from, in the
+    /// order they were declared.
+    pub(crate) fn try_parse_collect<T>(&mut self) -> Result<Vec<(Span, T)>, ParseError>
+    where
+        T: Attribute + Parse,
+    {
+        let mut matched = Vec::new();
+
+        for index in self.unused.iter().copied() {
+            let a = match self.attributes.get(index) {
+                Some(a) => a,
+                None => continue,
+            };
+
+            let ident = match a.path.try_as_ident() {
+                Some(ident) => ident,
+                None => continue,
+            };
+
+            let ident = ident.resolve(&self.storage, &self.source)?;
+
+            if ident != T::PATH {
+                continue;
+            }
+
+            let mut parser = Parser::from_token_stream(&a.input);
+            matched.push((index, a.span(), parser.parse::<T>()?));
+            parser.eof()?;
+        }
+
+        let mut out = Vec::with_capacity(matched.len());
+
+        for (index, span, matched) in matched {
+            self.unused.remove(&index);
+            out.push((span, matched));
+        }
+
+        Ok(out)
+    }
+
     /// Get the span of the first remaining attribute.
     pub(crate) fn remaining(&self) -> Option<Span> {
         for i in self.unused.iter().copied() {
@@ -98,4 +140,15 @@ impl Attributes {
 
         None
     }
+
+    /// Get the span covering all remaining attributes.
+    pub(crate) fn remaining_span(&self) -> Option<Span> {
+        let mut it = self
+            .unused
+            .iter()
+            .filter_map(|index| self.attributes.get(*index));
+
+        let first = it.next()?.span();
+        Some(it.fold(first, |span, a| span.join(a.span())))
+    }
 }
@@ -59,3 +59,19 @@ impl Attribute for Test {
     /// Must match the specified name.
     const PATH: &'static str = "test";
 }
+
+/// A doc attribute, like `#[doc = "..."]`, which is also what doc comments
+/// like `/// ...` are turned into.
+#[derive(Parse)]
+pub(crate) struct Doc {
+    /// The `=` token.
+    #[allow(dead_code)]
+    pub eq: T![=],
+    /// The doc string.
+    pub doc_string: ast::LitStr,
+}
+
+impl Attribute for Doc {
+    /// Must match the specified name.
+    const PATH: &'static str = "doc";
+}
@@ -1,4 +1,4 @@
-use runestick::{CompileMeta, SourceId, Span};
+use runestick::{CompileMeta, Item, SourceId, Span};
 
 /// A visitor that will be called for every language item compiled.
 pub trait CompileVisitor {
@@ -13,6 +13,21 @@ pub trait CompileVisitor {
 
     /// Visit something that is a module.
     fn visit_mod(&self, _source_id: SourceId, _span: Span) {}
+
+    /// Visit the doc comments of an item, like a function, type or module.
+    ///
+    /// Each line of a doc comment is a separate entry in `docs`.
+    fn visit_doc_comment(&self, _source_id: SourceId, _item: &Item, _docs: &[String]) {}
+
+    /// Visit the doc comments of a field in a struct or a variant.
+    fn visit_field_doc_comment(
+        &self,
+        _source_id: SourceId,
+        _item: &Item,
+        _field: &str,
+        _docs: &[String],
+    ) {
+    }
 }
 
 /// A compile visitor that does nothing.
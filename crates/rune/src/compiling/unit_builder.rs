@@ -46,6 +46,8 @@ impl UnitBuilder {
         this.prelude("char", &["char"]);
         this.prelude("dbg", &["io", "dbg"]);
         this.prelude("drop", &["mem", "drop"]);
+        this.prelude("eprint", &["io", "eprint"]);
+        this.prelude("eprintln", &["io", "eprintln"]);
         this.prelude("Err", &["result", "Result", "Err"]);
         this.prelude("file", &["macros", "builtin", "file"]);
         this.prelude("float", &["float"]);
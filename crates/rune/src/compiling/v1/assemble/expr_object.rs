@@ -67,7 +67,16 @@ impl Assemble for ast::ExprObject {
                         check_object_fields(&object.fields, check_keys, span, &meta.item.item)?;
 
                         let hash = Hash::type_hash(&meta.item.item);
-                        c.asm.push(Inst::StructVariant { hash, slot }, span);
+
+                        // Variants of native enums don't have runtime type
+                        // information in the unit, so they're constructed by
+                        // passing an object to their native constructor.
+                        if meta.source.is_none() {
+                            c.asm.push(Inst::Object { slot }, span);
+                            c.asm.push(Inst::Call { hash, args: 1 }, span);
+                        } else {
+                            c.asm.push(Inst::StructVariant { hash, slot }, span);
+                        }
                     }
                     _ => {
                         return Err(CompileError::new(
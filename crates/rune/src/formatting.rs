@@ -0,0 +1,861 @@
+//! Formatting of Rune source code.
+//!
+//! The formatter parses the source into an [ast::File] and prints the tokens
+//! produced by its [ToTokens] implementation with normalized whitespace.
+//! Comments are retained as trivia by the lexer and interleaved with the
+//! tokens by position. Template literals are copied verbatim.
+//!
+//! Whether a delimited group is broken up over multiple lines follows the
+//! original source: a group is broken if the original has a newline right after
+//! its opening delimiter, or if it contains any comments. Braces which contain
+//! multiple statements or items are always broken.
+
+use crate::ast;
+use crate::{Lexer, MacroContext, ParseError, Parser, ToTokens, TokenStream};
+use runestick::Span;
+use thiserror::Error;
+
+/// The indentation used for each level of nesting.
+const INDENT: &str = "    ";
+
+/// Error raised when formatting source code.
+#[derive(Debug, Error)]
+pub enum FormatError {
+    /// The source failed to parse.
+    #[error("{error}")]
+    ParseError {
+        /// The source error.
+        #[from]
+        error: ParseError,
+    },
+    /// The formatted output doesn't consist of the same tokens as the input.
+    /// This indicates a bug in the formatter, and the output is discarded.
+    #[error("formatting would change the meaning of the source")]
+    Unstable,
+}
+
+/// Format the given Rune source code.
+///
+/// Comments are preserved, and the output is verified to consist of exactly
+/// the same tokens as the input.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> Result<(), rune::FormatError> {
+/// let source = "fn main(){let a=1; // one\na+2}";
+///
+/// assert_eq!(
+///     rune::format_source(source)?,
+///     "fn main() {\n    let a = 1; // one\n    a + 2\n}\n"
+/// );
+/// # Ok(()) }
+/// ```
+pub fn format_source(source: &str) -> Result<String, FormatError> {
+    let mut parser = Parser::new(source);
+    let file = parser.parse::<ast::File>()?;
+    parser.eof()?;
+
+    let mut stream = TokenStream::new();
+    file.to_tokens(&MacroContext::empty(), &mut stream);
+    let tokens = stream.into_iter().collect::<Vec<_>>();
+
+    let mut lexer = Lexer::with_comments(source);
+    while lexer.next()?.is_some() {}
+
+    let atoms = atoms(source, &tokens, lexer.comments());
+    let broken = broken(&atoms);
+
+    let mut printer = Printer {
+        source,
+        out: String::with_capacity(source.len()),
+        frames: vec![Frame::root()],
+        last: None,
+        force_newline: false,
+    };
+
+    for (index, atom) in atoms.iter().enumerate() {
+        printer.atom(atom, broken[index]);
+    }
+
+    let mut out = printer.out;
+    out.truncate(out.trim_end().len());
+
+    if !out.is_empty() {
+        out.push('\n');
+    }
+
+    verify(source, &out)?;
+    Ok(out)
+}
+
+/// A single printable element of the source.
+#[derive(Debug, Clone, Copy)]
+struct Atom {
+    kind: AtomKind,
+    span: Span,
+    /// The number of newlines separating this atom from the one preceding it
+    /// in the original source.
+    newlines: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AtomKind {
+    /// A regular token.
+    Token(ast::Kind),
+    /// A template literal, which is printed as-is.
+    Template,
+    /// A line comment.
+    Comment,
+}
+
+/// How a token relates to its neighbours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Normal,
+    /// A prefix operator, like `-` in `-1`.
+    Unary,
+    /// The `!` of a macro call.
+    Macro,
+    /// The opening `|` of closure arguments.
+    ClosureOpen,
+    /// The closing `|` of closure arguments.
+    ClosureClose,
+}
+
+/// A delimited group which is being printed.
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    delimiter: Option<ast::Delimiter>,
+    /// If the group is broken up over multiple lines.
+    broken: bool,
+    /// If an inline group is padded with spaces, like `Foo { a: 1 }`.
+    padded: bool,
+    /// If the group is the body of an attribute, like `#[test]`.
+    attribute: bool,
+    /// If we are inside the arguments of a closure.
+    closure: bool,
+    /// If a `match` has been seen, whose body is the next brace.
+    pending_match: bool,
+    /// If the group is the body of a match.
+    is_match: bool,
+    /// If we are inside the pattern of a match arm.
+    in_pattern: bool,
+}
+
+impl Frame {
+    /// The frame corresponding to the whole file.
+    fn root() -> Self {
+        Self {
+            delimiter: None,
+            broken: true,
+            padded: false,
+            attribute: false,
+            closure: false,
+            pending_match: false,
+            is_match: false,
+            in_pattern: false,
+        }
+    }
+
+    /// If the frame contains statements or items.
+    fn is_block(&self) -> bool {
+        matches!(self.delimiter, None | Some(ast::Delimiter::Brace))
+    }
+}
+
+/// The last token which was printed.
+#[derive(Debug, Clone, Copy)]
+struct Last {
+    kind: AtomKind,
+    role: Role,
+    /// The frame closed by the token, if it was a closing delimiter.
+    closed: Option<Frame>,
+    /// If the token was preceded by `#`.
+    after_pound: bool,
+    /// If the token is part of the pattern of a match arm.
+    pattern: bool,
+}
+
+struct Printer<'a> {
+    source: &'a str,
+    out: String,
+    frames: Vec<Frame>,
+    last: Option<Last>,
+    /// Set if a newline must be emitted before the next atom, like after a
+    /// comment.
+    force_newline: bool,
+}
+
+impl Printer<'_> {
+    /// The innermost frame.
+    fn frame(&self) -> &Frame {
+        self.frames.last().expect("root frame is never popped")
+    }
+
+    fn frame_mut(&mut self) -> &mut Frame {
+        self.frames.last_mut().expect("root frame is never popped")
+    }
+
+    /// Print a single atom. `broken` indicates if the atom is an opening
+    /// delimiter whose group should be broken up over multiple lines.
+    fn atom(&mut self, atom: &Atom, broken: bool) {
+        let kind = match atom.kind {
+            AtomKind::Comment => {
+                self.comment(atom);
+                return;
+            }
+            AtomKind::Template => {
+                self.separate(atom, AtomKind::Template, Role::Normal);
+                self.out.push_str(&self.source[atom.span.range()]);
+
+                self.last = Some(Last {
+                    kind: AtomKind::Template,
+                    role: Role::Normal,
+                    closed: None,
+                    after_pound: false,
+                    pattern: false,
+                });
+
+                return;
+            }
+            AtomKind::Token(kind) => kind,
+        };
+
+        let role = self.role(kind);
+
+        let closed = if let ast::Kind::Close(..) = kind {
+            let frame = if self.frames.len() > 1 {
+                self.frames.pop()
+            } else {
+                None
+            };
+
+            let is_empty = matches!(
+                self.last,
+                Some(Last {
+                    kind: AtomKind::Token(ast::Kind::Open(..)),
+                    ..
+                })
+            );
+
+            if self.force_newline || frame.map(|f| f.broken).unwrap_or_default() {
+                self.newline(false);
+            } else if !is_empty && frame.map(|f| f.padded).unwrap_or_default() {
+                self.space();
+            }
+
+            self.force_newline = false;
+            frame
+        } else {
+            self.separate(atom, AtomKind::Token(kind), role);
+            None
+        };
+
+        self.out.push_str(&self.source[atom.span.range()]);
+
+        let after_pound = matches!(
+            self.last,
+            Some(Last {
+                kind: AtomKind::Token(K![#]),
+                ..
+            })
+        );
+
+        if let ast::Kind::Open(delimiter) = kind {
+            let attribute = delimiter == ast::Delimiter::Bracket
+                && match self.last {
+                    Some(last) => {
+                        last.kind == AtomKind::Token(K![#])
+                            || last.kind == AtomKind::Token(K![!]) && last.after_pound
+                    }
+                    None => false,
+                };
+
+            let padded = delimiter == ast::Delimiter::Brace
+                && !matches!(
+                    self.last.map(|l| l.kind),
+                    Some(AtomKind::Token(K![#])) | Some(AtomKind::Token(K![::]))
+                );
+
+            let is_match = padded && std::mem::take(&mut self.frame_mut().pending_match);
+
+            self.frames.push(Frame {
+                delimiter: Some(delimiter),
+                broken,
+                padded,
+                attribute,
+                closure: false,
+                pending_match: false,
+                is_match,
+                in_pattern: is_match,
+            });
+        }
+
+        let frame = self.frame_mut();
+        let pattern = frame.is_match && frame.in_pattern;
+
+        match kind {
+            K![match] => frame.pending_match = true,
+            K![=>] => frame.in_pattern = false,
+            K![,] | ast::Kind::Close(ast::Delimiter::Brace) => frame.in_pattern = frame.is_match,
+            _ => (),
+        }
+
+        self.last = Some(Last {
+            kind: AtomKind::Token(kind),
+            role,
+            closed,
+            after_pound,
+            pattern,
+        });
+    }
+
+    /// Determine the role of the given token, updating closure state.
+    fn role(&mut self, kind: ast::Kind) -> Role {
+        let operand = self.last.map(|l| is_operand(l.kind)).unwrap_or_default();
+
+        match kind {
+            K![!] => match self.last.map(|l| l.kind) {
+                Some(AtomKind::Token(ast::Kind::Ident(..))) => Role::Macro,
+                _ => Role::Unary,
+            },
+            K![-] | K![*] | K![&] | K![+] if !operand => Role::Unary,
+            K![|] => {
+                if self.frame().closure {
+                    self.frame_mut().closure = false;
+                    Role::ClosureClose
+                } else if operand {
+                    Role::Normal
+                } else {
+                    self.frame_mut().closure = true;
+                    Role::ClosureOpen
+                }
+            }
+            _ => Role::Normal,
+        }
+    }
+
+    /// Print a line comment.
+    fn comment(&mut self, atom: &Atom) {
+        let text = self.source[atom.span.range()].trim_end();
+
+        if self.last.is_some() || !self.out.is_empty() {
+            if atom.newlines == 0 {
+                self.space();
+            } else {
+                let blank = atom.newlines > 1 && !self.after_open();
+                self.newline(blank);
+            }
+        }
+
+        self.out.push_str(text);
+        self.force_newline = true;
+    }
+
+    /// Emit whatever should separate the previous atom from the given one.
+    fn separate(&mut self, atom: &Atom, kind: AtomKind, role: Role) {
+        let last = match self.last {
+            Some(last) => last,
+            None => {
+                if self.force_newline {
+                    self.force_newline = false;
+                    self.newline(atom.newlines > 1);
+                }
+
+                return;
+            }
+        };
+
+        if std::mem::take(&mut self.force_newline) || self.breaks_after(&last, kind) {
+            let blank = atom.newlines > 1 && !self.after_open();
+            self.newline(blank);
+            return;
+        }
+
+        // NB: method chains which are broken up in the original source are
+        // kept broken up.
+        if kind == AtomKind::Token(K![.]) && atom.newlines > 0 {
+            self.newline(false);
+            self.out.push_str(INDENT);
+            return;
+        }
+
+        let space = match (last.kind, kind) {
+            (AtomKind::Token(ast::Kind::Open(..)), _) => self.frame().padded,
+            (AtomKind::Token(last_kind), AtomKind::Token(kind)) => {
+                space_between(last_kind, last.role, kind, role)
+            }
+            (AtomKind::Template, AtomKind::Token(kind)) => {
+                space_between(ast::Kind::Eof, Role::Normal, kind, role) && !is_tight(kind)
+            }
+            (AtomKind::Token(last_kind), AtomKind::Template) => {
+                !matches!(last.role, Role::Unary | Role::ClosureOpen | Role::Macro)
+                    && !is_tight_after(last_kind)
+            }
+            _ => true,
+        };
+
+        if space {
+            self.space();
+        }
+    }
+
+    /// Test if a newline should be inserted after the last token, before the
+    /// atom of the given kind.
+    fn breaks_after(&self, last: &Last, kind: AtomKind) -> bool {
+        let frame = self.frame();
+
+        if !frame.broken {
+            return false;
+        }
+
+        let last_kind = match last.kind {
+            AtomKind::Token(kind) => kind,
+            _ => return false,
+        };
+
+        match last_kind {
+            // NB: closure arguments are never broken up.
+            K![,] => !frame.closure,
+            K![;] | ast::Kind::Open(..) => true,
+            ast::Kind::Close(ast::Delimiter::Bracket) => {
+                last.closed.map(|f| f.attribute).unwrap_or_default()
+            }
+            // NB: braces in the pattern of a match arm might be followed by
+            // a condition, like `Foo { a } if a > 0 => ..`.
+            ast::Kind::Close(ast::Delimiter::Brace) if frame.is_block() && !last.pattern => {
+                match kind {
+                    AtomKind::Token(kind) => !continues_expr(kind),
+                    _ => true,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Test if the last printed token was an opening delimiter.
+    fn after_open(&self) -> bool {
+        matches!(
+            self.last,
+            Some(Last {
+                kind: AtomKind::Token(ast::Kind::Open(..)),
+                ..
+            })
+        )
+    }
+
+    fn space(&mut self) {
+        self.out.push(' ');
+    }
+
+    fn newline(&mut self, blank: bool) {
+        self.out.truncate(self.out.trim_end_matches(' ').len());
+        self.out.push('\n');
+
+        if blank {
+            self.out.push('\n');
+        }
+
+        let indent = self.frames.iter().skip(1).filter(|f| f.broken).count();
+
+        for _ in 0..indent {
+            self.out.push_str(INDENT);
+        }
+    }
+}
+
+/// Build the atoms to print from the given tokens and comments.
+fn atoms(source: &str, tokens: &[ast::Token], comments: &[Span]) -> Vec<Atom> {
+    let mut atoms = Vec::with_capacity(tokens.len() + comments.len());
+    let mut it = tokens.iter();
+
+    while let Some(token) = it.next() {
+        // NB: the lexer desugars template literals into a macro call, where
+        // every synthetic token points to a backtick. The last token is the
+        // `)` which points to the closing backtick.
+        if token.kind == K![#] && &source[token.span.range()] == "`" {
+            let mut open = vec![token.span];
+            let mut end = token.span.end;
+
+            for token in it.by_ref() {
+                if &source[token.span.range()] == "`" {
+                    match token.kind {
+                        K![#] => open.push(token.span),
+                        K![')'] if open.last() != Some(&token.span) => {
+                            open.pop();
+                        }
+                        _ => (),
+                    }
+                }
+
+                if open.is_empty() {
+                    end = token.span.end;
+                    break;
+                }
+            }
+
+            atoms.push(Atom {
+                kind: AtomKind::Template,
+                span: Span::new(token.span.start, end),
+                newlines: 0,
+            });
+
+            continue;
+        }
+
+        atoms.push(Atom {
+            kind: AtomKind::Token(token.kind),
+            span: token.span,
+            newlines: 0,
+        });
+    }
+
+    // NB: comments inside of templates are part of the template.
+    let templates = atoms
+        .iter()
+        .filter(|atom| atom.kind == AtomKind::Template)
+        .map(|atom| atom.span)
+        .collect::<Vec<_>>();
+
+    let comments = comments
+        .iter()
+        .filter(|comment| {
+            !templates
+                .iter()
+                .any(|span| span.start <= comment.start && comment.end <= span.end)
+        })
+        .collect::<Vec<_>>();
+
+    // NB: the parser desugars doc comments into `#[doc = "..."]` attributes,
+    // where every synthetic token points into the comment. The comment is
+    // retained as is, so it's printed verbatim instead of its tokens.
+    atoms.retain(|atom| {
+        !comments
+            .iter()
+            .any(|span| span.start <= atom.span.start && atom.span.end <= span.end)
+    });
+
+    for comment in comments {
+        let index = atoms.partition_point(|atom| atom.span.start < comment.start);
+
+        atoms.insert(
+            index,
+            Atom {
+                kind: AtomKind::Comment,
+                span: *comment,
+                newlines: 0,
+            },
+        );
+    }
+
+    let mut end = 0;
+
+    for atom in &mut atoms {
+        let range = atom.span.range();
+        let start = usize::max(range.start, end);
+        atom.newlines = source[end..start].matches('\n').count();
+        end = usize::max(end, range.end);
+    }
+
+    atoms
+}
+
+/// Determine which atoms open a group that should be broken up over multiple
+/// lines.
+fn broken(atoms: &[Atom]) -> Vec<bool> {
+    struct Open {
+        index: usize,
+        delimiter: ast::Delimiter,
+        broken: bool,
+    }
+
+    let mut broken = vec![false; atoms.len()];
+    let mut stack = Vec::<Open>::new();
+    let mut after_brace = false;
+
+    for (index, atom) in atoms.iter().enumerate() {
+        let kind = match atom.kind {
+            AtomKind::Token(kind) => Some(kind),
+            _ => None,
+        };
+
+        // NB: a brace which contains multiple statements or items like
+        // `{ fn a() {} fn b() {} }` is also broken.
+        if after_brace
+            && atom.kind != AtomKind::Comment
+            && !kind.map(continues_expr).unwrap_or_default()
+        {
+            if let Some(open) = stack.last_mut() {
+                if open.delimiter == ast::Delimiter::Brace {
+                    open.broken = true;
+                }
+            }
+        }
+
+        if atom.kind != AtomKind::Comment {
+            after_brace = kind == Some(ast::Kind::Close(ast::Delimiter::Brace));
+        }
+
+        match atom.kind {
+            AtomKind::Comment => {
+                for open in &mut stack {
+                    open.broken = true;
+                }
+            }
+            AtomKind::Token(ast::Kind::Open(delimiter)) => {
+                let broken = match atoms.get(index + 1) {
+                    Some(next) => {
+                        !matches!(next.kind, AtomKind::Token(ast::Kind::Close(..)))
+                            && next.newlines > 0
+                    }
+                    None => false,
+                };
+
+                stack.push(Open {
+                    index,
+                    delimiter,
+                    broken,
+                });
+            }
+            AtomKind::Token(K![;]) => {
+                if let Some(open) = stack.last_mut() {
+                    if open.delimiter == ast::Delimiter::Brace {
+                        open.broken = true;
+                    }
+                }
+            }
+            AtomKind::Token(ast::Kind::Close(..)) => {
+                if let Some(open) = stack.pop() {
+                    broken[open.index] = open.broken;
+                }
+            }
+            _ => (),
+        }
+    }
+
+    broken
+}
+
+/// Test if the given kind of atom ends an operand, which determines if the
+/// following token is a binary or unary operator.
+fn is_operand(kind: AtomKind) -> bool {
+    let kind = match kind {
+        AtomKind::Token(kind) => kind,
+        AtomKind::Template => return true,
+        AtomKind::Comment => return false,
+    };
+
+    matches!(
+        kind,
+        ast::Kind::Ident(..)
+            | ast::Kind::Label(..)
+            | ast::Kind::Byte(..)
+            | ast::Kind::ByteStr(..)
+            | ast::Kind::Char(..)
+            | ast::Kind::Number(..)
+            | ast::Kind::Str(..)
+            | ast::Kind::Close(..)
+            | K![true]
+            | K![false]
+            | K![self]
+            | K![Self]
+            | K![super]
+            | K![crate]
+            | K![_]
+            | K![?]
+            | K![await]
+    )
+}
+
+/// Test if the given token continues an expression when it follows a closing
+/// brace, as opposed to starting a new statement.
+fn continues_expr(kind: ast::Kind) -> bool {
+    matches!(
+        kind,
+        ast::Kind::Close(..)
+            | K![,]
+            | K![;]
+            | K![.]
+            | K![?]
+            | K![..]
+            | K![..=]
+            | K![else]
+            | K![as]
+            | K![is]
+            | K![=>]
+            | K![=]
+            | K![==]
+            | K![!=]
+            | K![<]
+            | K![<=]
+            | K![>]
+            | K![>=]
+            | K![&&]
+            | K![||]
+            | K![+]
+            | K![+=]
+            | K![-]
+            | K![-=]
+            | K![*]
+            | K![*=]
+            | K![/]
+            | K![/=]
+            | K![%]
+            | K![%=]
+            | K![&]
+            | K![&=]
+            | K![|]
+            | K![|=]
+            | K![^]
+            | K![^=]
+            | K![<<]
+            | K![<<=]
+            | K![>>]
+            | K![>>=]
+    )
+}
+
+/// Tokens which are never preceded by a space.
+fn is_tight(kind: ast::Kind) -> bool {
+    matches!(
+        kind,
+        K![,]
+            | K![;]
+            | K![.]
+            | K![?]
+            | K![:]
+            | ast::Kind::Close(ast::Delimiter::Parenthesis)
+            | ast::Kind::Close(ast::Delimiter::Bracket)
+    )
+}
+
+/// Tokens which are never followed by a space.
+fn is_tight_after(kind: ast::Kind) -> bool {
+    matches!(
+        kind,
+        ast::Kind::Open(ast::Delimiter::Parenthesis)
+            | ast::Kind::Open(ast::Delimiter::Bracket)
+            | K![.]
+            | K![::]
+            | K![#]
+            | K![..]
+            | K![..=]
+    )
+}
+
+/// Test if a space should separate two tokens on the same line.
+fn space_between(last: ast::Kind, last_role: Role, kind: ast::Kind, role: Role) -> bool {
+    if matches!(last_role, Role::Unary | Role::Macro | Role::ClosureOpen) {
+        return false;
+    }
+
+    if matches!(role, Role::Macro | Role::ClosureClose) || is_tight(kind) {
+        return false;
+    }
+
+    if matches!(last, K![,] | K![;]) {
+        return true;
+    }
+
+    if is_tight_after(last) {
+        return false;
+    }
+
+    let operand = is_operand(AtomKind::Token(last));
+
+    match kind {
+        K![..] | K![..=] | K![::] => !operand,
+        ast::Kind::Open(ast::Delimiter::Parenthesis) => !operand && last != K![pub],
+        ast::Kind::Open(ast::Delimiter::Bracket) => !operand,
+        _ => true,
+    }
+}
+
+/// Verify that the formatted output consists of the same tokens and comments
+/// as the input.
+fn verify(input: &str, output: &str) -> Result<(), FormatError> {
+    let mut a = Lexer::with_comments(input);
+    let mut b = Lexer::with_comments(output);
+
+    loop {
+        let (a, b) = match (a.next()?, b.next()) {
+            (None, Ok(None)) => break,
+            (Some(a), Ok(Some(b))) => (a, b),
+            _ => return Err(FormatError::Unstable),
+        };
+
+        if a.kind != b.kind || input[a.span.range()] != output[b.span.range()] {
+            return Err(FormatError::Unstable);
+        }
+    }
+
+    let a = a
+        .comments()
+        .iter()
+        .map(|span| input[span.range()].trim_end());
+    let b = b
+        .comments()
+        .iter()
+        .map(|span| output[span.range()].trim_end());
+
+    if !a.eq(b) {
+        return Err(FormatError::Unstable);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_source;
+
+    macro_rules! assert_format {
+        ($source:expr, $expected:expr) => {{
+            let output = format_source($source).expect("source should format");
+            assert_eq!(output, $expected);
+            let again = format_source(&output).expect("output should format");
+            assert_eq!(again, output, "formatting should be idempotent");
+        }};
+    }
+
+    #[test]
+    fn test_format_items() {
+        assert_format!(
+            "use std::{a,b};\n\n\n#[test]fn foo(a,b){a+b}\nstruct Foo{a,b}\nenum Bar{A(a),B}\n",
+            "use std::{a, b};\n\n#[test]\nfn foo(a, b) { a + b }\nstruct Foo { a, b }\nenum Bar { A(a), B }\n"
+        );
+    }
+
+    #[test]
+    fn test_format_blocks() {
+        assert_format!(
+            "fn main() {\nlet a = -1;\nif a<0 {\nfoo!(a);\nbar(a)?;\n}   else { bar() }\n\n\nlet f = |a, b| a|b;\nlet o = #{\"a\": [1, 2][0], \"b\": 0..10};\nmatch a {\n1 => {\nreturn;\n}\n_ => (),\n}\n}",
+            "fn main() {\n    let a = -1;\n    if a < 0 {\n        foo!(a);\n        bar(a)?;\n    } else { bar() }\n\n    let f = |a, b| a | b;\n    let o = #{\"a\": [1, 2][0], \"b\": 0..10};\n    match a {\n        1 => {\n            return;\n        }\n        _ => (),\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_format_comments() {
+        assert_format!(
+            "// header\n\nfn main() { // trailing\n    // own line\n    foo(1, // one\n    2)\n}\n",
+            "// header\n\nfn main() { // trailing\n    // own line\n    foo(\n        1, // one\n        2\n    )\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_format_doc_comments() {
+        assert_format!(
+            "//! Module docs.\n\n/// Adds.\n///   Indented.\nfn add(a,b){\n/// Inner.\nlet c=a+b; c}\n",
+            "//! Module docs.\n\n/// Adds.\n///   Indented.\nfn add(a, b) {\n    /// Inner.\n    let c = a + b;\n    c\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_format_templates() {
+        assert_format!(
+            "fn main(){let a=`hello ${  `nested ${1+2}`  }   // world`;a}",
+            "fn main() {\n    let a = `hello ${  `nested ${1+2}`  }   // world`;\n    a\n}\n"
+        );
+    }
+}
@@ -421,6 +421,9 @@ impl<'a> Indexer<'a> {
                         }
                     }
 
+                    // NB: doc comments on macro calls are ignored.
+                    attributes.try_parse_collect::<attrs::Doc>()?;
+
                     if let Some(span) = attributes.remaining() {
                         return Err(CompileError::msg(span, "unsupported item attribute"));
                     }
@@ -484,6 +487,9 @@ impl<'a> Indexer<'a> {
                         queue.push_front(stmt);
                     }
 
+                    // NB: doc comments on macro calls are ignored.
+                    attributes.try_parse_collect::<attrs::Doc>()?;
+
                     if let Some(span) = attributes.remaining() {
                         return Err(CompileError::msg(span, "unsupported statement attribute"));
                     }
@@ -515,8 +521,64 @@ impl<'a> Indexer<'a> {
         }
     }
 
+    /// Take the doc comments out of the given attributes, and resolve them
+    /// into one string for each line.
+    fn resolve_docs(&self, attributes: &mut attrs::Attributes) -> CompileResult<Vec<String>> {
+        let mut docs = Vec::new();
+
+        for (_, doc) in attributes.try_parse_collect::<attrs::Doc>()? {
+            let doc = doc.doc_string.resolve(&self.storage, &self.source)?;
+            docs.push(doc.into_owned());
+        }
+
+        Ok(docs)
+    }
+
+    /// Resolve the doc comments in the given attributes, erroring with the
+    /// given message if there are any other attributes.
+    fn docs_only(
+        &self,
+        attributes: &[ast::Attribute],
+        message: &'static str,
+    ) -> CompileResult<Vec<String>> {
+        let mut attributes = attrs::Attributes::new(
+            attributes.to_vec(),
+            self.storage.clone(),
+            self.source.clone(),
+        );
+
+        let docs = self.resolve_docs(&mut attributes)?;
+
+        if let Some(span) = attributes.remaining_span() {
+            return Err(CompileError::msg(span, message));
+        }
+
+        Ok(docs)
+    }
+
+    /// Report the doc comments of the given item to the visitor.
+    fn visit_docs(&self, item: &Item, docs: &[String]) {
+        if !docs.is_empty() {
+            self.visitor.visit_doc_comment(self.source_id, item, docs);
+        }
+    }
+
+    /// Report the doc comments of the fields of the given item to the
+    /// visitor.
+    fn visit_field_docs(&self, item: &Item, fields: &[(String, Vec<String>)]) {
+        for (field, docs) in fields {
+            if !docs.is_empty() {
+                self.visitor
+                    .visit_field_doc_comment(self.source_id, item, field, docs);
+            }
+        }
+    }
+
     /// Handle a filesystem module.
-    pub(crate) fn handle_file_mod(&mut self, item_mod: &mut ast::ItemMod) -> CompileResult<()> {
+    pub(crate) fn handle_file_mod(
+        &mut self,
+        item_mod: &mut ast::ItemMod,
+    ) -> CompileResult<Arc<CompileMod>> {
         let span = item_mod.span();
         let name = item_mod.name.resolve(&self.storage, &*self.source)?;
         let _guard = self.items.push_name(name.as_ref());
@@ -566,10 +628,10 @@ impl<'a> Indexer<'a> {
                 root: self.root.clone(),
             },
             source_id,
-            mod_item,
+            mod_item: mod_item.clone(),
         });
 
-        Ok(())
+        Ok(mod_item)
     }
 }
 
@@ -580,12 +642,8 @@ pub(crate) trait Index {
 
 impl Index for ast::File {
     fn index(&mut self, idx: &mut Indexer<'_>) -> CompileResult<()> {
-        if let Some(first) = self.attributes.first() {
-            return Err(CompileError::msg(
-                first,
-                "file attributes are not supported",
-            ));
-        }
+        let docs = idx.docs_only(&self.attributes, "file attributes are not supported")?;
+        idx.visit_docs(&idx.mod_item.item, &docs);
 
         idx.preprocess_items(&mut self.items)?;
 
@@ -695,6 +753,8 @@ impl Index for ast::ItemFn {
             idx.source.clone(),
         );
 
+        let docs = idx.resolve_docs(&mut attributes)?;
+
         let is_test = match attributes.try_parse::<attrs::Test>()? {
             Some((span, _)) => {
                 if let Some(nested_span) = idx.nested_item {
@@ -715,6 +775,8 @@ impl Index for ast::ItemFn {
             return Err(CompileError::msg(attrs, "unrecognized function attribute"));
         }
 
+        idx.visit_docs(&item.item, &docs);
+
         if self.is_instance() {
             if is_test {
                 return Err(CompileError::msg(
@@ -804,12 +866,8 @@ impl Index for ast::ExprBlock {
         let span = self.span();
         log::trace!("ExprBlock => {:?}", idx.source.source(span));
 
-        if let Some(span) = self.attributes.option_span() {
-            return Err(CompileError::msg(
-                span,
-                "block attributes are not supported yet",
-            ));
-        }
+        // NB: doc comments on blocks are ignored.
+        idx.docs_only(&self.attributes, "block attributes are not supported yet")?;
 
         if self.async_token.is_none() && self.const_token.is_none() {
             if let Some(span) = self.move_token.option_span() {
@@ -945,9 +1003,8 @@ impl Index for ast::Local {
         let span = self.span();
         log::trace!("Local => {:?}", idx.source.source(span));
 
-        if let Some(span) = self.attributes.option_span() {
-            return Err(CompileError::msg(span, "attributes are not supported"));
-        }
+        // NB: doc comments on local declarations are ignored.
+        idx.docs_only(&self.attributes, "attributes are not supported")?;
 
         self.pat.index(idx)?;
         self.expr.index(idx)?;
@@ -1081,6 +1138,9 @@ impl Index for ast::Expr {
             idx.source.clone(),
         );
 
+        // NB: doc comments on expressions are ignored.
+        attributes.try_parse_collect::<attrs::Doc>()?;
+
         match self {
             ast::Expr::Path(path) => {
                 path.index(idx)?;
@@ -1290,12 +1350,7 @@ impl Index for ast::ItemEnum {
     fn index(&mut self, idx: &mut Indexer<'_>) -> CompileResult<()> {
         let span = self.span();
 
-        if let Some(first) = self.attributes.first() {
-            return Err(CompileError::msg(
-                first,
-                "enum attributes are not supported",
-            ));
-        }
+        let docs = idx.docs_only(&self.attributes, "enum attributes are not supported")?;
 
         let name = self.name.resolve(&idx.storage, &*idx.source)?;
         let _guard = idx.items.push_name(name.as_ref());
@@ -1310,22 +1365,21 @@ impl Index for ast::ItemEnum {
         )?;
 
         idx.query.index_enum(&enum_item, &idx.source)?;
+        idx.visit_docs(&enum_item.item, &docs);
 
         for (variant, _) in &mut self.variants {
-            if let Some(first) = variant.attributes.first() {
-                return Err(CompileError::msg(
-                    first,
-                    "variant attributes are not supported yet",
-                ));
-            }
+            let docs = idx.docs_only(
+                &variant.attributes,
+                "variant attributes are not supported yet",
+            )?;
+
+            let mut field_docs = Vec::new();
 
             for (field, _) in variant.body.fields() {
-                if let Some(first) = field.attributes.first() {
-                    return Err(CompileError::msg(
-                        first,
-                        "field attributes are not supported",
-                    ));
-                }
+                let docs =
+                    idx.docs_only(&field.attributes, "field attributes are not supported")?;
+                let name = field.name.resolve(&idx.storage, &idx.source)?;
+                field_docs.push((name.into_owned(), docs));
             }
 
             let span = variant.name.span();
@@ -1343,6 +1397,9 @@ impl Index for ast::ItemEnum {
 
             idx.query
                 .index_variant(&item, &idx.source, enum_item.id, variant.clone())?;
+
+            idx.visit_docs(&item.item, &docs);
+            idx.visit_field_docs(&item.item, &field_docs);
         }
 
         Ok(())
@@ -1353,25 +1410,21 @@ impl Index for Box<ast::ItemStruct> {
     fn index(&mut self, idx: &mut Indexer<'_>) -> CompileResult<()> {
         let span = self.span();
 
-        if let Some(first) = self.attributes.first() {
-            return Err(CompileError::msg(
-                first,
-                "struct attributes are not supported",
-            ));
-        }
+        let docs = idx.docs_only(&self.attributes, "struct attributes are not supported")?;
+        let mut field_docs = Vec::new();
 
         for (field, _) in self.body.fields() {
-            if let Some(first) = field.attributes.first() {
-                return Err(CompileError::msg(
-                    first,
-                    "field attributes are not supported",
-                ));
-            } else if !field.visibility.is_inherited() {
+            let docs = idx.docs_only(&field.attributes, "field attributes are not supported")?;
+
+            if !field.visibility.is_inherited() {
                 return Err(CompileError::msg(
                     &field,
                     "field visibility levels are not supported",
                 ));
             }
+
+            let name = field.name.resolve(&idx.storage, &idx.source)?;
+            field_docs.push((name.into_owned(), docs));
         }
 
         let ident = self.ident.resolve(&idx.storage, &*idx.source)?;
@@ -1388,18 +1441,16 @@ impl Index for Box<ast::ItemStruct> {
         self.id = Some(item.id);
 
         idx.query.index_struct(&item, &idx.source, self.clone())?;
+        idx.visit_docs(&item.item, &docs);
+        idx.visit_field_docs(&item.item, &field_docs);
         Ok(())
     }
 }
 
 impl Index for ast::ItemImpl {
     fn index(&mut self, idx: &mut Indexer<'_>) -> CompileResult<()> {
-        if let Some(first) = self.attributes.first() {
-            return Err(CompileError::msg(
-                first,
-                "impl attributes are not supported",
-            ));
-        }
+        // NB: doc comments on impl blocks are ignored.
+        idx.docs_only(&self.attributes, "impl attributes are not supported")?;
 
         let mut guards = Vec::new();
 
@@ -1432,18 +1483,14 @@ impl Index for ast::ItemImpl {
 
 impl Index for ast::ItemMod {
     fn index(&mut self, idx: &mut Indexer<'_>) -> CompileResult<()> {
-        if let Some(first) = self.attributes.first() {
-            return Err(CompileError::msg(
-                first,
-                "module attributes are not supported",
-            ));
-        }
+        let docs = idx.docs_only(&self.attributes, "module attributes are not supported")?;
 
         let name_span = self.name_span();
 
         match &mut self.body {
             ast::ItemModBody::EmptyBody(..) => {
-                idx.handle_file_mod(self)?;
+                let mod_item = idx.handle_file_mod(self)?;
+                idx.visit_docs(&mod_item.item, &docs);
             }
             ast::ItemModBody::InlineBody(body) => {
                 let name = self.name.resolve(&idx.storage, &*idx.source)?;
@@ -1459,6 +1506,7 @@ impl Index for ast::ItemMod {
                 )?;
 
                 self.id = Some(idx.items.id());
+                idx.visit_docs(&mod_item.item, &docs);
 
                 let replaced = std::mem::replace(&mut idx.mod_item, mod_item);
                 body.file.index(idx)?;
@@ -1472,12 +1520,10 @@ impl Index for ast::ItemMod {
 
 impl Index for Box<ast::ItemConst> {
     fn index(&mut self, idx: &mut Indexer<'_>) -> CompileResult<()> {
-        if let Some(first) = self.attributes.first() {
-            return Err(CompileError::msg(
-                first,
-                "attributes on constants are not supported",
-            ));
-        }
+        let docs = idx.docs_only(
+            &self.attributes,
+            "attributes on constants are not supported",
+        )?;
 
         let span = self.span();
         let name = self.name.resolve(&idx.storage, &*idx.source)?;
@@ -1498,6 +1544,7 @@ impl Index for Box<ast::ItemConst> {
         idx.nested_item = last;
 
         idx.query.index_const(&item, &idx.source, &self.expr)?;
+        idx.visit_docs(&item.item, &docs);
         Ok(())
     }
 }
@@ -1513,6 +1560,9 @@ impl Index for ast::Item {
             idx.source.clone(),
         );
 
+        // NB: doc comments are handled by each kind of item.
+        attributes.try_parse_collect::<attrs::Doc>()?;
+
         match self {
             ast::Item::Enum(item_enum) => {
                 item_enum.index(idx)?;
@@ -1836,13 +1886,9 @@ impl Index for ast::ExprCall {
 }
 
 impl Index for ast::ExprLit {
-    fn index(&mut self, _: &mut Indexer<'_>) -> CompileResult<()> {
-        if let Some(first) = self.attributes.first() {
-            return Err(CompileError::msg(
-                first,
-                "literal attributes are not supported",
-            ));
-        }
+    fn index(&mut self, idx: &mut Indexer<'_>) -> CompileResult<()> {
+        // NB: doc comments on literals are ignored.
+        idx.docs_only(&self.attributes, "literal attributes are not supported")?;
 
         match &mut self.lit {
             // NB: literals have nothing to index, they don't export language
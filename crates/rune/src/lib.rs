@@ -177,6 +177,7 @@ mod compiling;
 mod diagnostics;
 #[cfg(feature = "diagnostics")]
 mod emit_diagnostics;
+mod formatting;
 mod indexing;
 mod ir;
 mod load;
@@ -184,6 +185,8 @@ pub mod macros;
 mod options;
 mod parsing;
 mod query;
+mod reload;
+mod runtime;
 mod shared;
 mod spanned;
 mod worker;
@@ -206,6 +209,7 @@ pub use self::diagnostics::{Diagnostic, Diagnostics, Error, ErrorKind, Warning,
 pub use self::emit_diagnostics::{
     termcolor, DiagnosticsError, DumpInstructions, EmitDiagnostics, EmitSource,
 };
+pub use self::formatting::{format_source, FormatError};
 pub use self::ir::{IrError, IrErrorKind, IrValue};
 pub use self::load::{load_sources, load_sources_with_visitor, LoadSourcesError};
 pub use self::load::{FileSourceLoader, SourceLoader, Sources};
@@ -218,6 +222,8 @@ pub use self::parsing::{
     ResolveErrorKind, ResolveOwned,
 };
 pub use self::query::{QueryError, QueryErrorKind, Used};
+pub use self::reload::{Change, ChangeKind, Reload};
+pub use self::runtime::Runtime;
 pub use self::shared::{ScopeError, ScopeErrorKind};
 pub use self::spanned::{OptionSpanned, Spanned};
 pub use compiling::compile;
@@ -2,7 +2,7 @@ use runestick::{Source, SourceId};
 use std::sync::Arc;
 
 /// A collection of source files, and a queue of things to compile.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Sources {
     /// Sources associated.
     sources: Vec<Arc<Source>>,
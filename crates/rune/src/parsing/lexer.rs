@@ -13,6 +13,8 @@ pub struct Lexer<'a> {
     modes: LexerModes,
     /// Buffered tokens.
     buffer: VecDeque<ast::Token>,
+    /// Comments which have been retained as trivia, if enabled.
+    comments: Option<Vec<Span>>,
 }
 
 impl<'a> Lexer<'a> {
@@ -46,9 +48,41 @@ impl<'a> Lexer<'a> {
             iter: SourceIter::new(source),
             modes: LexerModes::default(),
             buffer: VecDeque::new(),
+            comments: None,
         }
     }
 
+    /// Construct a new lexer over the given source which retains the spans of
+    /// comments as trivia, instead of discarding them.
+    ///
+    /// The retained comments can be accessed through [Lexer::comments].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rune::Lexer;
+    /// use runestick::span;
+    ///
+    /// let mut lexer = Lexer::with_comments("a // first\nb // second");
+    /// while lexer.next().unwrap().is_some() {}
+    ///
+    /// assert_eq!(lexer.comments(), &[span!(2, 10), span!(13, 22)]);
+    /// ```
+    pub fn with_comments(source: &'a str) -> Self {
+        Self {
+            comments: Some(Vec::new()),
+            ..Self::new(source)
+        }
+    }
+
+    /// Access the spans of comments which have been retained so far.
+    ///
+    /// This is always empty unless the lexer was constructed using
+    /// [Lexer::with_comments].
+    pub fn comments(&self) -> &[Span] {
+        self.comments.as_deref().unwrap_or_default()
+    }
+
     /// Access the span of the lexer.
     pub fn span(&self) -> Span {
         self.iter.end_span(0)
@@ -88,6 +122,43 @@ impl<'a> Lexer<'a> {
         });
     }
 
+    /// Emit the tokens of a doc attribute, like `#[doc = "..."]`, for a doc
+    /// comment.
+    ///
+    /// The doc string refers directly to the text of the comment.
+    fn emit_doc_attribute(&mut self, inner: bool, span: Span, doc_span: Span) {
+        self.buffer.push_back(ast::Token { kind: K![#], span });
+
+        if inner {
+            self.buffer.push_back(ast::Token { kind: K![!], span });
+        }
+
+        self.buffer.push_back(ast::Token {
+            kind: K!['['],
+            span,
+        });
+
+        self.buffer.push_back(ast::Token {
+            kind: ast::Kind::Ident(ast::StringSource::BuiltIn(ast::BuiltIn::Doc)),
+            span,
+        });
+
+        self.buffer.push_back(ast::Token { kind: K![=], span });
+
+        self.buffer.push_back(ast::Token {
+            kind: ast::Kind::Str(ast::StrSource::Text(ast::StrText {
+                escaped: false,
+                wrapped: false,
+            })),
+            span: doc_span,
+        });
+
+        self.buffer.push_back(ast::Token {
+            kind: K![']'],
+            span,
+        });
+    }
+
     fn next_ident(&mut self, start: usize) -> Result<Option<ast::Token>, ParseError> {
         while let Some(c) = self.iter.peek() {
             if !matches!(c, 'a'..='z' | 'A'..='Z' | '_' | '0'..='9') {
@@ -321,9 +392,48 @@ impl<'a> Lexer<'a> {
         }))
     }
 
-    /// Consume the entire line.
-    fn consume_line(&mut self) {
-        while !matches!(self.iter.next(), Some('\n') | None) {}
+    /// Consume the entire line, retaining it as a comment if enabled.
+    fn consume_line(&mut self, start: usize) {
+        let mut end = self.iter.pos();
+
+        while !matches!(self.iter.next(), Some('\n') | None) {
+            end = self.iter.pos();
+        }
+
+        if let Some(comments) = &mut self.comments {
+            comments.push(Span::new(start, end));
+        }
+    }
+
+    /// Test if the comment being lexed is a doc comment, after its first `/`
+    /// has been consumed.
+    ///
+    /// Returns `Some(true)` for inner doc comments like `//!`, `Some(false)`
+    /// for outer doc comments like `///`, and `None` for regular comments,
+    /// which includes comments starting with `////`.
+    fn doc_comment_style(&self) -> Option<bool> {
+        let mut it = self.iter.clone();
+        it.next()?;
+
+        match (it.next()?, it.next()) {
+            ('!', _) => Some(true),
+            ('/', Some('/')) => None,
+            ('/', _) => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Consume the rest of the line of a doc comment, returning the span of its
+    /// text.
+    fn consume_doc_line(&mut self) -> Span {
+        let start = self.iter.pos();
+        let mut end = start;
+
+        while !matches!(self.iter.next(), Some('\n') | None) {
+            end = self.iter.pos();
+        }
+
+        Span::new(start, end)
     }
 
     fn template_next(&mut self) -> Result<(), ParseError> {
@@ -517,7 +627,18 @@ impl<'a> Lexer<'a> {
                             break ast::Kind::PipeEq;
                         }
                         ('/', '/') => {
-                            self.consume_line();
+                            if self.comments.is_none() {
+                                if let Some(inner) = self.doc_comment_style() {
+                                    self.iter.next();
+                                    self.iter.next();
+                                    let doc_span = self.consume_doc_line();
+                                    let span = Span::new(start, doc_span.end);
+                                    self.emit_doc_attribute(inner, span, doc_span);
+                                    continue 'outer;
+                                }
+                            }
+
+                            self.consume_line(start);
                             continue 'outer;
                         }
                         (':', ':') => {
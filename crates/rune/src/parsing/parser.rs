@@ -1,4 +1,4 @@
-use crate::ast::{Kind, Token};
+use crate::ast::{BuiltIn, Kind, StringSource, Token};
 use crate::macros::{TokenStream, TokenStreamIter};
 use crate::parsing::{Lexer, Parse, ParseError, ParseErrorKind, Peek};
 use crate::OptionSpanned as _;
@@ -79,6 +79,7 @@ impl<'a> Parser<'a> {
                 buf: VecDeque::new(),
                 error: None,
                 last: None,
+                doc_comments: false,
             },
             span,
         }
@@ -137,6 +138,16 @@ impl<'a> Parser<'a> {
             return Err(error);
         }
 
+        loop {
+            let len = self.peeker.doc_comment_len(0)?;
+
+            if len == 0 {
+                break;
+            }
+
+            self.peeker.buf.drain(..len);
+        }
+
         if let Some(t) = self.peeker.buf.pop_front() {
             return Ok(t);
         }
@@ -205,6 +216,9 @@ impl<'a> Parser<'a> {
 }
 
 /// Construct used to peek a parser.
+///
+/// Doc comments are only visible while parsing attributes, anywhere else they
+/// are skipped like any other comment.
 #[derive(Debug)]
 pub struct Peeker<'a> {
     pub(crate) source: Source<'a>,
@@ -213,6 +227,8 @@ pub struct Peeker<'a> {
     error: Option<ParseError>,
     /// The last span we encountered. Used to provide better EOF diagnostics.
     last: Option<Span>,
+    /// If doc comments are visible.
+    doc_comments: bool,
 }
 
 impl<'a> Peeker<'a> {
@@ -236,13 +252,41 @@ impl<'a> Peeker<'a> {
         }
     }
 
-    /// Make sure there are at least `n` items in the buffer, and return the
-    /// item at that point.
+    /// Set if doc comments are visible, returning if they were visible before.
+    pub(crate) fn set_doc_comments(&mut self, visible: bool) -> bool {
+        std::mem::replace(&mut self.doc_comments, visible)
+    }
+
+    /// Return the item at the given position, skipping over doc comments
+    /// unless they are visible.
     fn at(&mut self, n: usize) -> Result<Option<Token>, ParseError> {
         if let Some(error) = self.error.take() {
             return Err(error);
         }
 
+        let mut raw = 0;
+        let mut n = n;
+
+        loop {
+            let len = self.doc_comment_len(raw)?;
+
+            if len > 0 {
+                raw += len;
+                continue;
+            }
+
+            if n == 0 {
+                return self.fill(raw);
+            }
+
+            raw += 1;
+            n -= 1;
+        }
+    }
+
+    /// Make sure there are at least `n` items in the buffer, and return the
+    /// item at that point.
+    fn fill(&mut self, n: usize) -> Result<Option<Token>, ParseError> {
         while self.buf.len() <= n {
             let token = match self.source.next()? {
                 Some(token) => token,
@@ -256,6 +300,39 @@ impl<'a> Peeker<'a> {
         Ok(self.buf.get(n).copied())
     }
 
+    /// The number of tokens in the hidden doc comment which starts at the given
+    /// position in the buffer, or zero if there is none.
+    ///
+    /// The lexer emits a doc comment as a `#[doc = "..."]` attribute, where
+    /// `doc` is a built-in identifier.
+    fn doc_comment_len(&mut self, n: usize) -> Result<usize, ParseError> {
+        if self.doc_comments {
+            return Ok(0);
+        }
+
+        let mut kind = |n| Ok::<_, ParseError>(self.fill(n)?.map(|t| t.kind));
+
+        if kind(n)? != Some(K![#]) {
+            return Ok(0);
+        }
+
+        let open = if kind(n + 1)? == Some(K![!]) {
+            n + 2
+        } else {
+            n + 1
+        };
+
+        match (kind(open)?, kind(open + 1)?) {
+            (Some(K!['[']), Some(Kind::Ident(StringSource::BuiltIn(BuiltIn::Doc)))) => {
+                // NB: `[doc = "..."]`.
+                let len = open + 5 - n;
+                self.fill(n + len - 1)?;
+                Ok(len)
+            }
+            _ => Ok(0),
+        }
+    }
+
     /// Test if we are at end of file.
     pub fn is_eof(&mut self) -> bool {
         match self.at(0) {
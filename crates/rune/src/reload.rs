@@ -0,0 +1,175 @@
+use runestick::{Hash, Item, Unit, UnitFn};
+use std::fmt;
+
+/// How a function or type changed when reloading a unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// The item was added.
+    Added,
+    /// The item was removed.
+    Removed,
+    /// The signature of the item changed, like the number of arguments a
+    /// function takes or the kind of a type.
+    Changed,
+}
+
+impl fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added => write!(f, "added"),
+            Self::Removed => write!(f, "removed"),
+            Self::Changed => write!(f, "changed"),
+        }
+    }
+}
+
+/// A function or type which changed when reloading a unit.
+#[derive(Debug, Clone)]
+pub struct Change {
+    /// The hash of the function or type.
+    pub hash: Hash,
+    /// The item of the function or type, if it's known.
+    ///
+    /// Function items are only available if the unit was built with debug
+    /// info.
+    pub item: Option<Item>,
+    /// How the function or type changed.
+    pub kind: ChangeKind,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.item {
+            Some(item) => write!(f, "{} {}", self.kind, item),
+            None => write!(f, "{} {}", self.kind, self.hash),
+        }
+    }
+}
+
+/// Report of the differences between two builds of a unit.
+///
+/// Functions are compared through their hash, calling convention and number
+/// of arguments. Types are compared through their hash and their constructor.
+#[derive(Debug, Clone, Default)]
+pub struct Reload {
+    /// Functions which were added, removed or changed.
+    pub functions: Vec<Change>,
+    /// Types and variants which were added, removed or changed.
+    pub types: Vec<Change>,
+}
+
+impl Reload {
+    /// Test if nothing changed between the two units.
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty() && self.types.is_empty()
+    }
+
+    /// Compare two units.
+    pub(crate) fn diff(old: &Unit, new: &Unit) -> Self {
+        let mut functions = Vec::new();
+
+        for (hash, old_fn) in old.iter_functions() {
+            if !matches!(old_fn, UnitFn::Offset { .. }) {
+                continue;
+            }
+
+            let kind = match new.lookup(hash) {
+                Some(new_fn) if same_signature(*old_fn, new_fn) => continue,
+                Some(..) => ChangeKind::Changed,
+                None => ChangeKind::Removed,
+            };
+
+            functions.push(Change {
+                hash,
+                item: function_item(old, hash),
+                kind,
+            });
+        }
+
+        for (hash, new_fn) in new.iter_functions() {
+            if !matches!(new_fn, UnitFn::Offset { .. }) || old.lookup(hash).is_some() {
+                continue;
+            }
+
+            functions.push(Change {
+                hash,
+                item: function_item(new, hash),
+                kind: ChangeKind::Added,
+            });
+        }
+
+        let mut types = Vec::new();
+
+        for (hash, item) in iter_types(old) {
+            let kind = if !has_type(new, hash) {
+                ChangeKind::Removed
+            } else if old.lookup(hash) != new.lookup(hash) {
+                ChangeKind::Changed
+            } else {
+                continue;
+            };
+
+            types.push(Change {
+                hash,
+                item: Some(item.clone()),
+                kind,
+            });
+        }
+
+        for (hash, item) in iter_types(new) {
+            if has_type(old, hash) {
+                continue;
+            }
+
+            types.push(Change {
+                hash,
+                item: Some(item.clone()),
+                kind: ChangeKind::Added,
+            });
+        }
+
+        functions.sort_by_key(|change| change.hash);
+        types.sort_by_key(|change| change.hash);
+        Self { functions, types }
+    }
+}
+
+/// Test if two functions have the same signature.
+///
+/// Offsets are not compared, since they change whenever anything before the
+/// function changes.
+fn same_signature(a: UnitFn, b: UnitFn) -> bool {
+    match (a, b) {
+        (
+            UnitFn::Offset {
+                call: a_call,
+                args: a_args,
+                ..
+            },
+            UnitFn::Offset {
+                call: b_call,
+                args: b_args,
+                ..
+            },
+        ) => a_call == b_call && a_args == b_args,
+        (a, b) => a == b,
+    }
+}
+
+/// Iterate over all types and variants declared in a unit.
+fn iter_types(unit: &Unit) -> impl Iterator<Item = (Hash, &Item)> {
+    let types = unit.iter_rtti().map(|rtti| (rtti.hash, &rtti.item));
+    let variants = unit.iter_variant_rtti().map(|rtti| (rtti.hash, &rtti.item));
+    types.chain(variants)
+}
+
+/// Test if the unit declares the given type or variant.
+fn has_type(unit: &Unit, hash: Hash) -> bool {
+    unit.lookup_rtti(hash).is_some() || unit.lookup_variant_rtti(hash).is_some()
+}
+
+/// Look up the item of a function through debug info.
+fn function_item(unit: &Unit, hash: Hash) -> Option<Item> {
+    let debug = unit.debug_info()?;
+    Some(debug.functions.get(&hash)?.path.clone())
+}
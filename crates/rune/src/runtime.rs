@@ -0,0 +1,318 @@
+use crate::{Diagnostics, LoadSourcesError, Options, Reload, Sources};
+use runestick::{
+    Args, Context, FromValue, Function, IntoTypeHash, RuntimeContext, Source, SourceId, Unit, Vm,
+    VmError, VmExecution,
+};
+use std::cell::RefCell;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+#[cfg(feature = "diagnostics")]
+use crate::{termcolor::WriteColor, DiagnosticsError, EmitDiagnostics as _};
+
+/// A high-level facade for embedding Rune.
+///
+/// A runtime owns the [Context] and the [Sources] it compiles, the
+/// [Diagnostics] produced by the last build and the resulting [Unit].
+/// Virtual machines are handed out from a pool, so that repeated calls can
+/// reuse the stack allocated by earlier ones.
+///
+/// # Examples
+///
+/// ```rust
+/// use rune::termcolor::{ColorChoice, StandardStream};
+/// use runestick::Source;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut runtime = rune::Runtime::new(runestick::Context::with_default_modules()?);
+///
+/// runtime.insert(Source::new("entry", r#"
+/// pub fn add(a, b) {
+///     a + b
+/// }
+/// "#));
+///
+/// let result = runtime.build();
+///
+/// if !runtime.diagnostics().is_empty() {
+///     let mut writer = StandardStream::stderr(ColorChoice::Always);
+///     runtime.emit_diagnostics(&mut writer)?;
+/// }
+///
+/// result?;
+///
+/// let output = runtime.call::<i64, _, _>(&["add"], (1, 2))?;
+/// assert_eq!(output, 3);
+/// # Ok(()) }
+/// ```
+pub struct Runtime {
+    /// The context used to compile sources.
+    context: Context,
+    /// The runtime context used by virtual machines.
+    runtime: Arc<RuntimeContext>,
+    /// Options used when compiling.
+    options: Options,
+    /// Sources inserted into the runtime.
+    sources: Sources,
+    /// Sources the current unit was built from, including any loaded modules.
+    loaded: Sources,
+    /// Sources as loaded by the last build, which differ from the sources of
+    /// the current unit if the build failed.
+    last_build: Sources,
+    /// Diagnostics produced by the last build.
+    diagnostics: Diagnostics,
+    /// The unit produced by the last successful build.
+    unit: Arc<Unit>,
+    /// Virtual machines available for reuse.
+    pool: RefCell<Vec<Vm>>,
+}
+
+impl Runtime {
+    /// Construct a new runtime using the given context and default options.
+    ///
+    /// The runtime holds an empty unit until [build][Runtime::build] has been
+    /// called.
+    pub fn new(context: Context) -> Self {
+        Self::with_options(context, Options::default())
+    }
+
+    /// Construct a new runtime using the given context and compiler options.
+    pub fn with_options(context: Context, options: Options) -> Self {
+        let runtime = Arc::new(context.runtime());
+
+        Self {
+            context,
+            runtime,
+            options,
+            sources: Sources::new(),
+            loaded: Sources::new(),
+            last_build: Sources::new(),
+            diagnostics: Diagnostics::new(),
+            unit: Arc::new(Unit::default()),
+            pool: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Insert a source to be compiled by the next [build][Runtime::build].
+    pub fn insert(&mut self, source: Source) -> SourceId {
+        self.sources.insert(source)
+    }
+
+    /// Access the context used by the runtime.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// Access the runtime context used by virtual machines.
+    pub fn runtime_context(&self) -> &Arc<RuntimeContext> {
+        &self.runtime
+    }
+
+    /// Access the sources inserted into the runtime.
+    pub fn sources(&self) -> &Sources {
+        &self.sources
+    }
+
+    /// Access the sources inserted into the runtime mutably.
+    pub fn sources_mut(&mut self) -> &mut Sources {
+        &mut self.sources
+    }
+
+    /// Access the sources the current unit was built from, including any
+    /// modules loaded from the file system.
+    pub fn loaded_sources(&self) -> &Sources {
+        &self.loaded
+    }
+
+    /// Access the diagnostics produced by the last build.
+    pub fn diagnostics(&self) -> &Diagnostics {
+        &self.diagnostics
+    }
+
+    /// Access the unit produced by the last successful build.
+    pub fn unit(&self) -> &Arc<Unit> {
+        &self.unit
+    }
+
+    /// Compile all sources in the runtime.
+    ///
+    /// Diagnostics from the build replace any diagnostics from earlier builds
+    /// and can be accessed through [diagnostics][Runtime::diagnostics]. If the
+    /// build fails, the unit from the last successful build is kept.
+    pub fn build(&mut self) -> Result<(), LoadSourcesError> {
+        let mut sources = self.sources.clone();
+        let mut diagnostics = Diagnostics::new();
+
+        let result =
+            crate::load_sources(&self.context, &self.options, &mut sources, &mut diagnostics);
+
+        self.last_build = sources;
+        self.diagnostics = diagnostics;
+
+        self.unit = Arc::new(result?);
+        self.loaded = self.last_build.clone();
+        self.pool.get_mut().clear();
+        Ok(())
+    }
+
+    /// Read every source which was loaded from a file again, returning `true`
+    /// if any of them changed.
+    ///
+    /// This includes modules loaded from the file system by the last build.
+    /// Changed sources are compiled by the next [reload][Runtime::reload].
+    pub fn refresh(&mut self) -> io::Result<bool> {
+        let mut sources = Sources::new();
+        let mut changed = false;
+
+        for source in self.sources.iter() {
+            let source = match source.path() {
+                Some(path) => {
+                    let mut updated = Source::new(source.name(), fs::read_to_string(path)?);
+                    *updated.path_mut() = Some(path.to_owned());
+                    updated
+                }
+                None => source.clone(),
+            };
+
+            sources.insert(source);
+        }
+
+        self.sources = sources;
+
+        // NB: modules are read from the file system when building, so it's
+        // enough to check if they changed.
+        for source in self.last_build.iter() {
+            if let Some(path) = source.path() {
+                changed |= match fs::read_to_string(path) {
+                    Ok(content) => content != source.as_str(),
+                    Err(error) if error.kind() == io::ErrorKind::NotFound => true,
+                    Err(error) => return Err(error),
+                };
+            }
+        }
+
+        Ok(changed)
+    }
+
+    /// Rebuild all sources and report the functions and types which changed
+    /// compared to the unit from the last successful build.
+    ///
+    /// Virtual machines in the pool are replaced, but any virtual machines
+    /// and functions held on to elsewhere keep running the old unit until
+    /// they're updated through [migrate][Runtime::migrate] and
+    /// [rebind][Runtime::rebind].
+    pub fn reload(&mut self) -> Result<Reload, LoadSourcesError> {
+        let old = self.unit.clone();
+        self.build()?;
+        Ok(Reload::diff(&old, &self.unit))
+    }
+
+    /// Iterate over the paths of all files loaded by the last build,
+    /// including any modules loaded from the file system.
+    ///
+    /// This is useful for deciding which files to watch for changes.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.last_build.iter().filter_map(|source| source.path())
+    }
+
+    /// Migrate a virtual machine to the current unit.
+    ///
+    /// This can only be done at a safe point, where the virtual machine isn't
+    /// in the middle of an execution. See [Vm::swap_unit].
+    pub fn migrate(&self, vm: &mut Vm) -> Result<(), VmError> {
+        vm.swap_unit(self.unit.clone())?;
+        Ok(())
+    }
+
+    /// Rebind a function to the current unit through its hash.
+    ///
+    /// Returns `None` if the function no longer exists, or if its signature
+    /// changed. See [Function::rebind].
+    pub fn rebind(&self, function: &Function) -> Option<Function> {
+        function.rebind(&self.unit)
+    }
+
+    /// Take a virtual machine for the current unit out of the pool, or
+    /// construct a new one if the pool is empty.
+    ///
+    /// The virtual machine can be handed back with
+    /// [release][Runtime::release] once it's no longer used.
+    pub fn vm(&self) -> Vm {
+        match self.pool.borrow_mut().pop() {
+            Some(vm) => vm,
+            None => Vm::new(self.runtime.clone(), self.unit.clone()),
+        }
+    }
+
+    /// Release a virtual machine back into the pool.
+    ///
+    /// The virtual machine is cleared before it's added to the pool. Virtual
+    /// machines which don't belong to the current unit, like ones which were
+    /// taken out before a rebuild, are dropped.
+    pub fn release(&self, mut vm: Vm) {
+        if !vm.is_same(&self.runtime, &self.unit) {
+            return;
+        }
+
+        vm.clear();
+        self.pool.borrow_mut().push(vm);
+    }
+
+    /// Call the given function using a pooled virtual machine and convert its
+    /// output into `T`.
+    ///
+    /// If any async instructions are encountered, this will error.
+    pub fn call<T, A, N>(&self, name: N, args: A) -> Result<T, VmError>
+    where
+        T: FromValue,
+        N: IntoTypeHash,
+        A: Args,
+    {
+        let mut execution = self.vm().execute(name, args)?;
+        let result = execution.complete();
+        self.release_execution(execution);
+        T::from_value(result?)
+    }
+
+    /// Call the given function asynchronously using a pooled virtual machine
+    /// and convert its output into `T`.
+    pub async fn async_call<T, A, N>(&self, name: N, args: A) -> Result<T, VmError>
+    where
+        T: FromValue,
+        N: IntoTypeHash,
+        A: Args,
+    {
+        let mut execution = self.vm().execute(name, args)?;
+        let result = execution.async_complete().await;
+        self.release_execution(execution);
+        T::from_value(result?)
+    }
+
+    /// Emit the diagnostics produced by the last build.
+    #[cfg(feature = "diagnostics")]
+    pub fn emit_diagnostics<O>(&self, out: &mut O) -> Result<(), DiagnosticsError>
+    where
+        O: WriteColor,
+    {
+        self.diagnostics.emit_diagnostics(out, &self.last_build)
+    }
+
+    /// Emit the given virtual machine error, using the sources of the current
+    /// unit to render where it happened.
+    #[cfg(feature = "diagnostics")]
+    pub fn emit_vm_error<O>(&self, out: &mut O, error: &VmError) -> Result<(), DiagnosticsError>
+    where
+        O: WriteColor,
+    {
+        error.emit_diagnostics(out, &self.loaded)
+    }
+
+    /// Release the virtual machine an execution was started with.
+    fn release_execution(&self, execution: VmExecution) {
+        if let Some(vm) = execution.into_vm() {
+            self.release(vm);
+        }
+    }
+}
@@ -40,6 +40,8 @@ pub(crate) struct FieldAttrs {
 pub(crate) struct DeriveAttrs {
     /// `#[rune(name = "TypeName")]` to override the default type name.
     pub(crate) name: Option<syn::LitStr>,
+    /// `#[rune(item = "path::to::Enum")]` to override the item of an enum.
+    pub(crate) item: Option<syn::LitStr>,
     /// `#[rune(module = "...")]`.
     pub(crate) module: Option<syn::Path>,
     /// `#[rune(install_with = "...")]`.
@@ -53,8 +55,10 @@ pub(crate) struct Tokens {
     pub(crate) from_value: TokenStream,
     pub(crate) variant_data: TokenStream,
     pub(crate) hash: TokenStream,
+    pub(crate) item: TokenStream,
     pub(crate) module: TokenStream,
     pub(crate) named: TokenStream,
+    pub(crate) native_enum: TokenStream,
     pub(crate) object: TokenStream,
     pub(crate) pointer_guard: TokenStream,
     pub(crate) raw_into_mut: TokenStream,
@@ -69,6 +73,8 @@ pub(crate) struct Tokens {
     pub(crate) unsafe_from_value: TokenStream,
     pub(crate) unsafe_to_value: TokenStream,
     pub(crate) value: TokenStream,
+    pub(crate) variant: TokenStream,
+    pub(crate) variant_rtti: TokenStream,
     pub(crate) vm_error_kind: TokenStream,
     pub(crate) vm_error: TokenStream,
     pub(crate) install_with: TokenStream,
@@ -123,8 +129,10 @@ impl Context {
             from_value: quote!(#module::FromValue),
             variant_data: quote!(#module::VariantData),
             hash: quote!(#module::Hash),
+            item: quote!(#module::Item),
             module: quote!(#module::Module),
             named: quote!(#module::Named),
+            native_enum: quote!(#module::NativeEnum),
             object: quote!(#module::Object),
             pointer_guard: quote!(#module::SharedPointerGuard),
             raw_into_mut: quote!(#module::RawMut),
@@ -139,6 +147,8 @@ impl Context {
             unsafe_from_value: quote!(#module::UnsafeFromValue),
             unsafe_to_value: quote!(#module::UnsafeToValue),
             value: quote!(#module::Value),
+            variant: quote!(#module::Variant),
+            variant_rtti: quote!(#module::VariantRtti),
             vm_error_kind: quote!(#module::VmErrorKind),
             vm_error: quote!(#module::VmError),
             install_with: quote!(#module::InstallWith),
@@ -364,6 +374,14 @@ impl Context {
                     })) if path == NAME => {
                         output.name = Some(name);
                     }
+                    // Parse `#[rune(item = "..")]`.
+                    Meta(NameValue(syn::MetaNameValue {
+                        path,
+                        lit: Lit::Str(item),
+                        ..
+                    })) if path == ITEM => {
+                        output.item = Some(item);
+                    }
                     // Parse `#[rune(module = "..")]`.
                     Meta(NameValue(syn::MetaNameValue {
                         path,
@@ -564,7 +582,7 @@ impl Context {
                 }
 
                 fn type_info() -> #type_info {
-                    #type_info::Any(#raw_str::from_str(std::any::type_name::<Self>()))
+                    #type_info::Any(<Self as #named>::BASE_NAME)
                 }
             }
 
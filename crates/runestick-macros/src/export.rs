@@ -0,0 +1,269 @@
+use crate::context::{Context, DeriveAttrs, Tokens};
+use crate::internals::*;
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::ext::IdentExt as _;
+use syn::spanned::Spanned as _;
+use syn::Lit;
+use syn::Meta::*;
+use syn::NestedMeta::*;
+
+/// Arguments to the `#[export]` attribute.
+pub struct Args {
+    args: syn::AttributeArgs,
+}
+
+impl syn::parse::Parse for Args {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let args = input.parse_terminated::<_, syn::Token![,]>(syn::NestedMeta::parse)?;
+
+        Ok(Self {
+            args: args.into_iter().collect(),
+        })
+    }
+}
+
+/// How an exported method is named.
+enum ExportName {
+    /// A function with the given name.
+    Name(syn::LitStr),
+    /// A protocol function.
+    Protocol(syn::Ident),
+}
+
+/// An exported method.
+struct Method {
+    ident: syn::Ident,
+    name: ExportName,
+    instance: bool,
+    asyncness: bool,
+    span: proc_macro2::Span,
+}
+
+/// An `impl` block annotated with `#[export]`.
+pub struct Export {
+    item: syn::ItemImpl,
+}
+
+impl syn::parse::Parse for Export {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            item: input.parse()?,
+        })
+    }
+}
+
+impl Export {
+    pub(super) fn expand(mut self, args: Args) -> Result<TokenStream, Vec<syn::Error>> {
+        let mut ctx = Context::new();
+
+        let attrs = match parse_args(&mut ctx, args.args) {
+            Some(attrs) => attrs,
+            None => return Err(ctx.errors),
+        };
+
+        let tokens = ctx.tokens_with_module(&attrs);
+        let mut exports = Vec::new();
+
+        for item in &mut self.item.items {
+            let method = match item {
+                syn::ImplItem::Method(method) => method,
+                _ => continue,
+            };
+
+            let mut export = None;
+
+            for attr in std::mem::take(&mut method.attrs) {
+                if attr.path != RUNE {
+                    method.attrs.push(attr);
+                    continue;
+                }
+
+                if let Some(name) = parse_method_attr(&mut ctx, &attr, &method.sig.ident) {
+                    export = Some(name);
+                }
+            }
+
+            let name = match export {
+                Some(name) => name,
+                None => continue,
+            };
+
+            let sig = &method.sig;
+
+            if !sig.generics.params.is_empty() {
+                ctx.errors.push(syn::Error::new_spanned(
+                    &sig.generics,
+                    "generic methods can't be exported",
+                ));
+                continue;
+            }
+
+            let instance = matches!(sig.inputs.first(), Some(syn::FnArg::Receiver(..)));
+
+            if !instance {
+                if let ExportName::Protocol(protocol) = &name {
+                    ctx.errors.push(syn::Error::new_spanned(
+                        protocol,
+                        "protocol functions must take `self`",
+                    ));
+                    continue;
+                }
+            }
+
+            exports.push(Method {
+                ident: sig.ident.clone(),
+                name,
+                instance,
+                asyncness: sig.asyncness.is_some(),
+                span: sig.span(),
+            });
+        }
+
+        if !ctx.errors.is_empty() {
+            return Err(ctx.errors);
+        }
+
+        let Tokens {
+            module,
+            context_error,
+            named,
+            raw_str,
+            ..
+        } = &tokens;
+
+        let installers = exports.iter().map(|export| {
+            let ident = &export.ident;
+
+            let name = match &export.name {
+                ExportName::Name(name) => quote!(#name),
+                ExportName::Protocol(protocol) => {
+                    let protocol_path = &tokens.protocol;
+                    quote!(#protocol_path::#protocol)
+                }
+            };
+
+            match (export.instance, export.asyncness) {
+                (true, false) => quote_spanned! { export.span =>
+                    module.inst_fn(#name, Self::#ident)?;
+                },
+                (true, true) => quote_spanned! { export.span =>
+                    module.async_inst_fn(#name, Self::#ident)?;
+                },
+                (false, false) => quote_spanned! { export.span =>
+                    module.function(&[<Self as #named>::BASE_NAME, #raw_str::from_str(#name)], Self::#ident)?;
+                },
+                (false, true) => quote_spanned! { export.span =>
+                    module.async_function(&[<Self as #named>::BASE_NAME, #raw_str::from_str(#name)], Self::#ident)?;
+                },
+            }
+        });
+
+        let installer = quote! {
+            /// Install all functions exported from this `impl` block into the
+            /// given module.
+            pub fn install_exports(module: &mut #module) -> ::std::result::Result<(), #context_error> {
+                #(#installers)*
+                Ok(())
+            }
+        };
+
+        self.item.items.push(syn::ImplItem::Verbatim(installer));
+
+        let item = &self.item;
+        Ok(quote!(#item))
+    }
+}
+
+/// Parse the arguments to the `#[export(..)]` attribute.
+fn parse_args(ctx: &mut Context, args: syn::AttributeArgs) -> Option<DeriveAttrs> {
+    let mut output = DeriveAttrs::default();
+
+    for arg in args {
+        match arg {
+            // Parse `#[export(module = "..")]`.
+            Meta(NameValue(syn::MetaNameValue {
+                path,
+                lit: Lit::Str(s),
+                ..
+            })) if path == MODULE => match s.parse_with(syn::Path::parse_mod_style) {
+                Ok(module) => {
+                    output.module = Some(module);
+                }
+                Err(error) => {
+                    ctx.errors.push(error);
+                    return None;
+                }
+            },
+            arg => {
+                ctx.errors
+                    .push(syn::Error::new_spanned(arg, "unsupported attribute"));
+                return None;
+            }
+        }
+    }
+
+    Some(output)
+}
+
+/// Parse a `#[rune]`, `#[rune(name = "..")]` or `#[rune(protocol = "..")]`
+/// attribute on a method.
+fn parse_method_attr(
+    ctx: &mut Context,
+    attr: &syn::Attribute,
+    ident: &syn::Ident,
+) -> Option<ExportName> {
+    let default = || ExportName::Name(syn::LitStr::new(&ident.unraw().to_string(), ident.span()));
+
+    let meta = match attr.parse_meta() {
+        Ok(Path(..)) => return Some(default()),
+        Ok(List(meta)) => meta,
+        Ok(other) => {
+            ctx.errors.push(syn::Error::new_spanned(
+                other,
+                "expected #[rune] or #[rune(...)]",
+            ));
+            return None;
+        }
+        Err(error) => {
+            ctx.errors.push(error);
+            return None;
+        }
+    };
+
+    let mut name = None;
+
+    for meta in meta.nested {
+        match meta {
+            // Parse `#[rune(name = "..")]`.
+            Meta(NameValue(syn::MetaNameValue {
+                path,
+                lit: Lit::Str(s),
+                ..
+            })) if path == NAME => {
+                name = Some(ExportName::Name(s));
+            }
+            // Parse `#[rune(protocol = "..")]`.
+            Meta(NameValue(syn::MetaNameValue {
+                path,
+                lit: Lit::Str(s),
+                ..
+            })) if path == PROTOCOL => match s.parse::<syn::Ident>() {
+                Ok(protocol) => {
+                    name = Some(ExportName::Protocol(protocol));
+                }
+                Err(error) => {
+                    ctx.errors.push(error);
+                    return None;
+                }
+            },
+            meta => {
+                ctx.errors
+                    .push(syn::Error::new_spanned(meta, "unsupported attribute"));
+                return None;
+            }
+        }
+    }
+
+    Some(name.unwrap_or_else(default))
+}
@@ -132,6 +132,18 @@ impl Expander {
         let vm_error = &self.tokens.vm_error;
         let vm_error_kind = &self.tokens.vm_error_kind;
 
+        let tuple = if unnamed_matches.is_empty() {
+            quote!(_)
+        } else {
+            quote!(tuple)
+        };
+
+        let object = if named_matches.is_empty() {
+            quote!(_)
+        } else {
+            quote!(object)
+        };
+
         let variant = quote_spanned! { input.span() =>
             #value::Variant(variant) => {
                 let variant = variant.borrow_ref()?;
@@ -149,13 +161,13 @@ impl Expander {
                             return Err(#vm_error::from(#vm_error_kind::MissingVariant { name: name.into() }))
                         }
                     },
-                    #variant_data::Tuple(tuple) => match name {
+                    #variant_data::Tuple(#tuple) => match name {
                         #(#unnamed_matches)*
                         name => {
                             return Err(#vm_error::from(#vm_error_kind::MissingVariant { name: name.into() }))
                         }
                     },
-                    #variant_data::Struct(object) => match name {
+                    #variant_data::Struct(#object) => match name {
                         #(#named_matches)*
                         name => {
                             return Err(#vm_error::from(#vm_error_kind::MissingVariant { name: name.into() }))
@@ -8,8 +8,10 @@ pub struct Symbol(&'static str);
 pub const RUNESTICK: Symbol = Symbol("runestick");
 pub const RUNE: Symbol = Symbol("rune");
 pub const NAME: Symbol = Symbol("name");
+pub const ITEM: Symbol = Symbol("item");
 pub const MODULE: Symbol = Symbol("module");
 pub const INSTALL_WITH: Symbol = Symbol("install_with");
+pub const PROTOCOL: Symbol = Symbol("protocol");
 
 pub const GET: Symbol = Symbol("get");
 pub const SET: Symbol = Symbol("set");
@@ -51,8 +51,10 @@ use quote::quote;
 
 mod any;
 mod context;
+mod export;
 mod from_value;
 mod internals;
+mod native_enum;
 mod to_value;
 
 /// Conversion macro for constructing proxy objects from a dynamic value.
@@ -65,6 +67,23 @@ pub fn from_value(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
 }
 
 /// Conversion macro for constructing proxy objects from a dynamic value.
+///
+/// Enums are converted into variants of the enum with the same name as the
+/// type, like a script-defined `enum Shape { .. }`. Use
+/// `#[rune(item = "path::to::Enum")]` to convert into variants of an enum
+/// declared in another module, or registered by a native module.
+///
+/// ```rust
+/// use runestick::ToValue;
+///
+/// #[derive(ToValue)]
+/// #[rune(item = "geometry::Shape")]
+/// enum Shape {
+///     Circle(f64),
+///     Rect { w: f64, h: f64 },
+///     Empty,
+/// }
+/// ```
 #[proc_macro_derive(ToValue, attributes(rune))]
 pub fn to_value(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
@@ -73,6 +92,41 @@ pub fn to_value(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         .into()
 }
 
+/// Derive for registering a Rust enum as a native enum, with its unit, tuple
+/// and struct variants.
+///
+/// The enum is registered with `Module::native_enum`, under its own name or
+/// the one given with `#[rune(name = "..")]`. If `#[rune(item = "..")]` is
+/// used, the last component of the item is used as the name and the enum
+/// should be installed in a module with the remaining components. Combined
+/// with `#[derive(ToValue, FromValue)]`, this lets values be passed between
+/// Rust and scripts which match on the variants.
+///
+/// ```rust
+/// use runestick::{FromValue, NativeEnum, ToValue};
+///
+/// #[derive(NativeEnum, ToValue, FromValue)]
+/// #[rune(item = "geometry::Shape")]
+/// enum Shape {
+///     Circle(f64),
+///     Rect { w: f64, h: f64 },
+///     Empty,
+/// }
+///
+/// fn install() -> Result<runestick::Module, runestick::ContextError> {
+///     let mut module = runestick::Module::with_item(&["geometry"]);
+///     module.native_enum::<Shape>()?;
+///     Ok(module)
+/// }
+/// ```
+#[proc_macro_derive(NativeEnum, attributes(rune))]
+pub fn native_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    native_enum::expand(&input)
+        .unwrap_or_else(to_compile_errors)
+        .into()
+}
+
 /// Macro to mark a value as external, which will implement all the appropriate
 /// traits.
 ///
@@ -106,6 +160,66 @@ pub fn any(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive.expand().unwrap_or_else(to_compile_errors).into()
 }
 
+/// Attribute for an `impl` block which generates an installer for all methods
+/// marked with `#[rune]`.
+///
+/// The installer is an associated function called `install_exports`, which
+/// registers methods that take `self` as instance functions and all other
+/// methods as associated functions on the type, like `Foo::new`. It can be
+/// called directly or hooked up with `#[rune(install_with = "...")]` so that
+/// the methods are installed together with the type.
+///
+/// Methods are exported under their own name by default, which can be changed
+/// with `#[rune(name = "...")]`. Instance methods can instead be registered as
+/// protocol functions with `#[rune(protocol = "...")]`, using the name of a
+/// constant in [Protocol](https://docs.rs/runestick/0/runestick/struct.Protocol.html).
+///
+/// ```rust
+/// use runestick::Any;
+/// use std::fmt::Write as _;
+///
+/// #[derive(Any)]
+/// #[rune(install_with = "Counter::install_exports")]
+/// struct Counter {
+///     count: i64,
+/// }
+///
+/// #[runestick::export]
+/// impl Counter {
+///     #[rune]
+///     fn new(count: i64) -> Self {
+///         Self { count }
+///     }
+///
+///     #[rune(name = "inc")]
+///     fn increment(&mut self) {
+///         self.count += 1;
+///     }
+///
+///     #[rune(protocol = "STRING_DISPLAY")]
+///     fn display(&self, buf: &mut String) -> std::fmt::Result {
+///         write!(buf, "{}", self.count)
+///     }
+///
+///     fn not_exported(&self) {}
+/// }
+///
+/// fn install() -> Result<runestick::Module, runestick::ContextError> {
+///     let mut module = runestick::Module::new();
+///     module.ty::<Counter>()?;
+///     Ok(module)
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn export(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let args = syn::parse_macro_input!(args as export::Args);
+    let export = syn::parse_macro_input!(input as export::Export);
+    export.expand(args).unwrap_or_else(to_compile_errors).into()
+}
+
 /// Internal macro to implement external.
 #[proc_macro]
 #[doc(hidden)]
@@ -0,0 +1,90 @@
+use crate::context::Context;
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned as _;
+
+pub(super) fn expand(input: &syn::DeriveInput) -> Result<TokenStream, Vec<syn::Error>> {
+    let mut ctx = Context::new();
+
+    let attrs = match ctx.parse_derive_attrs(&input.attrs) {
+        Some(attrs) => attrs,
+        None => {
+            return Err(ctx.errors);
+        }
+    };
+
+    let tokens = ctx.tokens_with_module(&attrs);
+
+    let en = match &input.data {
+        syn::Data::Enum(en) => en,
+        syn::Data::Struct(st) => {
+            ctx.errors.push(syn::Error::new_spanned(
+                st.struct_token,
+                "only supported on enums",
+            ));
+            return Err(ctx.errors);
+        }
+        syn::Data::Union(un) => {
+            ctx.errors.push(syn::Error::new_spanned(
+                un.union_token,
+                "only supported on enums",
+            ));
+            return Err(ctx.errors);
+        }
+    };
+
+    // The enum is registered relative to the module it's installed in, so only
+    // the last component of `#[rune(item = "..")]` is used.
+    let name = match (&attrs.item, &attrs.name) {
+        (Some(item), _) => {
+            let value = item.value();
+            let last = value.rsplit("::").next().unwrap_or_default();
+            syn::LitStr::new(last, item.span())
+        }
+        (None, Some(name)) => name.clone(),
+        (None, None) => syn::LitStr::new(&input.ident.to_string(), input.ident.span()),
+    };
+
+    let mut variants = Vec::new();
+
+    for variant in &en.variants {
+        let lit_str = syn::LitStr::new(&variant.ident.to_string(), variant.span());
+
+        variants.push(match &variant.fields {
+            syn::Fields::Unit => quote_spanned! { variant.span() =>
+                .unit(#lit_str)
+            },
+            syn::Fields::Unnamed(unnamed) => {
+                let args = unnamed.unnamed.len();
+
+                quote_spanned! { variant.span() =>
+                    .tuple(#lit_str, #args)
+                }
+            }
+            syn::Fields::Named(named) => {
+                let fields = named.named.iter().flat_map(|f| {
+                    let ident = f.ident.as_ref()?;
+                    Some(syn::LitStr::new(&ident.to_string(), ident.span()))
+                });
+
+                quote_spanned! { variant.span() =>
+                    .struct_(#lit_str, &[#(#fields),*])
+                }
+            }
+        });
+    }
+
+    let ident = &input.ident;
+    let module = &tokens.module;
+    let context_error = &tokens.context_error;
+    let native_enum = &tokens.native_enum;
+
+    Ok(quote! {
+        impl #native_enum for #ident {
+            fn install_enum(module: &mut #module) -> ::std::result::Result<(), #context_error> {
+                module.enum_(&[#name]) #(#variants)*;
+                Ok(())
+            }
+        }
+    })
+}
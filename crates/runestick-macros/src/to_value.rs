@@ -1,4 +1,4 @@
-use crate::context::{Context, Tokens};
+use crate::context::{Context, DeriveAttrs, Tokens};
 use proc_macro2::TokenStream;
 use quote::{quote, quote_spanned};
 use syn::spanned::Spanned as _;
@@ -31,6 +31,119 @@ impl Expander {
         })
     }
 
+    /// Expand on an enum.
+    fn expand_enum(
+        &mut self,
+        input: &syn::DeriveInput,
+        attrs: &DeriveAttrs,
+        en: &syn::DataEnum,
+    ) -> Option<TokenStream> {
+        let components = match &attrs.item {
+            Some(item) => item
+                .value()
+                .split("::")
+                .map(|c| syn::LitStr::new(c, item.span()))
+                .collect::<Vec<_>>(),
+            None => match &attrs.name {
+                Some(name) => vec![name.clone()],
+                None => vec![syn::LitStr::new(
+                    &input.ident.to_string(),
+                    input.ident.span(),
+                )],
+            },
+        };
+
+        let Tokens {
+            hash,
+            item,
+            object,
+            to_value,
+            tuple,
+            value,
+            variant,
+            variant_rtti,
+            vm_error,
+            ..
+        } = &self.tokens;
+
+        let mut matches = Vec::new();
+
+        for v in &en.variants {
+            let ident = &v.ident;
+            let name = &syn::LitStr::new(&ident.to_string(), ident.span());
+
+            match &v.fields {
+                syn::Fields::Unit => {
+                    matches.push(quote_spanned! { v.span() =>
+                        Self::#ident => #variant::unit(rtti(#name))
+                    });
+                }
+                syn::Fields::Unnamed(unnamed) => {
+                    let mut bindings = Vec::new();
+
+                    for (index, field) in unnamed.unnamed.iter().enumerate() {
+                        let _ = self.ctx.parse_field_attrs(&field.attrs)?;
+                        bindings.push(quote::format_ident!("f{}", index));
+                    }
+
+                    let cap = bindings.len();
+
+                    matches.push(quote_spanned! { v.span() =>
+                        Self::#ident(#(#bindings),*) => {
+                            let mut tuple = Vec::with_capacity(#cap);
+                            #(tuple.push(#to_value::to_value(#bindings)?);)*
+                            #variant::tuple(rtti(#name), #tuple::from(tuple))
+                        }
+                    });
+                }
+                syn::Fields::Named(named) => {
+                    let mut idents = Vec::new();
+                    let mut names = Vec::new();
+
+                    for field in &named.named {
+                        let ident = field.ident.as_ref()?;
+                        let _ = self.ctx.parse_field_attrs(&field.attrs)?;
+                        names.push(syn::LitStr::new(&ident.to_string(), ident.span()));
+                        idents.push(ident);
+                    }
+
+                    matches.push(quote_spanned! { v.span() =>
+                        Self::#ident { #(#idents),* } => {
+                            let mut object = <#object>::new();
+                            #(object.insert(String::from(#names), #to_value::to_value(#idents)?);)*
+                            #variant::struct_(rtti(#name), object)
+                        }
+                    });
+                }
+            }
+        }
+
+        let ident = &input.ident;
+
+        Some(quote! {
+            impl #to_value for #ident {
+                fn to_value(self) -> ::std::result::Result<#value, #vm_error> {
+                    let rtti = |name: &str| {
+                        let enum_item = #item::with_item(&[#(#components),*]);
+                        let item = #item::with_item(&[#(#components,)* name]);
+
+                        ::std::sync::Arc::new(#variant_rtti {
+                            enum_hash: #hash::type_hash(&enum_item),
+                            hash: #hash::type_hash(&item),
+                            item,
+                        })
+                    };
+
+                    let variant: #variant = match self {
+                        #(#matches,)*
+                    };
+
+                    Ok(#value::from(variant))
+                }
+            }
+        })
+    }
+
     /// Expand field decoding.
     fn expand_fields(&mut self, fields: &syn::Fields) -> Option<TokenStream> {
         match fields {
@@ -143,10 +256,9 @@ pub(super) fn expand(input: &syn::DeriveInput) -> Result<TokenStream, Vec<syn::E
             }
         }
         syn::Data::Enum(en) => {
-            expander.ctx.errors.push(syn::Error::new_spanned(
-                en.enum_token,
-                "not supported on enums",
-            ));
+            if let Some(expanded) = expander.expand_enum(input, &attrs, en) {
+                return Ok(expanded);
+            }
         }
         syn::Data::Union(un) => {
             expander.ctx.errors.push(syn::Error::new_spanned(
@@ -12,6 +12,15 @@ pub trait Args {
     fn count(&self) -> usize;
 }
 
+/// Trait for arguments where the number of arguments is known up front.
+///
+/// This is implemented for tuples, which allows the number of arguments to be
+/// checked before anything is called. See [TypedFunction][crate::TypedFunction].
+pub trait FixedArgs: Args {
+    /// The number of arguments.
+    const COUNT: usize;
+}
+
 macro_rules! impl_into_args {
     () => {
         impl_into_args!{@impl 0,}
@@ -45,6 +54,13 @@ macro_rules! impl_into_args {
                 $count
             }
         }
+
+        impl<$($ty,)*> FixedArgs for ($($ty,)*)
+        where
+            $($ty: $crate::ToValue,)*
+        {
+            const COUNT: usize = $count;
+        }
     };
 }
 
@@ -1,11 +1,13 @@
 use crate::{
     collections::{HashMap, HashSet},
     module::{
-        ModuleAssociatedFn, ModuleFn, ModuleInternalEnum, ModuleMacro, ModuleType, ModuleUnitType,
+        ModuleAssociatedFn, ModuleEnum, ModuleFn, ModuleInternalEnum, ModuleMacro, ModuleType,
+        ModuleUnitType, ModuleVariantKind,
     },
-    CompileMeta, CompileMetaKind, CompileMetaStruct, CompileMetaTuple, ComponentRef, ConstValue,
-    Hash, IntoComponent, Item, Module, Names, Protocol, RuntimeContext, Stack, StaticType,
-    TypeCheck, TypeInfo, TypeOf, VmError,
+    CompileMeta, CompileMetaEmpty, CompileMetaKind, CompileMetaStruct, CompileMetaTuple,
+    ComponentRef, ConstValue, FunctionMeta, Hash, IntoComponent, Item, Module, Names, Protocol,
+    Rtti, RuntimeContext, Stack, StaticType, Tuple, TypeCheck, TypeInfo, TypeOf, Variant,
+    VariantRtti, VmError, VmErrorKind,
 };
 use std::{any, fmt, sync::Arc};
 
@@ -96,6 +98,17 @@ pub enum ContextError {
         /// The instance type.
         instance_type: TypeInfo,
     },
+    /// Error raised when the named arguments of a function don't match the
+    /// number of arguments it accepts.
+    #[error("function `{item}` takes {expected} arguments, but {actual} were named")]
+    MismatchedFunctionArgs {
+        /// The function with mismatched arguments.
+        item: Item,
+        /// The number of arguments which were named.
+        actual: usize,
+        /// The number of arguments the function accepts.
+        expected: usize,
+    },
     /// Error raised when attempting to create a constant value.
     #[error("error when converting to constant value: {error}")]
     ValueError {
@@ -144,6 +157,9 @@ pub enum ContextSignature {
         item: Item,
         /// Arguments.
         args: Option<usize>,
+        /// Documentation, named arguments and the returned type of the
+        /// function.
+        meta: FunctionMeta,
     },
     /// An instance function or method
     Instance {
@@ -157,53 +173,107 @@ pub enum ContextSignature {
         args: Option<usize>,
         /// Information on the self type.
         self_type_info: TypeInfo,
+        /// Documentation, named arguments and the returned type of the
+        /// function.
+        meta: FunctionMeta,
     },
 }
 
-impl fmt::Display for ContextSignature {
-    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl ContextSignature {
+    /// Access the type information of the value returned by the function, if
+    /// it's known.
+    ///
+    /// For async functions this is the [Future][crate::Future] being returned.
+    pub fn return_type(&self) -> Option<&TypeInfo> {
+        self.meta().return_type.as_ref()
+    }
+
+    /// Access the documentation, named arguments and the returned type of the
+    /// function.
+    pub fn meta(&self) -> &FunctionMeta {
         match self {
-            Self::Function { item, args, .. } => {
-                write!(fmt, "{}(", item)?;
+            Self::Function { meta, .. } => meta,
+            Self::Instance { meta, .. } => meta,
+        }
+    }
 
-                if let Some(args) = args {
-                    let mut it = 0..*args;
-                    let last = it.next_back();
+    /// Collect the name and type information of each argument of the
+    /// function, excluding `self`.
+    ///
+    /// If no arguments were named when the function was registered, they are
+    /// named after their position, like `#0`, and have no type information.
+    /// Returns `None` if the arguments of the function are not known.
+    pub fn args(&self) -> Option<Vec<(String, Option<&TypeInfo>)>> {
+        let (args, skip) = match self {
+            Self::Function { args, .. } => (*args, 0),
+            Self::Instance { args, .. } => (*args, 1),
+        };
 
-                    for n in it {
-                        write!(fmt, "#{}, ", n)?;
-                    }
+        let meta = self.meta();
 
-                    if let Some(n) = last {
-                        write!(fmt, "#{}", n)?;
-                    }
-                } else {
-                    write!(fmt, "...")?;
-                }
+        if !meta.args.is_empty() {
+            return Some(
+                meta.args
+                    .iter()
+                    .map(|arg| (arg.name.clone(), arg.type_info.as_ref()))
+                    .collect(),
+            );
+        }
 
-                write!(fmt, ")")?;
+        let args = args?.checked_sub(skip)?;
+        Some((0..args).map(|n| (format!("#{}", n), None)).collect())
+    }
+}
+
+impl fmt::Display for ContextSignature {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+
+        match self {
+            Self::Function { item, .. } => {
+                write!(fmt, "{}(", item)?;
             }
             Self::Instance {
                 item,
                 name,
                 self_type_info,
-                args,
                 ..
             } => {
                 write!(fmt, "{}::{}(self: {}", item, name, self_type_info)?;
+                first = false;
+            }
+        }
 
-                if let Some(args) = args {
-                    for n in 0..*args {
-                        write!(fmt, ", #{}", n)?;
+        match self.args() {
+            Some(args) => {
+                for (name, type_info) in args {
+                    if !std::mem::take(&mut first) {
+                        write!(fmt, ", ")?;
+                    }
+
+                    match type_info {
+                        Some(type_info) => write!(fmt, "{}: {}", name, type_info)?,
+                        None => write!(fmt, "{}", name)?,
                     }
-                } else {
-                    write!(fmt, ", ...")?;
+                }
+            }
+            None => {
+                if !first {
+                    write!(fmt, ", ")?;
                 }
 
-                write!(fmt, ")")?;
+                write!(fmt, "...")?;
             }
         }
 
+        write!(fmt, ")")?;
+
+        match self.return_type() {
+            Some(TypeInfo::StaticType(ty)) if *ty == crate::UNIT_TYPE => (),
+            Some(return_type) => write!(fmt, " -> {}", return_type)?,
+            None => (),
+        }
+
         Ok(())
     }
 }
@@ -251,7 +321,9 @@ impl Context {
     /// Construct a default set of modules with the given configuration.
     ///
     /// * `stdio` determines if we include I/O functions that interact with
-    ///   stdout and stderr by default, like `dbg`, `print`, and `println`.
+    ///   stdout and stderr by default, like `dbg`, `print`, `println` and
+    ///   `eprintln`. Output can be routed elsewhere by passing `false` and
+    ///   installing an [io::output_module][crate::modules::io::output_module].
     pub fn with_config(stdio: bool) -> Result<Self, ContextError> {
         let mut this = Self::new();
         this.install(&crate::modules::any::module()?)?;
@@ -418,6 +490,10 @@ impl Context {
             self.install_internal_enum(module, internal_enum)?;
         }
 
+        for native_enum in &module.enums {
+            self.install_enum(module, native_enum)?;
+        }
+
         for (key, inst) in &module.associated_functions {
             self.install_associated_function(
                 key.type_hash,
@@ -507,6 +583,7 @@ impl Context {
         f: &ModuleFn,
     ) -> Result<(), ContextError> {
         let item = module.item.join(item);
+        check_meta_args(&item, &f.meta, f.args)?;
         self.names.insert(&item);
 
         let hash = Hash::type_hash(&item);
@@ -515,6 +592,7 @@ impl Context {
             type_hash: hash,
             item: item.clone(),
             args: f.args,
+            meta: f.meta.clone(),
         };
 
         if let Some(old) = self.functions_info.insert(hash, signature) {
@@ -618,8 +696,11 @@ impl Context {
             name: assoc.name.clone(),
             args: assoc.args,
             self_type_info: info.type_info.clone(),
+            meta: assoc.meta.clone(),
         };
         let item = info.item.extended(&assoc.name);
+        // NB: the named arguments of instance functions don't include `self`.
+        check_meta_args(&item, &assoc.meta, assoc.args.map(|args| args - 1))?;
 
         self.constants.insert(
             Hash::instance_function(hash, Protocol::INTO_TYPE_NAME),
@@ -740,6 +821,7 @@ impl Context {
                 type_hash: variant.type_hash,
                 item,
                 args: Some(variant.args),
+                meta: FunctionMeta::returning(variant.return_type.clone()),
             };
 
             if let Some(old) = self.functions_info.insert(hash, signature) {
@@ -754,6 +836,138 @@ impl Context {
         Ok(())
     }
 
+    /// Install a native enum.
+    fn install_enum(
+        &mut self,
+        module: &Module,
+        native_enum: &ModuleEnum,
+    ) -> Result<(), ContextError> {
+        let enum_item = module.item.join(&native_enum.item);
+        let enum_hash = Hash::type_hash(&enum_item);
+
+        self.install_meta(CompileMeta {
+            item: Arc::new(enum_item.clone().into()),
+            kind: CompileMetaKind::Enum {
+                type_hash: enum_hash,
+            },
+            source: None,
+        })?;
+
+        self.install_type_info(
+            enum_hash,
+            ContextTypeInfo {
+                type_check: TypeCheck::Type(enum_hash),
+                item: enum_item.clone(),
+                type_hash: enum_hash,
+                type_info: TypeInfo::Typed(Arc::new(Rtti {
+                    hash: enum_hash,
+                    item: enum_item.clone(),
+                })),
+            },
+        )?;
+
+        for variant in &native_enum.variants {
+            let item = enum_item.extended(&*variant.name);
+            let hash = Hash::type_hash(&item);
+
+            let rtti = Arc::new(VariantRtti {
+                enum_hash,
+                hash,
+                item: item.clone(),
+            });
+
+            self.install_type_info(
+                hash,
+                ContextTypeInfo {
+                    type_check: TypeCheck::Variant(hash),
+                    item: item.clone(),
+                    type_hash: hash,
+                    type_info: TypeInfo::Variant(rtti.clone()),
+                },
+            )?;
+
+            let (kind, args, constructor): (_, _, Arc<Handler>) = match &variant.kind {
+                ModuleVariantKind::Unit => (
+                    CompileMetaKind::UnitVariant {
+                        type_hash: hash,
+                        enum_item: enum_item.clone(),
+                        empty: CompileMetaEmpty { hash },
+                    },
+                    0,
+                    Arc::new(move |stack, args| {
+                        check_args(args, 0)?;
+                        stack.push(Variant::unit(rtti.clone()));
+                        Ok(())
+                    }),
+                ),
+                ModuleVariantKind::Tuple(expected) => {
+                    let expected = *expected;
+
+                    (
+                        CompileMetaKind::TupleVariant {
+                            type_hash: hash,
+                            enum_item: enum_item.clone(),
+                            tuple: CompileMetaTuple {
+                                args: expected,
+                                hash,
+                            },
+                        },
+                        expected,
+                        Arc::new(move |stack, args| {
+                            check_args(args, expected)?;
+                            let tuple = Tuple::from(stack.pop_sequence(args)?);
+                            stack.push(Variant::tuple(rtti.clone(), tuple));
+                            Ok(())
+                        }),
+                    )
+                }
+                ModuleVariantKind::Struct(fields) => (
+                    CompileMetaKind::StructVariant {
+                        type_hash: hash,
+                        enum_item: enum_item.clone(),
+                        object: CompileMetaStruct {
+                            fields: fields.iter().cloned().collect(),
+                        },
+                    },
+                    1,
+                    // Struct variants are constructed from an object with
+                    // their fields.
+                    Arc::new(move |stack, args| {
+                        check_args(args, 1)?;
+                        let object = stack.pop()?.into_object()?;
+                        let object = object.take()?;
+                        stack.push(Variant::struct_(rtti.clone(), object));
+                        Ok(())
+                    }),
+                ),
+            };
+
+            self.install_meta(CompileMeta {
+                item: Arc::new(item.clone().into()),
+                kind,
+                source: None,
+            })?;
+
+            let signature = ContextSignature::Function {
+                type_hash: hash,
+                item,
+                args: Some(args),
+                meta: FunctionMeta::default(),
+            };
+
+            if let Some(old) = self.functions_info.insert(hash, signature) {
+                return Err(ContextError::ConflictingFunction {
+                    signature: old,
+                    hash,
+                });
+            }
+
+            self.functions.insert(hash, constructor);
+        }
+
+        Ok(())
+    }
+
     /// Add a piece of internal tuple meta.
     fn add_internal_tuple<C, Args>(
         &mut self,
@@ -802,6 +1016,7 @@ impl Context {
             type_hash,
             item,
             args: Some(args),
+            meta: FunctionMeta::returning(C::Return::type_info()),
         };
 
         if let Some(old) = self.functions_info.insert(hash, signature) {
@@ -815,6 +1030,38 @@ impl Context {
     }
 }
 
+/// Check that the named arguments of a function match the number of arguments
+/// it accepts, if they're known.
+fn check_meta_args(
+    item: &Item,
+    meta: &FunctionMeta,
+    args: Option<usize>,
+) -> Result<(), ContextError> {
+    match args {
+        Some(expected) if !meta.args.is_empty() && meta.args.len() != expected => {
+            Err(ContextError::MismatchedFunctionArgs {
+                item: item.clone(),
+                actual: meta.args.len(),
+                expected,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Check that a native constructor was called with the expected number of
+/// arguments.
+fn check_args(args: usize, expected: usize) -> Result<(), VmError> {
+    if args != expected {
+        return Err(VmError::from(VmErrorKind::BadArgumentCount {
+            actual: args,
+            expected,
+        }));
+    }
+
+    Ok(())
+}
+
 impl fmt::Debug for Context {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Context")
@@ -3,7 +3,7 @@ use crate::internal::AssertSend;
 use crate::VmErrorKind;
 use crate::{
     Args, Call, ConstValue, FromValue, Hash, RawRef, Ref, Rtti, RuntimeContext, Shared, Stack,
-    Tuple, Unit, UnsafeFromValue, Value, VariantRtti, Vm, VmCall, VmError, VmHalt,
+    Tuple, Unit, UnitFn, UnsafeFromValue, Value, VariantRtti, Vm, VmCall, VmError, VmHalt,
 };
 use std::fmt;
 use std::future::Future;
@@ -244,6 +244,57 @@ where
         }
     }
 
+    /// Rebind the function to the given unit, resolving it through its hash.
+    ///
+    /// This keeps functions valid when a unit is reloaded. Native functions
+    /// are returned as they are. Returns `None` if the function no longer
+    /// exists in the given unit, or if its signature changed.
+    pub fn rebind(&self, unit: &Arc<Unit>) -> Option<Self> {
+        let inner = match &self.inner {
+            Inner::FnHandler(func) => Inner::FnHandler(FnHandler {
+                handler: func.handler.clone(),
+                hash: func.hash,
+            }),
+            Inner::FnOffset(func) => Inner::FnOffset(func.rebind(unit)?),
+            Inner::FnClosureOffset(func) => Inner::FnClosureOffset(FnClosureOffset {
+                fn_offset: func.fn_offset.rebind(unit)?,
+                environment: func.environment.clone(),
+            }),
+            Inner::FnUnitStruct(func) => match unit.lookup(func.rtti.hash)? {
+                UnitFn::UnitStruct { hash } => Inner::FnUnitStruct(FnUnitStruct {
+                    rtti: unit.lookup_rtti(hash)?.clone(),
+                }),
+                _ => return None,
+            },
+            Inner::FnTupleStruct(func) => match unit.lookup(func.rtti.hash)? {
+                UnitFn::TupleStruct { hash, args } if args == func.args => {
+                    Inner::FnTupleStruct(FnTupleStruct {
+                        rtti: unit.lookup_rtti(hash)?.clone(),
+                        args,
+                    })
+                }
+                _ => return None,
+            },
+            Inner::FnUnitVariant(func) => match unit.lookup(func.rtti.hash)? {
+                UnitFn::UnitVariant { hash } => Inner::FnUnitVariant(FnUnitVariant {
+                    rtti: unit.lookup_variant_rtti(hash)?.clone(),
+                }),
+                _ => return None,
+            },
+            Inner::FnTupleVariant(func) => match unit.lookup(func.rtti.hash)? {
+                UnitFn::TupleVariant { hash, args } if args == func.args => {
+                    Inner::FnTupleVariant(FnTupleVariant {
+                        rtti: unit.lookup_variant_rtti(hash)?.clone(),
+                        args,
+                    })
+                }
+                _ => return None,
+            },
+        };
+
+        Some(Self { inner })
+    }
+
     #[inline]
     fn check_args(actual: usize, expected: usize) -> Result<(), VmError> {
         if actual != expected {
@@ -429,6 +480,23 @@ impl FnOffset {
         vm.set_ip(self.offset);
         Ok(Some(VmCall::new(self.call, vm)))
     }
+
+    /// Resolve the same function in a different unit.
+    fn rebind(&self, unit: &Arc<Unit>) -> Option<Self> {
+        match unit.lookup(self.hash)? {
+            UnitFn::Offset { offset, call, args } if call == self.call && args == self.args => {
+                Some(Self {
+                    context: self.context.clone(),
+                    unit: unit.clone(),
+                    offset,
+                    call,
+                    args,
+                    hash: self.hash,
+                })
+            }
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Debug for FnOffset {
@@ -92,6 +92,7 @@ mod range;
 mod raw_str;
 mod runtime_context;
 mod select;
+mod send_value;
 mod shared;
 mod source;
 mod span;
@@ -104,6 +105,8 @@ mod to_value;
 mod tuple;
 mod type_info;
 mod type_of;
+mod typed_function;
+mod typed_seed;
 mod unit;
 mod variant;
 mod vec;
@@ -148,7 +151,7 @@ pub type Result<T, E = anyhow::Error> = std::result::Result<T, E>;
 pub type Error = anyhow::Error;
 
 pub use self::any_obj::{AnyObj, AnyObjError, AnyObjVtable};
-pub use self::args::Args;
+pub use self::args::{Args, FixedArgs};
 pub use self::compile_meta::{
     CompileItem, CompileMeta, CompileMetaCapture, CompileMetaEmpty, CompileMetaKind,
     CompileMetaStruct, CompileMetaTuple, CompileMod, CompileSource,
@@ -164,11 +167,14 @@ pub use self::iterator::Iterator;
 pub use self::key::Key;
 pub use self::label::{DebugLabel, Label};
 pub use self::location::Location;
-pub use self::module::{InstFnNameHash, InstallWith, Module};
+pub use self::module::{
+    FunctionArg, FunctionMeta, InstFnNameHash, InstallWith, Module, ModuleEnum, NativeEnum,
+};
 pub use self::named::Named;
 pub use self::raw_str::RawStr;
 pub use self::runtime_context::RuntimeContext;
 pub use self::select::Select;
+pub use self::send_value::SendValue;
 pub use self::source::Source;
 pub use self::span::{ByteIndex, IntoByteIndex, Span};
 pub use self::spanned_error::{SpannedError, WithSpan};
@@ -183,6 +189,8 @@ pub use self::stream::Stream;
 pub use self::to_value::{ToValue, UnsafeToValue};
 pub use self::tuple::Tuple;
 pub use self::type_info::TypeInfo;
+pub use self::typed_function::TypedFunction;
+pub use self::typed_seed::TypedSeed;
 pub use self::variant::{Variant, VariantData};
 pub use self::vec::Vec;
 pub use crate::access::{
@@ -220,7 +228,7 @@ pub use crate::vm_error::{VmError, VmErrorKind, VmIntegerRepr};
 pub use crate::vm_execution::{VmExecution, VmSendExecution};
 pub use crate::vm_halt::{VmHalt, VmHaltInfo};
 pub(crate) use runestick_macros::__internal_impl_any;
-pub use runestick_macros::{Any, FromValue};
+pub use runestick_macros::{export, Any, FromValue, NativeEnum, ToValue};
 
 mod collections {
     pub use hashbrown::{hash_map, HashMap};
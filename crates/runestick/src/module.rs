@@ -69,6 +69,7 @@ impl ModuleInternalEnum {
             name,
             type_check,
             args: C::args(),
+            return_type: C::Return::type_info(),
             constructor,
             type_hash,
         });
@@ -83,12 +84,82 @@ pub(crate) struct ModuleInternalVariant {
     pub(crate) type_check: TypeCheck,
     /// Arguments for the variant.
     pub(crate) args: usize,
+    /// Type information of the value constructed by the variant.
+    pub(crate) return_type: TypeInfo,
     /// The constructor of the variant.
     pub(crate) constructor: Arc<Handler>,
     /// The value type of the variant.
     pub(crate) type_hash: Hash,
 }
 
+/// The kind of a variant in a native enum.
+pub(crate) enum ModuleVariantKind {
+    /// A unit variant.
+    Unit,
+    /// A tuple variant with the given number of fields.
+    Tuple(usize),
+    /// A struct variant with the given fields.
+    Struct(Vec<Box<str>>),
+}
+
+/// A variant in a native enum.
+pub(crate) struct ModuleVariant {
+    /// The name of the variant.
+    pub(crate) name: Box<str>,
+    /// The kind of the variant.
+    pub(crate) kind: ModuleVariantKind,
+}
+
+/// A native enum registered through [Module::enum_].
+///
+/// Values of the enum are [Variant][crate::Variant] values, which scripts can
+/// construct and pattern match on in the same way as enums declared in
+/// scripts.
+pub struct ModuleEnum {
+    /// The item of the enum, relative to the module.
+    pub(crate) item: Item,
+    /// The variants of the enum.
+    pub(crate) variants: Vec<ModuleVariant>,
+}
+
+impl ModuleEnum {
+    /// Register a unit variant, like `Shape::Empty`.
+    pub fn unit(&mut self, name: &str) -> &mut Self {
+        self.variant(name, ModuleVariantKind::Unit)
+    }
+
+    /// Register a tuple variant with the given number of fields, like
+    /// `Shape::Circle(radius)`.
+    pub fn tuple(&mut self, name: &str, args: usize) -> &mut Self {
+        self.variant(name, ModuleVariantKind::Tuple(args))
+    }
+
+    /// Register a struct variant with the given fields, like
+    /// `Shape::Rect { w, h }`.
+    pub fn struct_(&mut self, name: &str, fields: &[&str]) -> &mut Self {
+        let fields = fields.iter().map(|field| (*field).into()).collect();
+        self.variant(name, ModuleVariantKind::Struct(fields))
+    }
+
+    fn variant(&mut self, name: &str, kind: ModuleVariantKind) -> &mut Self {
+        self.variants.push(ModuleVariant {
+            name: name.into(),
+            kind,
+        });
+
+        self
+    }
+}
+
+/// A Rust enum which can be registered as a native enum with
+/// [Module::native_enum].
+///
+/// This is typically implemented with `#[derive(NativeEnum)]`.
+pub trait NativeEnum {
+    /// Register the enum and its variants in the given module.
+    fn install_enum(module: &mut Module) -> Result<(), ContextError>;
+}
+
 pub(crate) struct ModuleType {
     /// The item of the installed type.
     pub(crate) name: Box<str>,
@@ -117,6 +188,7 @@ pub(crate) struct ModuleAssociatedFn {
     pub(crate) args: Option<usize>,
     pub(crate) type_info: TypeInfo,
     pub(crate) name: String,
+    pub(crate) meta: FunctionMeta,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -129,6 +201,101 @@ pub(crate) struct ModuleAssocKey {
 pub(crate) struct ModuleFn {
     pub(crate) handler: Arc<Handler>,
     pub(crate) args: Option<usize>,
+    pub(crate) meta: FunctionMeta,
+}
+
+/// Optional documentation and signature information of a native function.
+///
+/// This is returned when a function is registered in a [Module], and is only
+/// used by tooling like the language server and the documentation generator.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> runestick::Result<()> {
+/// let mut module = runestick::Module::default();
+///
+/// module
+///     .function(&["add"], |a: i64, b: i64| a + b)?
+///     .docs(&["Add two numbers together."])
+///     .arg::<i64>("a")
+///     .arg::<i64>("b")
+///     .returns::<i64>();
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FunctionMeta {
+    /// Lines of documentation.
+    pub docs: Vec<String>,
+    /// Named arguments of the function.
+    ///
+    /// For instance functions this doesn't include `self`.
+    pub args: Vec<FunctionArg>,
+    /// Type information on the returned value, if known.
+    pub return_type: Option<TypeInfo>,
+}
+
+impl FunctionMeta {
+    /// Construct metadata for a function returning a value with the given
+    /// type information.
+    pub(crate) fn returning(return_type: TypeInfo) -> Self {
+        Self {
+            return_type: Some(return_type),
+            ..Self::default()
+        }
+    }
+
+    /// Add lines of documentation to the function.
+    pub fn docs<I>(&mut self, docs: I) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.docs
+            .extend(docs.into_iter().map(|line| line.as_ref().to_owned()));
+        self
+    }
+
+    /// Add an argument with the given name and the type information of `T`.
+    pub fn arg<T>(&mut self, name: &str) -> &mut Self
+    where
+        T: ?Sized + TypeOf,
+    {
+        self.args.push(FunctionArg {
+            name: name.to_owned(),
+            type_info: Some(T::type_info()),
+        });
+        self
+    }
+
+    /// Add an argument with the given name, but without type information.
+    ///
+    /// This is useful for arguments which accept any value.
+    pub fn untyped_arg(&mut self, name: &str) -> &mut Self {
+        self.args.push(FunctionArg {
+            name: name.to_owned(),
+            type_info: None,
+        });
+        self
+    }
+
+    /// Set the type information of the returned value to the one of `T`.
+    pub fn returns<T>(&mut self) -> &mut Self
+    where
+        T: ?Sized + TypeOf,
+    {
+        self.return_type = Some(T::type_info());
+        self
+    }
+}
+
+/// A named argument of a native function.
+#[derive(Debug, Clone)]
+pub struct FunctionArg {
+    /// The name of the argument.
+    pub name: String,
+    /// Type information on the argument, if known.
+    pub type_info: Option<TypeInfo>,
 }
 
 pub(crate) struct ModuleMacro {
@@ -154,6 +321,8 @@ pub struct Module {
     pub(crate) unit_type: Option<ModuleUnitType>,
     /// Registered generator state type.
     pub(crate) internal_enums: Vec<ModuleInternalEnum>,
+    /// Registered native enums.
+    pub(crate) enums: Vec<ModuleEnum>,
 }
 
 impl Module {
@@ -194,6 +363,7 @@ impl Module {
             types: Default::default(),
             unit_type: None,
             internal_enums: Vec::new(),
+            enums: Vec::new(),
             constants: Default::default(),
         }
     }
@@ -261,6 +431,54 @@ impl Module {
         Ok(())
     }
 
+    /// Register a native enum, whose variants are added through the returned
+    /// [ModuleEnum].
+    ///
+    /// Values of the enum are [Variant][crate::Variant] values. These can be
+    /// produced from Rust with `#[derive(ToValue)]` and read back with
+    /// `#[derive(FromValue)]`, as long as the item of the enum matches.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use runestick::Module;
+    ///
+    /// # fn main() -> runestick::Result<()> {
+    /// let mut module = Module::with_item(&["geometry"]);
+    ///
+    /// module
+    ///     .enum_(&["Shape"])
+    ///     .unit("Empty")
+    ///     .tuple("Circle", 1)
+    ///     .struct_("Rect", &["w", "h"]);
+    ///
+    /// let mut context = runestick::Context::new();
+    /// context.install(&module)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn enum_<N>(&mut self, name: N) -> &mut ModuleEnum
+    where
+        N: IntoIterator,
+        N::Item: IntoComponent,
+    {
+        let index = self.enums.len();
+
+        self.enums.push(ModuleEnum {
+            item: Item::with_item(name),
+            variants: Vec::new(),
+        });
+
+        &mut self.enums[index]
+    }
+
+    /// Register a Rust enum implementing [NativeEnum] as a native enum.
+    pub fn native_enum<T>(&mut self) -> Result<(), ContextError>
+    where
+        T: NativeEnum,
+    {
+        T::install_enum(self)
+    }
+
     /// Construct type information for the `unit` type.
     ///
     /// Registering this allows the given type to be used in Rune scripts when
@@ -401,9 +619,13 @@ impl Module {
 
     /// Register a function that cannot error internally.
     ///
+    /// The function can be a closure which captures state.
+    ///
     /// # Examples
     ///
     /// ```rust
+    /// use std::sync::Arc;
+    ///
     /// fn add_ten(value: i64) -> i64 {
     ///     value + 10
     /// }
@@ -412,12 +634,19 @@ impl Module {
     /// let mut module = runestick::Module::default();
     ///
     /// module.function(&["add_ten"], add_ten)?;
+    ///
+    /// let prefix = Arc::new(String::from("hello "));
+    /// module.function(&["greet"], move |name: &str| format!("{}{}", prefix, name))?;
     /// module.function(&["empty"], || Ok::<_, runestick::Error>(()))?;
     /// module.function(&["string"], |a: String| Ok::<_, runestick::Error>(()))?;
     /// module.function(&["optional"], |a: Option<String>| Ok::<_, runestick::Error>(()))?;
     /// # Ok(()) }
     /// ```
-    pub fn function<Func, Args, N>(&mut self, name: N, f: Func) -> Result<(), ContextError>
+    pub fn function<Func, Args, N>(
+        &mut self,
+        name: N,
+        f: Func,
+    ) -> Result<&mut FunctionMeta, ContextError>
     where
         Func: Function<Args>,
         N: IntoIterator,
@@ -429,15 +658,13 @@ impl Module {
             return Err(ContextError::ConflictingFunctionName { name });
         }
 
-        self.functions.insert(
-            name,
-            ModuleFn {
-                handler: Arc::new(move |stack, args| f.fn_call(stack, args)),
-                args: Some(Func::args()),
-            },
-        );
+        let function = self.functions.entry(name).or_insert(ModuleFn {
+            handler: Arc::new(move |stack, args| f.fn_call(stack, args)),
+            args: Some(Func::args()),
+            meta: FunctionMeta::default(),
+        });
 
-        Ok(())
+        Ok(&mut function.meta)
     }
 
     /// Register a constant value, at a crate, module or associated level.
@@ -529,7 +756,11 @@ impl Module {
     /// module.async_function(&["optional"], |a: Option<String>| async { Ok::<_, runestick::Error>(()) })?;
     /// # Ok(()) }
     /// ```
-    pub fn async_function<Func, Args, N>(&mut self, name: N, f: Func) -> Result<(), ContextError>
+    pub fn async_function<Func, Args, N>(
+        &mut self,
+        name: N,
+        f: Func,
+    ) -> Result<&mut FunctionMeta, ContextError>
     where
         Func: AsyncFunction<Args>,
         N: IntoIterator,
@@ -541,22 +772,20 @@ impl Module {
             return Err(ContextError::ConflictingFunctionName { name });
         }
 
-        self.functions.insert(
-            name,
-            ModuleFn {
-                handler: Arc::new(move |stack, args| f.fn_call(stack, args)),
-                args: Some(Func::args()),
-            },
-        );
+        let function = self.functions.entry(name).or_insert(ModuleFn {
+            handler: Arc::new(move |stack, args| f.fn_call(stack, args)),
+            args: Some(Func::args()),
+            meta: FunctionMeta::returning(TypeInfo::StaticType(crate::FUTURE_TYPE)),
+        });
 
-        Ok(())
+        Ok(&mut function.meta)
     }
 
     /// Register a raw function which interacts directly with the virtual
     /// machine.
-    pub fn raw_fn<F, N>(&mut self, name: N, f: F) -> Result<(), ContextError>
+    pub fn raw_fn<F, N>(&mut self, name: N, f: F) -> Result<&mut FunctionMeta, ContextError>
     where
-        F: 'static + Copy + Fn(&mut Stack, usize) -> Result<(), VmError> + Send + Sync,
+        F: 'static + Fn(&mut Stack, usize) -> Result<(), VmError> + Send + Sync,
         N: IntoIterator,
         N::Item: IntoComponent,
     {
@@ -566,15 +795,13 @@ impl Module {
             return Err(ContextError::ConflictingFunctionName { name });
         }
 
-        self.functions.insert(
-            name,
-            ModuleFn {
-                handler: Arc::new(move |stack, args| f(stack, args)),
-                args: None,
-            },
-        );
+        let function = self.functions.entry(name).or_insert(ModuleFn {
+            handler: Arc::new(move |stack, args| f(stack, args)),
+            args: None,
+            meta: FunctionMeta::default(),
+        });
 
-        Ok(())
+        Ok(&mut function.meta)
     }
 
     /// Register an instance function.
@@ -612,7 +839,11 @@ impl Module {
     /// context.install(&module)?;
     /// # Ok(()) }
     /// ```
-    pub fn inst_fn<N, Func, Args>(&mut self, name: N, f: Func) -> Result<(), ContextError>
+    pub fn inst_fn<N, Func, Args>(
+        &mut self,
+        name: N,
+        f: Func,
+    ) -> Result<&mut FunctionMeta, ContextError>
     where
         N: InstFnNameHash,
         Func: InstFn<Args>,
@@ -626,7 +857,7 @@ impl Module {
         protocol: Protocol,
         name: N,
         f: Func,
-    ) -> Result<(), ContextError>
+    ) -> Result<&mut FunctionMeta, ContextError>
     where
         N: InstFnNameHash,
         Func: InstFn<Args>,
@@ -640,7 +871,7 @@ impl Module {
         name: N,
         f: Func,
         kind: ModuleAssociatedKind,
-    ) -> Result<(), ContextError>
+    ) -> Result<&mut FunctionMeta, ContextError>
     where
         N: InstFnNameHash,
         Func: InstFn<Args>,
@@ -667,10 +898,14 @@ impl Module {
             args: Some(Func::args()),
             type_info,
             name,
+            meta: FunctionMeta::default(),
         };
 
-        self.associated_functions.insert(key, instance_function);
-        Ok(())
+        Ok(&mut self
+            .associated_functions
+            .entry(key)
+            .or_insert(instance_function)
+            .meta)
     }
 
     /// Register an instance function.
@@ -700,7 +935,11 @@ impl Module {
     /// module.async_inst_fn("test", MyType::test)?;
     /// # Ok(()) }
     /// ```
-    pub fn async_inst_fn<N, Func, Args>(&mut self, name: N, f: Func) -> Result<(), ContextError>
+    pub fn async_inst_fn<N, Func, Args>(
+        &mut self,
+        name: N,
+        f: Func,
+    ) -> Result<&mut FunctionMeta, ContextError>
     where
         N: InstFnNameHash,
         Func: AsyncInstFn<Args>,
@@ -727,10 +966,14 @@ impl Module {
             args: Some(Func::args()),
             type_info,
             name,
+            meta: FunctionMeta::returning(TypeInfo::StaticType(crate::FUTURE_TYPE)),
         };
 
-        self.associated_functions.insert(key, instance_function);
-        Ok(())
+        Ok(&mut self
+            .associated_functions
+            .entry(key)
+            .or_insert(instance_function)
+            .meta)
     }
 }
 
@@ -765,7 +1008,7 @@ impl<'a> InstFnNameHash for Hash {
 }
 
 /// Trait used to provide the [function][Module::function] function.
-pub trait Function<Args>: 'static + Copy + Send + Sync {
+pub trait Function<Args>: 'static + Send + Sync {
     /// The return type of the function.
     type Return;
 
@@ -773,11 +1016,11 @@ pub trait Function<Args>: 'static + Copy + Send + Sync {
     fn args() -> usize;
 
     /// Perform the vm call.
-    fn fn_call(self, stack: &mut Stack, args: usize) -> Result<(), VmError>;
+    fn fn_call(&self, stack: &mut Stack, args: usize) -> Result<(), VmError>;
 }
 
 /// Trait used to provide the [async_function][Module::async_function] function.
-pub trait AsyncFunction<Args>: 'static + Copy + Send + Sync {
+pub trait AsyncFunction<Args>: 'static + Send + Sync {
     /// The return type of the function.
     type Return;
 
@@ -785,11 +1028,11 @@ pub trait AsyncFunction<Args>: 'static + Copy + Send + Sync {
     fn args() -> usize;
 
     /// Perform the vm call.
-    fn fn_call(self, stack: &mut Stack, args: usize) -> Result<(), VmError>;
+    fn fn_call(&self, stack: &mut Stack, args: usize) -> Result<(), VmError>;
 }
 
 /// Trait used to provide the [inst_fn][Module::inst_fn] function.
-pub trait InstFn<Args>: 'static + Copy + Send + Sync {
+pub trait InstFn<Args>: 'static + Send + Sync {
     /// The type of the instance.
     type Instance;
     /// The return type of the function.
@@ -805,11 +1048,11 @@ pub trait InstFn<Args>: 'static + Copy + Send + Sync {
     fn instance_type_info() -> TypeInfo;
 
     /// Perform the vm call.
-    fn fn_call(self, stack: &mut Stack, args: usize) -> Result<(), VmError>;
+    fn fn_call(&self, stack: &mut Stack, args: usize) -> Result<(), VmError>;
 }
 
 /// Trait used to provide the [async_inst_fn][Module::async_inst_fn] function.
-pub trait AsyncInstFn<Args>: 'static + Copy + Send + Sync {
+pub trait AsyncInstFn<Args>: 'static + Send + Sync {
     /// The type of the instance.
     type Instance;
     /// The return type of the function.
@@ -825,7 +1068,7 @@ pub trait AsyncInstFn<Args>: 'static + Copy + Send + Sync {
     fn instance_type_info() -> TypeInfo;
 
     /// Perform the vm call.
-    fn fn_call(self, stack: &mut Stack, args: usize) -> Result<(), VmError>;
+    fn fn_call(&self, stack: &mut Stack, args: usize) -> Result<(), VmError>;
 }
 
 macro_rules! impl_register {
@@ -841,7 +1084,7 @@ macro_rules! impl_register {
     (@impl $count:expr, $({$ty:ident, $var:ident, $num:expr},)*) => {
         impl<Func, Return, $($ty,)*> Function<($($ty,)*)> for Func
         where
-            Func: 'static + Copy + Send + Sync + Fn($($ty,)*) -> Return,
+            Func: 'static + Send + Sync + Fn($($ty,)*) -> Return,
             Return: ToValue,
             $($ty: UnsafeFromValue,)*
         {
@@ -852,7 +1095,7 @@ macro_rules! impl_register {
             }
 
             fn fn_call(
-                self,
+                &self,
                 stack: &mut Stack,
                 args: usize
             ) -> Result<(), VmError> {
@@ -882,8 +1125,8 @@ macro_rules! impl_register {
 
         impl<Func, Return, $($ty,)*> AsyncFunction<($($ty,)*)> for Func
         where
-            Func: 'static + Copy + Send + Sync + Fn($($ty,)*) -> Return,
-            Return: future::Future,
+            Func: 'static + Send + Sync + Fn($($ty,)*) -> Return,
+            Return: 'static + future::Future,
             Return::Output: ToValue,
             $($ty: 'static + UnsafeFromValue,)*
         {
@@ -894,7 +1137,7 @@ macro_rules! impl_register {
             }
 
             fn fn_call(
-                self,
+                &self,
                 stack: &mut Stack,
                 args: usize
             ) -> Result<(), VmError> {
@@ -913,8 +1156,14 @@ macro_rules! impl_register {
                 let ret = unsafe {
                     impl_register!{@unsafe-vars $count, $($ty, $var, $num,)*}
 
+                    let future = self($(<$ty>::unsafe_coerce($var.0),)*);
+                    // NB: the guards of the arguments must live for as long as
+                    // the future does.
+                    let guards = ($($var.1,)*);
+
                     Future::new(async move {
-                        let output = self($(<$ty>::unsafe_coerce($var.0),)*).await;
+                        let _guards = guards;
+                        let output = future.await;
                         let value = output.to_value()?;
                         Ok(value)
                     })
@@ -927,7 +1176,7 @@ macro_rules! impl_register {
 
         impl<Func, Return, Instance, $($ty,)*> InstFn<(Instance, $($ty,)*)> for Func
         where
-            Func: 'static + Copy + Send + Sync + Fn(Instance $(, $ty)*) -> Return,
+            Func: 'static + Send + Sync + Fn(Instance $(, $ty)*) -> Return,
             Return: ToValue,
             Instance: UnsafeFromValue + TypeOf,
             $($ty: UnsafeFromValue,)*
@@ -947,7 +1196,7 @@ macro_rules! impl_register {
                 Instance::type_info()
             }
 
-            fn fn_call(self, stack: &mut Stack, args: usize) -> Result<(), VmError> {
+            fn fn_call(&self, stack: &mut Stack, args: usize) -> Result<(), VmError> {
                 impl_register!{@check-args ($count + 1), args}
 
                 #[allow(unused_mut)]
@@ -974,8 +1223,8 @@ macro_rules! impl_register {
 
         impl<Func, Return, Instance, $($ty,)*> AsyncInstFn<(Instance, $($ty,)*)> for Func
         where
-            Func: 'static + Copy + Send + Sync + Fn(Instance $(, $ty)*) -> Return,
-            Return: future::Future,
+            Func: 'static + Send + Sync + Fn(Instance $(, $ty)*) -> Return,
+            Return: 'static + future::Future,
             Return::Output: ToValue,
             Instance: UnsafeFromValue + TypeOf,
             $($ty: UnsafeFromValue,)*
@@ -995,7 +1244,7 @@ macro_rules! impl_register {
                 Instance::type_info()
             }
 
-            fn fn_call(self, stack: &mut Stack, args: usize) -> Result<(), VmError> {
+            fn fn_call(&self, stack: &mut Stack, args: usize) -> Result<(), VmError> {
                 impl_register!{@check-args ($count + 1), args}
 
                 #[allow(unused_mut)]
@@ -1012,8 +1261,14 @@ macro_rules! impl_register {
                 let ret = unsafe {
                     impl_register!{@unsafe-inst-vars inst, $count, $($ty, $var, $num,)*}
 
+                    let future = self(Instance::unsafe_coerce(inst.0), $(<$ty>::unsafe_coerce($var.0),)*);
+                    // NB: the guards of the arguments must live for as long as
+                    // the future does.
+                    let guards = (inst.1, $($var.1,)*);
+
                     Future::new(async move {
-                        let output = self(Instance::unsafe_coerce(inst.0), $(<$ty>::unsafe_coerce($var.0),)*).await;
+                        let _guards = guards;
+                        let output = future.await;
                         let value = output.to_value()?;
                         Ok(value)
                     })
@@ -13,9 +13,23 @@ pub fn module() -> Result<Module, ContextError> {
     module.ty::<f64>()?;
     module.ty::<i64>()?;
 
-    module.function(&["panic"], panic_impl)?;
-    module.function(&["is_readable"], is_readable)?;
-    module.function(&["is_writable"], is_writable)?;
+    module
+        .function(&["panic"], panic_impl)?
+        .docs(&["Cause a virtual machine panic with the given message."])
+        .arg::<str>("message");
+
+    module
+        .function(&["is_readable"], is_readable)?
+        .docs(&["Test if the given value can be borrowed for reading."])
+        .untyped_arg("value")
+        .returns::<bool>();
+
+    module
+        .function(&["is_writable"], is_writable)?
+        .docs(&["Test if the given value can be borrowed for writing."])
+        .untyped_arg("value")
+        .returns::<bool>();
+
     Ok(module)
 }
 
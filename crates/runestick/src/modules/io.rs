@@ -1,12 +1,36 @@
 //! The `std::io` module.
+//!
+//! By default the `print`, `println`, `eprint`, `eprintln` and `dbg`
+//! functions write to the stdout and stderr of the current process. To route
+//! script output somewhere else, construct a context without stdio and install
+//! an [output_module] with a custom [Output]:
+//!
+//! ```rust
+//! use runestick::modules::io::{self, CaptureOutput};
+//!
+//! # fn main() -> runestick::Result<()> {
+//! let output = CaptureOutput::new();
+//!
+//! let mut context = runestick::Context::with_config(false)?;
+//! context.install(&io::output_module(output.clone())?)?;
+//!
+//! // after running a script:
+//! let stdout = output.drain_stdout();
+//! # Ok(())
+//! # }
+//! ```
 
 use crate::{ContextError, Module, Panic, Protocol, Stack, Value, VmError};
 use std::fmt;
 use std::fmt::Write as _;
 use std::io;
 use std::io::Write as _;
+use std::sync::{Arc, Mutex};
 
 /// Construct the `std::io` module.
+///
+/// If `stdio` is `true`, the functions printing to stdout and stderr are
+/// installed as well.
 pub fn module(stdio: bool) -> Result<Module, ContextError> {
     let mut module = Module::with_crate_item("std", &["io"]);
 
@@ -14,38 +38,177 @@ pub fn module(stdio: bool) -> Result<Module, ContextError> {
     module.inst_fn(Protocol::STRING_DISPLAY, format_io_error)?;
 
     if stdio {
-        module.function(&["print"], print_impl)?;
-        module.function(&["println"], println_impl)?;
-        module.raw_fn(&["dbg"], dbg_impl)?;
+        install_output(&mut module, Arc::new(StdOutput))?;
     }
 
     Ok(module)
 }
 
+/// Construct a module with the `print`, `println`, `eprint`, `eprintln` and
+/// `dbg` functions of `std::io`, which write to the given output.
+///
+/// This is intended to be installed in a context constructed without stdio,
+/// like [Context::with_config(false)][crate::Context::with_config].
+pub fn output_module<O>(output: O) -> Result<Module, ContextError>
+where
+    O: Output,
+{
+    let mut module = Module::with_crate_item("std", &["io"]);
+    install_output(&mut module, Arc::new(output))?;
+    Ok(module)
+}
+
+/// The stream that output is written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stream {
+    /// Standard output, written to by `print`, `println` and `dbg`.
+    Stdout,
+    /// Standard error, written to by `eprint` and `eprintln`.
+    Stderr,
+}
+
+/// A sink for output written by scripts.
+///
+/// This is implemented for closures, so that output can easily be routed to
+/// something like a log:
+///
+/// ```rust
+/// use runestick::modules::io::{self, Stream};
+///
+/// # fn main() -> runestick::Result<()> {
+/// let module = io::output_module(|stream: Stream, bytes: &[u8]| {
+///     log::info!("{:?}: {}", stream, String::from_utf8_lossy(bytes));
+///     Ok(())
+/// })?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait Output: 'static + Send + Sync {
+    /// Write the given bytes to the given stream.
+    ///
+    /// Each call corresponds to a single call to an output function, so for
+    /// example `println` writes its argument together with the trailing
+    /// newline.
+    fn write(&self, stream: Stream, bytes: &[u8]) -> io::Result<()>;
+}
+
+impl<F> Output for F
+where
+    F: 'static + Send + Sync + Fn(Stream, &[u8]) -> io::Result<()>,
+{
+    fn write(&self, stream: Stream, bytes: &[u8]) -> io::Result<()> {
+        self(stream, bytes)
+    }
+}
+
+/// Output which is written to the stdout and stderr of the current process.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdOutput;
+
+impl Output for StdOutput {
+    fn write(&self, stream: Stream, bytes: &[u8]) -> io::Result<()> {
+        match stream {
+            Stream::Stdout => io::stdout().lock().write_all(bytes),
+            Stream::Stderr => io::stderr().lock().write_all(bytes),
+        }
+    }
+}
+
+/// Output which is captured into buffers, one for each stream.
+///
+/// Clones of it share the same buffers.
+#[derive(Debug, Clone, Default)]
+pub struct CaptureOutput {
+    stdout: Arc<Mutex<Vec<u8>>>,
+    stderr: Arc<Mutex<Vec<u8>>>,
+}
+
+impl CaptureOutput {
+    /// Construct new empty captured output.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take everything written to stdout so far.
+    pub fn drain_stdout(&self) -> Vec<u8> {
+        std::mem::take(&mut *lock(&self.stdout))
+    }
+
+    /// Take everything written to stderr so far.
+    pub fn drain_stderr(&self) -> Vec<u8> {
+        std::mem::take(&mut *lock(&self.stderr))
+    }
+}
+
+impl Output for CaptureOutput {
+    fn write(&self, stream: Stream, bytes: &[u8]) -> io::Result<()> {
+        let buf = match stream {
+            Stream::Stdout => &self.stdout,
+            Stream::Stderr => &self.stderr,
+        };
+
+        lock(buf).extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+/// Lock the given buffer, ignoring poisoning since a buffer of bytes can't be
+/// left in an inconsistent state.
+fn lock(buf: &Mutex<Vec<u8>>) -> std::sync::MutexGuard<'_, Vec<u8>> {
+    buf.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Install the output functions into the given module.
+fn install_output(module: &mut Module, output: Arc<dyn Output>) -> Result<(), ContextError> {
+    module.function(&["print"], {
+        let output = output.clone();
+        move |m: &str| write(&*output, Stream::Stdout, m, false)
+    })?;
+
+    module.function(&["println"], {
+        let output = output.clone();
+        move |m: &str| write(&*output, Stream::Stdout, m, true)
+    })?;
+
+    module.function(&["eprint"], {
+        let output = output.clone();
+        move |m: &str| write(&*output, Stream::Stderr, m, false)
+    })?;
+
+    module.function(&["eprintln"], {
+        let output = output.clone();
+        move |m: &str| write(&*output, Stream::Stderr, m, true)
+    })?;
+
+    module.raw_fn(&["dbg"], move |stack, args| dbg_impl(&*output, stack, args))?;
+    Ok(())
+}
+
 fn format_io_error(error: &std::io::Error, buf: &mut String) -> fmt::Result {
     write!(buf, "{}", error)
 }
 
-fn dbg_impl(stack: &mut Stack, args: usize) -> Result<(), VmError> {
-    let stdout = io::stdout();
-    let mut stdout = stdout.lock();
+fn dbg_impl(output: &dyn Output, stack: &mut Stack, args: usize) -> Result<(), VmError> {
+    let mut buf = String::new();
 
     for value in stack.drain_stack_top(args)? {
-        writeln!(stdout, "{:?}", value).map_err(VmError::panic)?;
+        writeln!(buf, "{:?}", value).map_err(VmError::panic)?;
     }
 
+    output
+        .write(Stream::Stdout, buf.as_bytes())
+        .map_err(VmError::panic)?;
+
     stack.push(Value::Unit);
     Ok(())
 }
 
-fn print_impl(m: &str) -> Result<(), Panic> {
-    let stdout = io::stdout();
-    let mut stdout = stdout.lock();
-    write!(stdout, "{}", m).map_err(Panic::custom)
-}
+fn write(output: &dyn Output, stream: Stream, m: &str, newline: bool) -> Result<(), Panic> {
+    let result = if newline {
+        output.write(stream, format!("{}\n", m).as_bytes())
+    } else {
+        output.write(stream, m.as_bytes())
+    };
 
-fn println_impl(m: &str) -> Result<(), Panic> {
-    let stdout = io::stdout();
-    let mut stdout = stdout.lock();
-    writeln!(stdout, "{}", m).map_err(Panic::custom)
+    result.map_err(Panic::custom)
 }
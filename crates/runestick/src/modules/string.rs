@@ -7,8 +7,11 @@ pub fn module() -> Result<Module, ContextError> {
     let mut module = Module::with_crate_item("std", &["string"]);
 
     module.ty::<String>()?;
+    module.ty::<NotCharBoundary>()?;
+    module.ty::<FromUtf8Error>()?;
 
     module.function(&["String", "from_str"], <String as From<&str>>::from)?;
+    module.function(&["String", "from_utf8"], from_utf8)?;
     module.function(&["String", "new"], String::new)?;
     module.function(&["String", "with_capacity"], String::with_capacity)?;
 
@@ -28,12 +31,29 @@ pub fn module() -> Result<Module, ContextError> {
     module.inst_fn("char_at", char_at)?;
     module.inst_fn("split", string_split)?;
     module.inst_fn("trim", string_trim)?;
+    module.inst_fn("trim_start", string_trim_start)?;
     module.inst_fn("trim_end", string_trim_end)?;
+    module.inst_fn("contains", string_contains)?;
+    module.inst_fn("find", string_find)?;
+    module.inst_fn("rfind", string_rfind)?;
+    module.inst_fn("strip_prefix", string_strip_prefix)?;
+    module.inst_fn("strip_suffix", string_strip_suffix)?;
+    module.inst_fn("splitn", string_splitn)?;
+    module.inst_fn("split_whitespace", string_split_whitespace)?;
+    module.inst_fn("lines", string_lines)?;
+    module.inst_fn("to_lowercase", str::to_lowercase)?;
+    module.inst_fn("to_uppercase", str::to_uppercase)?;
+    module.inst_fn("repeat", string_repeat)?;
+    module.inst_fn("pad_start", string_pad_start)?;
+    module.inst_fn("pad_end", string_pad_end)?;
+    module.inst_fn("center", string_center)?;
     module.inst_fn("replace", str::replace::<&str>)?;
     // TODO: deprecate this variant.
     module.inst_fn("split_str", string_split)?;
     module.inst_fn("is_empty", str::is_empty)?;
     module.inst_fn("chars", string_chars)?;
+    module.inst_fn("char_indices", string_char_indices)?;
+    module.inst_fn("bytes", string_bytes)?;
     module.inst_fn(Protocol::ADD, add)?;
     module.inst_fn(Protocol::ADD_ASSIGN, String::push_str)?;
     module.inst_fn(Protocol::INDEX_GET, string_index_get)?;
@@ -62,6 +82,56 @@ impl NotCharBoundary {
     }
 }
 
+/// The error raised when constructing a string from bytes which are not valid
+/// UTF-8.
+#[derive(Any, Debug, Clone)]
+#[rune(module = "crate", install_with = "FromUtf8Error::install")]
+struct FromUtf8Error {
+    error: std::str::Utf8Error,
+}
+
+impl FromUtf8Error {
+    fn string_display(&self, s: &mut String) -> std::fmt::Result {
+        use std::fmt::Write as _;
+        write!(s, "{}", self.error)
+    }
+
+    fn valid_up_to(&self) -> usize {
+        self.error.valid_up_to()
+    }
+
+    fn install(m: &mut Module) -> Result<(), ContextError> {
+        m.inst_fn(crate::Protocol::STRING_DISPLAY, Self::string_display)?;
+        m.inst_fn("valid_up_to", Self::valid_up_to)?;
+        Ok(())
+    }
+}
+
+/// A string or character pattern used when searching strings.
+enum Pattern {
+    String(String),
+    Char(char),
+}
+
+impl Pattern {
+    /// Convert the argument at the given position into a pattern.
+    fn from_value(arg: usize, value: Value) -> Result<Self, VmError> {
+        Ok(match value {
+            Value::String(s) => Self::String(s.borrow_ref()?.clone()),
+            Value::StaticString(s) => Self::String(s.as_str().to_owned()),
+            Value::Char(c) => Self::Char(c),
+            value => return Err(VmError::bad_argument::<String>(arg, &value)?),
+        })
+    }
+}
+
+/// Construct a string from UTF-8 encoded bytes.
+fn from_utf8(bytes: Bytes) -> Result<String, FromUtf8Error> {
+    String::from_utf8(bytes.into_vec()).map_err(|error| FromUtf8Error {
+        error: error.utf8_error(),
+    })
+}
+
 /// into_bytes shim for strings.
 fn into_bytes(s: String) -> Bytes {
     Bytes::from_vec(s.into_bytes())
@@ -86,7 +156,7 @@ fn string_split(this: &str, value: Value) -> Result<Iterator, VmError> {
             .map(String::from)
             .collect::<Vec<String>>(),
         Value::Char(pat) => this.split(pat).map(String::from).collect::<Vec<String>>(),
-        value => return Err(VmError::bad_argument::<String>(0, &value)?),
+        value => return Err(VmError::bad_argument::<String>(1, &value)?),
     };
 
     Ok(Iterator::from_double_ended(
@@ -99,10 +169,150 @@ fn string_trim(this: &str) -> String {
     this.trim().to_owned()
 }
 
+fn string_trim_start(this: &str) -> String {
+    this.trim_start().to_owned()
+}
+
 fn string_trim_end(this: &str) -> String {
     this.trim_end().to_owned()
 }
 
+fn string_contains(this: &str, pattern: Value) -> Result<bool, VmError> {
+    Ok(match Pattern::from_value(1, pattern)? {
+        Pattern::String(s) => this.contains(s.as_str()),
+        Pattern::Char(c) => this.contains(c),
+    })
+}
+
+/// Find the byte index of the first match of the pattern.
+fn string_find(this: &str, pattern: Value) -> Result<Option<usize>, VmError> {
+    Ok(match Pattern::from_value(1, pattern)? {
+        Pattern::String(s) => this.find(s.as_str()),
+        Pattern::Char(c) => this.find(c),
+    })
+}
+
+/// Find the byte index of the last match of the pattern.
+fn string_rfind(this: &str, pattern: Value) -> Result<Option<usize>, VmError> {
+    Ok(match Pattern::from_value(1, pattern)? {
+        Pattern::String(s) => this.rfind(s.as_str()),
+        Pattern::Char(c) => this.rfind(c),
+    })
+}
+
+fn string_strip_prefix(this: &str, pattern: Value) -> Result<Option<String>, VmError> {
+    let out = match Pattern::from_value(1, pattern)? {
+        Pattern::String(s) => this.strip_prefix(s.as_str()),
+        Pattern::Char(c) => this.strip_prefix(c),
+    };
+
+    Ok(out.map(String::from))
+}
+
+fn string_strip_suffix(this: &str, pattern: Value) -> Result<Option<String>, VmError> {
+    let out = match Pattern::from_value(1, pattern)? {
+        Pattern::String(s) => this.strip_suffix(s.as_str()),
+        Pattern::Char(c) => this.strip_suffix(c),
+    };
+
+    Ok(out.map(String::from))
+}
+
+/// Split the string by the pattern into at most `n` parts.
+fn string_splitn(this: &str, n: usize, pattern: Value) -> Result<Iterator, VmError> {
+    let parts = match Pattern::from_value(2, pattern)? {
+        Pattern::String(s) => this
+            .splitn(n, s.as_str())
+            .map(String::from)
+            .collect::<Vec<String>>(),
+        Pattern::Char(c) => this.splitn(n, c).map(String::from).collect::<Vec<String>>(),
+    };
+
+    Ok(Iterator::from("std::str::SplitN", parts.into_iter()))
+}
+
+fn string_split_whitespace(this: &str) -> Iterator {
+    let parts = this
+        .split_whitespace()
+        .map(String::from)
+        .collect::<Vec<String>>();
+
+    Iterator::from_double_ended("std::str::SplitWhitespace", parts.into_iter())
+}
+
+fn string_lines(this: &str) -> Iterator {
+    let lines = this.lines().map(String::from).collect::<Vec<String>>();
+    Iterator::from_double_ended("std::str::Lines", lines.into_iter())
+}
+
+/// Allocate a string with capacity for `len` bytes, which is `None` if
+/// computing it overflowed.
+///
+/// Errors instead of panicking if the string can't be allocated.
+fn string_with_capacity(len: Option<usize>) -> Result<String, VmError> {
+    let len = len.ok_or(VmErrorKind::Overflow)?;
+    let mut string = String::new();
+    string.try_reserve(len).map_err(VmError::panic)?;
+    Ok(string)
+}
+
+/// The length in bytes of `this` padded with `padding` number of `fill`
+/// characters.
+fn padded_len(this: &str, padding: usize, fill: char) -> Option<usize> {
+    padding
+        .checked_mul(fill.len_utf8())?
+        .checked_add(this.len())
+}
+
+fn push_fill(string: &mut String, fill: char, count: usize) {
+    for _ in 0..count {
+        string.push(fill);
+    }
+}
+
+/// Repeat the string `count` times.
+fn string_repeat(this: &str, count: usize) -> Result<String, VmError> {
+    let mut string = string_with_capacity(this.len().checked_mul(count))?;
+
+    for _ in 0..count {
+        string.push_str(this);
+    }
+
+    Ok(string)
+}
+
+/// Pad the start of the string with `fill` until it's `width` characters
+/// long.
+fn string_pad_start(this: &str, width: usize, fill: char) -> Result<String, VmError> {
+    let padding = width.saturating_sub(this.chars().count());
+    let mut string = string_with_capacity(padded_len(this, padding, fill))?;
+    push_fill(&mut string, fill, padding);
+    string.push_str(this);
+    Ok(string)
+}
+
+/// Pad the end of the string with `fill` until it's `width` characters long.
+fn string_pad_end(this: &str, width: usize, fill: char) -> Result<String, VmError> {
+    let padding = width.saturating_sub(this.chars().count());
+    let mut string = string_with_capacity(padded_len(this, padding, fill))?;
+    string.push_str(this);
+    push_fill(&mut string, fill, padding);
+    Ok(string)
+}
+
+/// Pad both sides of the string with `fill` until it's `width` characters
+/// long. If the padding can't be evenly distributed, the extra character is
+/// added to the end.
+fn string_center(this: &str, width: usize, fill: char) -> Result<String, VmError> {
+    let padding = width.saturating_sub(this.chars().count());
+    let start = padding / 2;
+    let mut string = string_with_capacity(padded_len(this, padding, fill))?;
+    push_fill(&mut string, fill, start);
+    string.push_str(this);
+    push_fill(&mut string, fill, padding - start);
+    Ok(string)
+}
+
 fn parse_int(s: &str) -> Result<i64, std::num::ParseIntError> {
     str::parse::<i64>(s)
 }
@@ -124,6 +334,17 @@ fn string_chars(s: &str) -> Iterator {
     Iterator::from_double_ended("std::str::Chars", iter)
 }
 
+/// Iterate over the characters of the string and their byte indexes.
+fn string_char_indices(s: &str) -> Iterator {
+    let iter = s.char_indices().collect::<Vec<_>>().into_iter();
+    Iterator::from_double_ended("std::str::CharIndices", iter)
+}
+
+fn string_bytes(s: &str) -> Iterator {
+    let iter = s.bytes().collect::<Vec<_>>().into_iter();
+    Iterator::from_double_ended("std::str::Bytes", iter)
+}
+
 /// Get a specific string index.
 fn string_get(s: &str, key: Value) -> Result<Option<String>, VmError> {
     use crate::{FromValue as _, RangeLimits, TypeOf as _};
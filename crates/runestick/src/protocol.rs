@@ -67,6 +67,14 @@ impl Protocol {
         hash: Hash::new(0x418f5becbf885806),
     };
 
+    /// Compare two types, used by the `<`, `<=`, `>` and `>=` operators.
+    ///
+    /// The function is expected to return a `std::cmp::Ordering`.
+    pub const CMP: Protocol = Protocol {
+        name: "cmp",
+        hash: Hash::new(0x4a4ef1f2e0b8a9c7),
+    };
+
     /// The function to access a field.
     pub const GET: Protocol = Protocol {
         name: "get",
@@ -0,0 +1,341 @@
+use crate::{
+    Bytes, FromValue, Hash, Object, Range, RangeLimits, Rtti, Shared, StaticString, Struct,
+    ToValue, Tuple, TypeInfo, Value, Variant, VariantData, VariantRtti, Vec, VmError, VmErrorKind,
+};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::vec;
+
+/// A thread-safe snapshot of a [Value].
+///
+/// A [Value] can't be sent across threads since its containers are shared
+/// through [Shared]. A `SendValue` is a deep copy of a value, which owns all of
+/// its data and can be used to move script data between virtual machines
+/// running on different threads.
+///
+/// Conversion from a [Value] is done through [FromValue], which borrows the
+/// value so that the original is left intact. Values which hold on to
+/// execution state or native data, like futures, generators, iterators,
+/// functions and external types, can't be converted. Neither can values which
+/// contain themselves, since they have no finite copy.
+///
+/// # Examples
+///
+/// ```rust
+/// use runestick::{FromValue as _, SendValue, Value};
+///
+/// # fn main() -> runestick::Result<()> {
+/// let value = Value::vec(vec![Value::Integer(1), Value::Integer(2)]);
+/// let send = SendValue::from_value(value)?;
+///
+/// let value = std::thread::spawn(move || send).join().unwrap().into_value();
+/// let value: Vec<i64> = runestick::FromValue::from_value(value)?;
+/// assert_eq!(value, vec![1, 2]);
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone)]
+pub enum SendValue {
+    /// The unit value.
+    Unit,
+    /// A boolean.
+    Bool(bool),
+    /// A single byte.
+    Byte(u8),
+    /// A character.
+    Char(char),
+    /// A number.
+    Integer(i64),
+    /// A float.
+    Float(f64),
+    /// A type hash.
+    Type(Hash),
+    /// A static string.
+    StaticString(Arc<StaticString>),
+    /// A UTF-8 string.
+    String(String),
+    /// A byte string.
+    Bytes(Bytes),
+    /// A vector.
+    Vec(vec::Vec<SendValue>),
+    /// A tuple.
+    Tuple(Box<[SendValue]>),
+    /// An object.
+    Object(BTreeMap<String, SendValue>),
+    /// A range.
+    Range(Option<Box<SendValue>>, Option<Box<SendValue>>, RangeLimits),
+    /// An option.
+    Option(Option<Box<SendValue>>),
+    /// A result.
+    Result(Result<Box<SendValue>, Box<SendValue>>),
+    /// A unit struct.
+    UnitStruct(Arc<Rtti>),
+    /// A tuple struct.
+    TupleStruct(Arc<Rtti>, Box<[SendValue]>),
+    /// A struct.
+    Struct(Arc<Rtti>, BTreeMap<String, SendValue>),
+    /// A unit variant.
+    UnitVariant(Arc<VariantRtti>),
+    /// A tuple variant.
+    TupleVariant(Arc<VariantRtti>, Box<[SendValue]>),
+    /// A struct variant.
+    StructVariant(Arc<VariantRtti>, BTreeMap<String, SendValue>),
+}
+
+impl SendValue {
+    /// Convert into a virtual machine value.
+    ///
+    /// This is infallible, since every snapshot corresponds to a valid value.
+    pub fn into_value(self) -> Value {
+        match self {
+            Self::Unit => Value::Unit,
+            Self::Bool(b) => Value::Bool(b),
+            Self::Byte(b) => Value::Byte(b),
+            Self::Char(c) => Value::Char(c),
+            Self::Integer(n) => Value::Integer(n),
+            Self::Float(n) => Value::Float(n),
+            Self::Type(hash) => Value::Type(hash),
+            Self::StaticString(s) => Value::StaticString(s),
+            Self::String(s) => Value::from(s),
+            Self::Bytes(b) => Value::from(b),
+            Self::Vec(vec) => Value::from(Vec::from(into_values(vec))),
+            Self::Tuple(tuple) => Value::from(Tuple::from(into_values(tuple.into_vec()))),
+            Self::Object(object) => Value::from(into_object(object)),
+            Self::Range(start, end, limits) => Value::from(Range::new(
+                start.map(|value| value.into_value()),
+                end.map(|value| value.into_value()),
+                limits,
+            )),
+            Self::Option(option) => {
+                Value::Option(Shared::new(option.map(|value| value.into_value())))
+            }
+            Self::Result(result) => Value::Result(Shared::new(match result {
+                Ok(value) => Ok(value.into_value()),
+                Err(value) => Err(value.into_value()),
+            })),
+            Self::UnitStruct(rtti) => Value::unit_struct(rtti),
+            Self::TupleStruct(rtti, tuple) => {
+                Value::tuple_struct(rtti, into_values(tuple.into_vec()))
+            }
+            Self::Struct(rtti, object) => Value::from(Struct {
+                rtti,
+                data: into_object(object),
+            }),
+            Self::UnitVariant(rtti) => Value::from(Variant::unit(rtti)),
+            Self::TupleVariant(rtti, tuple) => Value::from(Variant::tuple(
+                rtti,
+                Tuple::from(into_values(tuple.into_vec())),
+            )),
+            Self::StructVariant(rtti, object) => {
+                Value::from(Variant::struct_(rtti, into_object(object)))
+            }
+        }
+    }
+
+    /// Get the type information of the value.
+    pub fn type_info(&self) -> TypeInfo {
+        match self {
+            Self::Unit => TypeInfo::StaticType(crate::UNIT_TYPE),
+            Self::Bool(..) => TypeInfo::StaticType(crate::BOOL_TYPE),
+            Self::Byte(..) => TypeInfo::StaticType(crate::BYTE_TYPE),
+            Self::Char(..) => TypeInfo::StaticType(crate::CHAR_TYPE),
+            Self::Integer(..) => TypeInfo::StaticType(crate::INTEGER_TYPE),
+            Self::Float(..) => TypeInfo::StaticType(crate::FLOAT_TYPE),
+            Self::Type(..) => TypeInfo::StaticType(crate::TYPE),
+            Self::StaticString(..) => TypeInfo::StaticType(crate::STRING_TYPE),
+            Self::String(..) => TypeInfo::StaticType(crate::STRING_TYPE),
+            Self::Bytes(..) => TypeInfo::StaticType(crate::BYTES_TYPE),
+            Self::Vec(..) => TypeInfo::StaticType(crate::VEC_TYPE),
+            Self::Tuple(..) => TypeInfo::StaticType(crate::TUPLE_TYPE),
+            Self::Object(..) => TypeInfo::StaticType(crate::OBJECT_TYPE),
+            Self::Range(..) => TypeInfo::StaticType(crate::RANGE_TYPE),
+            Self::Option(..) => TypeInfo::StaticType(crate::OPTION_TYPE),
+            Self::Result(..) => TypeInfo::StaticType(crate::RESULT_TYPE),
+            Self::UnitStruct(rtti) => TypeInfo::Typed(rtti.clone()),
+            Self::TupleStruct(rtti, ..) => TypeInfo::Typed(rtti.clone()),
+            Self::Struct(rtti, ..) => TypeInfo::Typed(rtti.clone()),
+            Self::UnitVariant(rtti) => TypeInfo::Variant(rtti.clone()),
+            Self::TupleVariant(rtti, ..) => TypeInfo::Variant(rtti.clone()),
+            Self::StructVariant(rtti, ..) => TypeInfo::Variant(rtti.clone()),
+        }
+    }
+}
+
+impl FromValue for SendValue {
+    fn from_value(value: Value) -> Result<Self, VmError> {
+        from_value(&value, &mut vec::Vec::new())
+    }
+}
+
+/// Convert a value, where `path` holds the containers which are currently
+/// being converted so that a value which contains itself can be detected.
+fn from_value(value: &Value, path: &mut vec::Vec<*const ()>) -> Result<SendValue, VmError> {
+    Ok(match value {
+        Value::Unit => SendValue::Unit,
+        Value::Bool(b) => SendValue::Bool(*b),
+        Value::Byte(b) => SendValue::Byte(*b),
+        Value::Char(c) => SendValue::Char(*c),
+        Value::Integer(n) => SendValue::Integer(*n),
+        Value::Float(n) => SendValue::Float(*n),
+        Value::Type(hash) => SendValue::Type(*hash),
+        Value::StaticString(s) => SendValue::StaticString(s.clone()),
+        Value::String(s) => SendValue::String(s.borrow_ref()?.clone()),
+        Value::Bytes(b) => SendValue::Bytes(b.borrow_ref()?.clone()),
+        Value::Vec(vec) => enter(vec, path, |vec, path| {
+            Ok(SendValue::Vec(from_values(vec.iter(), path)?))
+        })?,
+        Value::Tuple(tuple) => enter(tuple, path, |tuple, path| {
+            Ok(SendValue::Tuple(
+                from_values(tuple.iter(), path)?.into_boxed_slice(),
+            ))
+        })?,
+        Value::Object(object) => enter(object, path, |object, path| {
+            Ok(SendValue::Object(from_object(object, path)?))
+        })?,
+        Value::Range(range) => enter(range, path, |range, path| {
+            Ok(SendValue::Range(
+                from_option(range.start.as_ref(), path)?,
+                from_option(range.end.as_ref(), path)?,
+                range.limits,
+            ))
+        })?,
+        Value::Option(option) => enter(option, path, |option, path| {
+            Ok(SendValue::Option(from_option(option.as_ref(), path)?))
+        })?,
+        Value::Result(result) => enter(result, path, |result, path| {
+            Ok(SendValue::Result(match result {
+                Ok(value) => Ok(Box::new(from_value(value, path)?)),
+                Err(value) => Err(Box::new(from_value(value, path)?)),
+            }))
+        })?,
+        Value::UnitStruct(empty) => SendValue::UnitStruct(empty.borrow_ref()?.rtti.clone()),
+        Value::TupleStruct(tuple) => enter(tuple, path, |tuple, path| {
+            let data = from_values(tuple.data.iter(), path)?.into_boxed_slice();
+            Ok(SendValue::TupleStruct(tuple.rtti.clone(), data))
+        })?,
+        Value::Struct(object) => enter(object, path, |object, path| {
+            Ok(SendValue::Struct(
+                object.rtti.clone(),
+                from_object(&object.data, path)?,
+            ))
+        })?,
+        Value::Variant(variant) => enter(variant, path, |variant, path| {
+            let rtti = variant.rtti.clone();
+
+            Ok(match &variant.data {
+                VariantData::Unit => SendValue::UnitVariant(rtti),
+                VariantData::Tuple(tuple) => SendValue::TupleVariant(
+                    rtti,
+                    from_values(tuple.iter(), path)?.into_boxed_slice(),
+                ),
+                VariantData::Struct(object) => {
+                    SendValue::StructVariant(rtti, from_object(object, path)?)
+                }
+            })
+        })?,
+        value => {
+            return Err(VmError::from(VmErrorKind::SendNotSupported {
+                actual: value.type_info()?,
+            }))
+        }
+    })
+}
+
+/// Convert the content of a shared container, erroring if the container is
+/// already being converted further up.
+fn enter<T, O, F>(shared: &Shared<T>, path: &mut vec::Vec<*const ()>, f: F) -> Result<O, VmError>
+where
+    F: FnOnce(&T, &mut vec::Vec<*const ()>) -> Result<O, VmError>,
+{
+    let ptr = shared.as_ptr();
+
+    if path.contains(&ptr) {
+        return Err(VmError::from(VmErrorKind::SendCycle));
+    }
+
+    path.push(ptr);
+    let output = f(&*shared.borrow_ref()?, path);
+    path.pop();
+    output
+}
+
+impl ToValue for SendValue {
+    fn to_value(self) -> Result<Value, VmError> {
+        Ok(SendValue::into_value(self))
+    }
+}
+
+fn from_values<'a, I>(
+    values: I,
+    path: &mut vec::Vec<*const ()>,
+) -> Result<vec::Vec<SendValue>, VmError>
+where
+    I: IntoIterator<Item = &'a Value>,
+{
+    let mut output = vec::Vec::new();
+
+    for value in values {
+        output.push(from_value(value, path)?);
+    }
+
+    Ok(output)
+}
+
+fn from_object(
+    object: &Object,
+    path: &mut vec::Vec<*const ()>,
+) -> Result<BTreeMap<String, SendValue>, VmError> {
+    let mut output = BTreeMap::new();
+
+    for (key, value) in object {
+        output.insert(key.clone(), from_value(value, path)?);
+    }
+
+    Ok(output)
+}
+
+fn from_option(
+    value: Option<&Value>,
+    path: &mut vec::Vec<*const ()>,
+) -> Result<Option<Box<SendValue>>, VmError> {
+    Ok(match value {
+        Some(value) => Some(Box::new(from_value(value, path)?)),
+        None => None,
+    })
+}
+
+fn into_values(values: vec::Vec<SendValue>) -> vec::Vec<Value> {
+    values.into_iter().map(SendValue::into_value).collect()
+}
+
+fn into_object(object: BTreeMap<String, SendValue>) -> Object {
+    let mut output = Object::with_capacity(object.len());
+
+    for (key, value) in object {
+        output.insert(key, value.into_value());
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SendValue;
+
+    fn assert_send<T>()
+    where
+        T: Send,
+    {
+    }
+
+    fn assert_sync<T>()
+    where
+        T: Sync,
+    {
+    }
+
+    #[test]
+    fn assert_send_sync() {
+        assert_send::<SendValue>();
+        assert_sync::<SendValue>();
+    }
+}
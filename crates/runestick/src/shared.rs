@@ -665,6 +665,14 @@ impl Shared<AnyObj> {
     }
 }
 
+impl<T: ?Sized> Shared<T> {
+    /// Get a pointer identifying the shared container, which is the same for
+    /// all clones of it.
+    pub(crate) fn as_ptr(&self) -> *const () {
+        self.inner.as_ptr() as *const ()
+    }
+}
+
 impl<T: ?Sized> Clone for Shared<T> {
     fn clone(&self) -> Self {
         unsafe {
@@ -2,21 +2,48 @@ use crate::{
     FromValue, GeneratorState, InstallWith, Mut, Named, RawMut, RawRef, RawStr, Ref, Shared,
     UnsafeFromValue, Value, Vm, VmError, VmErrorKind, VmExecution,
 };
+use futures_util::StreamExt as _;
 use std::fmt;
 use std::mem;
+use std::pin::Pin;
 
-/// A stream with a stored virtual machine.
+/// A native stream of values.
+type NativeStream = Pin<Box<dyn futures_core::Stream<Item = Result<Value, VmError>>>>;
+
+/// A stream with a stored virtual machine, or a native stream of values.
 pub struct Stream {
-    execution: Option<VmExecution>,
-    first: bool,
+    inner: Inner,
+}
+
+enum Inner {
+    Vm {
+        execution: Option<VmExecution>,
+        first: bool,
+    },
+    Native(Option<NativeStream>),
 }
 
 impl Stream {
     /// Construct a stream from a virtual machine.
     pub(crate) fn new(vm: Vm) -> Self {
         Self {
-            execution: Some(VmExecution::new(vm)),
-            first: true,
+            inner: Inner::Vm {
+                execution: Some(VmExecution::new(vm)),
+                first: true,
+            },
+        }
+    }
+
+    /// Construct a stream from a native stream of values, so that it can be
+    /// consumed like any other stream in Rune.
+    ///
+    /// Values passed in through [resume][Stream::resume] are ignored.
+    pub fn from_stream<S>(stream: S) -> Self
+    where
+        S: 'static + futures_core::Stream<Item = Result<Value, VmError>>,
+    {
+        Self {
+            inner: Inner::Native(Some(Box::pin(stream))),
         }
     }
 
@@ -30,29 +57,50 @@ impl Stream {
 
     /// Get the next value produced by this stream.
     pub async fn resume(&mut self, value: Value) -> Result<GeneratorState, VmError> {
-        let execution = self
-            .execution
-            .as_mut()
-            .ok_or_else(|| VmErrorKind::GeneratorComplete)?;
-
-        if !mem::take(&mut self.first) {
-            execution.vm_mut()?.stack_mut().push(value);
-        }
-
-        let state = execution.async_resume().await?;
-
-        if state.is_complete() {
-            self.execution = None;
+        match &mut self.inner {
+            Inner::Vm { execution, first } => {
+                let vm_execution = execution
+                    .as_mut()
+                    .ok_or_else(|| VmErrorKind::GeneratorComplete)?;
+
+                if !mem::take(first) {
+                    vm_execution.vm_mut()?.stack_mut().push(value);
+                }
+
+                let state = vm_execution.async_resume().await?;
+
+                if state.is_complete() {
+                    *execution = None;
+                }
+
+                Ok(state)
+            }
+            Inner::Native(stream) => {
+                let native = stream
+                    .as_mut()
+                    .ok_or_else(|| VmErrorKind::GeneratorComplete)?;
+
+                match native.next().await {
+                    Some(value) => Ok(GeneratorState::Yielded(value?)),
+                    None => {
+                        *stream = None;
+                        Ok(GeneratorState::Complete(Value::Unit))
+                    }
+                }
+            }
         }
-
-        Ok(state)
     }
 }
 
 impl fmt::Debug for Stream {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let completed = match &self.inner {
+            Inner::Vm { execution, .. } => execution.is_none(),
+            Inner::Native(stream) => stream.is_none(),
+        };
+
         f.debug_struct("Stream")
-            .field("completed", &self.execution.is_none())
+            .field("completed", &completed)
             .finish()
     }
 }
@@ -0,0 +1,147 @@
+use crate::{
+    FixedArgs, FromValue, Hash, IntoTypeHash, Unit, UnitFn, Vm, VmError, VmErrorKind, VmExecution,
+};
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// A handle to a function in a [Unit], which has been resolved up front to
+/// take the arguments `A` and produce a value which can be converted into `R`.
+///
+/// Calling through a handle avoids looking up the function every time, and
+/// the number of arguments is checked once when the handle is constructed.
+///
+/// See [Unit::function].
+pub struct TypedFunction<A, R> {
+    /// The unit the function was resolved in.
+    unit: Arc<Unit>,
+    /// The hash of the function.
+    hash: Hash,
+    /// The offset of the function.
+    offset: usize,
+    _marker: PhantomData<fn(A) -> R>,
+}
+
+impl<A, R> TypedFunction<A, R>
+where
+    A: FixedArgs,
+    R: FromValue,
+{
+    /// Resolve the function with the given name in the unit.
+    pub(crate) fn new<N>(unit: &Arc<Unit>, name: N) -> Result<Self, VmError>
+    where
+        N: IntoTypeHash,
+    {
+        let hash = name.into_type_hash();
+
+        let info = unit.lookup(hash).ok_or_else(|| {
+            VmError::from(VmErrorKind::MissingEntry {
+                hash,
+                item: name.into_item(),
+            })
+        })?;
+
+        let offset = match info {
+            UnitFn::Offset { offset, args, .. } => {
+                if args != A::COUNT {
+                    return Err(VmError::from(VmErrorKind::BadArgumentCount {
+                        actual: A::COUNT,
+                        expected: args,
+                    }));
+                }
+
+                offset
+            }
+            _ => {
+                return Err(VmError::from(VmErrorKind::MissingFunction { hash }));
+            }
+        };
+
+        Ok(Self {
+            unit: unit.clone(),
+            hash,
+            offset,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Get the hash of the function.
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    /// Call the function on the given virtual machine.
+    ///
+    /// The virtual machine must use the same unit as the one the function was
+    /// resolved in. It's cleared before the call, and can be used again
+    /// afterwards.
+    ///
+    /// If any async instructions are encountered, this will error.
+    pub fn call(&self, vm: &mut Vm, args: A) -> Result<R, VmError> {
+        let mut execution = self.execution(vm, args)?;
+        let result = execution.complete();
+        Self::restore(vm, execution);
+        R::from_value(result?)
+    }
+
+    /// Call the function asynchronously on the given virtual machine.
+    ///
+    /// The virtual machine must use the same unit as the one the function was
+    /// resolved in. It's cleared before the call, and can be used again
+    /// afterwards.
+    pub async fn async_call(&self, vm: &mut Vm, args: A) -> Result<R, VmError> {
+        let mut execution = self.execution(vm, args)?;
+        let result = execution.async_complete().await;
+        Self::restore(vm, execution);
+        R::from_value(result?)
+    }
+
+    /// Set up an execution of the function, temporarily taking the virtual
+    /// machine out of the given reference.
+    fn execution(&self, vm: &mut Vm, args: A) -> Result<VmExecution, VmError> {
+        if !Arc::ptr_eq(vm.unit(), &self.unit) {
+            return Err(VmError::from(VmErrorKind::IncompatibleUnit {
+                hash: self.hash,
+            }));
+        }
+
+        vm.clear();
+        vm.set_ip(self.offset);
+
+        if let Err(error) = args.into_stack(vm.stack_mut()) {
+            vm.clear();
+            return Err(error);
+        }
+
+        let placeholder = Vm::new(vm.context().clone(), vm.unit().clone());
+        Ok(VmExecution::new(std::mem::replace(vm, placeholder)))
+    }
+
+    /// Put the virtual machine an execution was started with back.
+    fn restore(vm: &mut Vm, execution: VmExecution) {
+        if let Some(mut done) = execution.into_vm() {
+            done.clear();
+            *vm = done;
+        }
+    }
+}
+
+impl<A, R> Clone for TypedFunction<A, R> {
+    fn clone(&self) -> Self {
+        Self {
+            unit: self.unit.clone(),
+            hash: self.hash,
+            offset: self.offset,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<A, R> fmt::Debug for TypedFunction<A, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedFunction")
+            .field("hash", &self.hash)
+            .field("offset", &self.offset)
+            .finish()
+    }
+}
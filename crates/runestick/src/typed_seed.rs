@@ -0,0 +1,274 @@
+use crate::{
+    Hash, Item, Object, Rtti, Struct, Tuple, TupleStruct, Unit, UnitFn, UnitStruct, Value, Variant,
+    VariantRtti,
+};
+use serde::de;
+use serde::Deserialize as _;
+use std::fmt;
+use std::sync::Arc;
+
+/// A [DeserializeSeed][de::DeserializeSeed] which deserializes a value as the
+/// named script type with the given hash declared in a [Unit].
+///
+/// Structs are expected to be represented as maps of their fields, tuple
+/// structs as sequences and unit structs as units. Enums use the externally
+/// tagged representation, which is the serde default and how [Value]
+/// serializes variants: unit variants are plain strings and every other
+/// variant is a single-entry map from the variant name to its content.
+///
+/// Only the outermost type is reconstructed. The content of fields is
+/// deserialized as untyped values, the same way as [Value] deserializes.
+///
+/// A [Unit] doesn't record the declared fields of structs and struct variants,
+/// so their keys are not validated. Missing or unknown fields are accepted and
+/// only surface as errors once a script tries to access them.
+#[derive(Clone, Copy)]
+pub struct TypedSeed<'a> {
+    unit: &'a Unit,
+    hash: Hash,
+}
+
+impl<'a> TypedSeed<'a> {
+    /// Construct a seed for the type with the given hash.
+    pub fn new(unit: &'a Unit, hash: Hash) -> Self {
+        Self { unit, hash }
+    }
+
+    /// Construct a seed for the type with the given item, like `Foo::Bar`.
+    pub fn from_item(unit: &'a Unit, item: &Item) -> Self {
+        Self::new(unit, Hash::type_hash(item))
+    }
+}
+
+impl<'de> de::DeserializeSeed<'de> for TypedSeed<'_> {
+    type Value = Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        if let Some(rtti) = self.unit.lookup_rtti(self.hash) {
+            return StructSeed {
+                rtti: rtti.clone(),
+                kind: self.unit.lookup(self.hash),
+            }
+            .deserialize(deserializer);
+        }
+
+        let variants = self
+            .unit
+            .iter_variant_rtti()
+            .filter(|rtti| rtti.enum_hash == self.hash)
+            .collect::<Vec<_>>();
+
+        if variants.is_empty() {
+            return Err(de::Error::custom(format!(
+                "missing runtime type information for type with hash `{}`",
+                self.hash
+            )));
+        }
+
+        deserializer.deserialize_any(EnumVisitor {
+            unit: self.unit,
+            variants,
+        })
+    }
+}
+
+/// Seed for a struct, tuple struct or unit struct.
+struct StructSeed {
+    rtti: Arc<Rtti>,
+    kind: Option<UnitFn>,
+}
+
+impl<'de> de::DeserializeSeed<'de> for StructSeed {
+    type Value = Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let rtti = self.rtti;
+
+        Ok(match self.kind {
+            Some(UnitFn::UnitStruct { .. }) => {
+                <()>::deserialize(deserializer)?;
+                Value::from(UnitStruct { rtti })
+            }
+            Some(UnitFn::TupleStruct { args, .. }) => {
+                let data = TupleSeed { args }.deserialize(deserializer)?;
+                Value::from(TupleStruct { rtti, data })
+            }
+            _ => {
+                let data = ObjectSeed.deserialize(deserializer)?;
+                Value::from(Struct { rtti, data })
+            }
+        })
+    }
+}
+
+/// Visitor for an externally tagged enum.
+struct EnumVisitor<'a> {
+    unit: &'a Unit,
+    variants: Vec<&'a Arc<VariantRtti>>,
+}
+
+impl<'a> EnumVisitor<'a> {
+    /// Find the variant with the given name.
+    fn find<E>(&self, name: &str) -> Result<(&'a Arc<VariantRtti>, Option<UnitFn>), E>
+    where
+        E: de::Error,
+    {
+        for rtti in &self.variants {
+            let matches = match rtti.item.last() {
+                Some(last) => last.to_string() == name,
+                None => false,
+            };
+
+            if matches {
+                return Ok((rtti, self.unit.lookup(rtti.hash)));
+            }
+        }
+
+        Err(E::custom(format!("unknown variant `{}`", name)))
+    }
+}
+
+impl<'de> de::Visitor<'de> for EnumVisitor<'_> {
+    type Value = Value;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("a variant name or a map with a single variant")
+    }
+
+    fn visit_str<E>(self, name: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        match self.find(name)? {
+            (rtti, Some(UnitFn::UnitVariant { .. })) => {
+                Ok(Value::from(Variant::unit(rtti.clone())))
+            }
+            _ => Err(E::custom(format!(
+                "variant `{}` is not a unit variant",
+                name
+            ))),
+        }
+    }
+
+    fn visit_map<M>(self, mut map: M) -> Result<Self::Value, M::Error>
+    where
+        M: de::MapAccess<'de>,
+    {
+        let name = match map.next_key::<String>()? {
+            Some(name) => name,
+            None => return Err(de::Error::invalid_length(0, &self)),
+        };
+
+        let variant = match self.find(&name)? {
+            (rtti, Some(UnitFn::UnitVariant { .. })) => {
+                map.next_value::<()>()?;
+                Variant::unit(rtti.clone())
+            }
+            (rtti, Some(UnitFn::TupleVariant { args: 1, .. })) => {
+                let value = map.next_value::<Value>()?;
+                Variant::tuple(rtti.clone(), Tuple::from(vec![value]))
+            }
+            (rtti, Some(UnitFn::TupleVariant { args, .. })) => {
+                let data = map.next_value_seed(TupleSeed { args })?;
+                Variant::tuple(rtti.clone(), data)
+            }
+            (rtti, _) => {
+                let data = map.next_value_seed(ObjectSeed)?;
+                Variant::struct_(rtti.clone(), data)
+            }
+        };
+
+        if map.next_key::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(2, &self));
+        }
+
+        Ok(Value::from(variant))
+    }
+}
+
+/// Seed and visitor for a tuple with a fixed number of elements.
+#[derive(Clone, Copy)]
+struct TupleSeed {
+    args: usize,
+}
+
+impl<'de> de::DeserializeSeed<'de> for TupleSeed {
+    type Value = Tuple;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> de::Visitor<'de> for TupleSeed {
+    type Value = Tuple;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "a sequence of {} elements", self.args)
+    }
+
+    fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
+    where
+        S: de::SeqAccess<'de>,
+    {
+        let mut values = Vec::with_capacity(self.args);
+
+        while values.len() < self.args {
+            match seq.next_element::<Value>()? {
+                Some(value) => values.push(value),
+                None => return Err(de::Error::invalid_length(values.len(), &self)),
+            }
+        }
+
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(self.args + 1, &self));
+        }
+
+        Ok(Tuple::from(values))
+    }
+}
+
+/// Seed and visitor for the fields of a struct.
+#[derive(Clone, Copy)]
+struct ObjectSeed;
+
+impl<'de> de::DeserializeSeed<'de> for ObjectSeed {
+    type Value = Object;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> de::Visitor<'de> for ObjectSeed {
+    type Value = Object;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("a map of fields")
+    }
+
+    fn visit_map<M>(self, mut map: M) -> Result<Self::Value, M::Error>
+    where
+        M: de::MapAccess<'de>,
+    {
+        let mut object = Object::with_capacity(map.size_hint().unwrap_or_default());
+
+        while let Some((key, value)) = map.next_entry::<String, Value>()? {
+            object.insert(key, value);
+        }
+
+        Ok(object)
+    }
+}
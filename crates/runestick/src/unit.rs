@@ -5,7 +5,8 @@
 
 use crate::collections::HashMap;
 use crate::{
-    Call, ConstValue, DebugInfo, Hash, Inst, Rtti, StaticString, VariantRtti, VmError, VmErrorKind,
+    Call, ConstValue, DebugInfo, FixedArgs, FromValue, Hash, Inst, IntoTypeHash, Rtti,
+    StaticString, TypedFunction, VariantRtti, VmError, VmErrorKind,
 };
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -134,11 +135,57 @@ impl Unit {
         self.variant_rtti.get(&hash)
     }
 
+    /// Iterate over all runtime type information in the unit.
+    pub fn iter_rtti(&self) -> impl Iterator<Item = &Arc<Rtti>> + '_ {
+        self.rtti.values()
+    }
+
+    /// Iterate over all variant runtime information in the unit.
+    pub fn iter_variant_rtti(&self) -> impl Iterator<Item = &Arc<VariantRtti>> + '_ {
+        self.variant_rtti.values()
+    }
+
     /// Lookup information of a function.
     pub fn lookup(&self, hash: Hash) -> Option<UnitFn> {
         self.functions.get(&hash).copied()
     }
 
+    /// Resolve a function in the unit into a typed handle, which takes the
+    /// arguments `A` and produces a value which is converted into `R`.
+    ///
+    /// This errors if the function doesn't exist, or if it takes a different
+    /// number of arguments than `A` provides.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use runestick::{Context, Unit, Vm};
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() -> runestick::Result<()> {
+    /// let context = Arc::new(Context::with_default_modules()?.runtime());
+    /// // Normally the unit would be created by compiling some source.
+    /// let unit = Arc::new(Unit::default());
+    ///
+    /// let add = unit.function::<(i64, i64), i64>(&["add"])?;
+    /// let mut vm = Vm::new(context, unit);
+    ///
+    /// for n in 0..10 {
+    ///     println!("{}", add.call(&mut vm, (n, 1))?);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn function<A, R>(
+        self: &Arc<Self>,
+        name: impl IntoTypeHash,
+    ) -> Result<TypedFunction<A, R>, VmError>
+    where
+        A: FixedArgs,
+        R: FromValue,
+    {
+        TypedFunction::new(self, name)
+    }
+
     /// Read a constant value from the unit.
     pub fn constant(&self, hash: Hash) -> Option<&ConstValue> {
         self.constants.get(&hash)
@@ -146,7 +193,7 @@ impl Unit {
 }
 
 /// The kind and necessary information on registered functions.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum UnitFn {
     /// Offset to call a "real" function.
     Offset {
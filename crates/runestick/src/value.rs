@@ -3,7 +3,7 @@ use crate::protocol_caller::{EnvProtocolCaller, ProtocolCaller};
 use crate::{
     Any, AnyObj, Bytes, ConstValue, Format, Function, Future, Generator, GeneratorState, Hash,
     Item, Iterator, Mut, Object, Protocol, Range, RawMut, RawRef, Ref, Shared, StaticString,
-    Stream, Tuple, TypeInfo, Variant, Vec, Vm, VmError, VmErrorKind,
+    Stream, Tuple, TypeInfo, Variant, VariantData, Vec, Vm, VmError, VmErrorKind,
 };
 use serde::{de, ser, Deserialize, Serialize};
 use std::cmp;
@@ -1305,23 +1305,67 @@ impl ser::Serialize for Value {
             }
             Value::Object(object) => {
                 let object = object.borrow_ref().map_err(ser::Error::custom)?;
-                let mut serializer = serializer.serialize_map(Some(object.len()))?;
-
-                for (key, value) in &*object {
-                    serializer.serialize_entry(key, value)?;
-                }
-
-                serializer.end()
+                SerializeObject(&object).serialize(serializer)
             }
             Value::Option(option) => {
                 let option = option.borrow_ref().map_err(ser::Error::custom)?;
                 <Option<Value>>::serialize(&*option, serializer)
             }
+            Value::Result(result) => {
+                let result = result.borrow_ref().map_err(ser::Error::custom)?;
+                let mut serializer = serializer.serialize_map(Some(1))?;
+
+                match &*result {
+                    Ok(value) => serializer.serialize_entry("Ok", value)?,
+                    Err(value) => serializer.serialize_entry("Err", value)?,
+                }
+
+                serializer.end()
+            }
             Value::UnitStruct(..) => serializer.serialize_unit(),
-            Value::TupleStruct(..) => Err(ser::Error::custom("cannot serialize tuple structs")),
-            Value::Struct(..) => Err(ser::Error::custom("cannot serialize objects structs")),
-            Value::Variant(..) => Err(ser::Error::custom("cannot serialize variants")),
-            Value::Result(..) => Err(ser::Error::custom("cannot serialize results")),
+            Value::TupleStruct(tuple) => {
+                let tuple = tuple.borrow_ref().map_err(ser::Error::custom)?;
+                let mut serializer = serializer.serialize_seq(Some(tuple.data.len()))?;
+
+                for value in tuple.data.iter() {
+                    serializer.serialize_element(value)?;
+                }
+
+                serializer.end()
+            }
+            Value::Struct(object) => {
+                let object = object.borrow_ref().map_err(ser::Error::custom)?;
+                SerializeObject(&object.data).serialize(serializer)
+            }
+            Value::Variant(variant) => {
+                let variant = variant.borrow_ref().map_err(ser::Error::custom)?;
+
+                // Variants are externally tagged, which is serde's default
+                // representation for enums.
+                let name = match variant.rtti.item.last() {
+                    Some(name) => name.to_string(),
+                    None => return Err(ser::Error::custom("cannot serialize unnamed variant")),
+                };
+
+                match &variant.data {
+                    VariantData::Unit => serializer.serialize_str(&name),
+                    VariantData::Tuple(tuple) => {
+                        let mut serializer = serializer.serialize_map(Some(1))?;
+
+                        match &tuple[..] {
+                            [value] => serializer.serialize_entry(&name, value)?,
+                            values => serializer.serialize_entry(&name, values)?,
+                        }
+
+                        serializer.end()
+                    }
+                    VariantData::Struct(object) => {
+                        let mut serializer = serializer.serialize_map(Some(1))?;
+                        serializer.serialize_entry(&name, &SerializeObject(object))?;
+                        serializer.end()
+                    }
+                }
+            }
             Value::Type(..) => Err(ser::Error::custom("cannot serialize types")),
             Value::Future(..) => Err(ser::Error::custom("cannot serialize futures")),
             Value::Stream(..) => Err(ser::Error::custom("cannot serialize streams")),
@@ -1338,6 +1382,26 @@ impl ser::Serialize for Value {
     }
 }
 
+/// Helper to serialize the fields of an object as a map.
+struct SerializeObject<'a>(&'a Object);
+
+impl ser::Serialize for SerializeObject<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        use serde::ser::SerializeMap as _;
+
+        let mut serializer = serializer.serialize_map(Some(self.0.len()))?;
+
+        for (key, value) in self.0 {
+            serializer.serialize_entry(key, value)?;
+        }
+
+        serializer.end()
+    }
+}
+
 struct VmVisitor;
 
 impl<'de> de::Visitor<'de> for VmVisitor {
@@ -9,6 +9,7 @@ use crate::{
     Unit, UnitStruct, Value, Variant, VariantData, Vec, VmError, VmErrorKind, VmExecution, VmHalt,
     VmIntegerRepr, VmSendExecution,
 };
+use std::cmp;
 use std::fmt;
 use std::mem;
 use std::sync::Arc;
@@ -161,6 +162,21 @@ impl Vm {
         self.ip = self.ip.overflowing_add(1).0;
     }
 
+    /// Replace the unit used by this virtual machine, returning the old unit.
+    ///
+    /// This can only be done at a safe point, where the virtual machine isn't
+    /// in the middle of an execution, since instruction offsets are not stable
+    /// across units. This is the case for a freshly constructed or cleared
+    /// virtual machine, and one whose execution has completed.
+    pub fn swap_unit(&mut self, unit: Arc<Unit>) -> Result<Arc<Unit>, VmError> {
+        if !self.stack.is_empty() || !self.call_frames.is_empty() {
+            return Err(VmError::from(VmErrorKind::NotAtSafePoint));
+        }
+
+        self.ip = 0;
+        Ok(std::mem::replace(&mut self.unit, unit))
+    }
+
     /// Reset this virtual machine, freeing all memory used.
     pub fn clear(&mut self) {
         self.ip = 0;
@@ -458,6 +474,7 @@ impl Vm {
         &mut self,
         int_op: fn(i64, i64) -> bool,
         float_op: fn(f64, f64) -> bool,
+        ordering_op: fn(cmp::Ordering) -> bool,
         op: &'static str,
         lhs: InstAddress,
         rhs: InstAddress,
@@ -469,11 +486,15 @@ impl Vm {
             (Value::Integer(lhs), Value::Integer(rhs)) => int_op(lhs, rhs),
             (Value::Float(lhs), Value::Float(rhs)) => float_op(lhs, rhs),
             (lhs, rhs) => {
-                return Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
-                    op,
-                    lhs: lhs.type_info()?,
-                    rhs: rhs.type_info()?,
-                }))
+                if !self.call_instance_fn(lhs.clone(), Protocol::CMP, (&rhs,))? {
+                    return Err(VmError::from(VmErrorKind::UnsupportedBinaryOperation {
+                        op,
+                        lhs: lhs.type_info()?,
+                        rhs: rhs.type_info()?,
+                    }));
+                }
+
+                ordering_op(cmp::Ordering::from_value(self.stack.pop()?)?)
             }
         };
 
@@ -1713,16 +1734,44 @@ impl Vm {
                 self.internal_infallible_bitwise(Protocol::SHR, std::ops::Shr::shr, lhs, rhs)?;
             }
             InstOp::Gt => {
-                self.internal_boolean_ops(|a, b| a > b, |a, b| a > b, ">", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a > b,
+                    |a, b| a > b,
+                    cmp::Ordering::is_gt,
+                    ">",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Gte => {
-                self.internal_boolean_ops(|a, b| a >= b, |a, b| a >= b, ">=", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a >= b,
+                    |a, b| a >= b,
+                    cmp::Ordering::is_ge,
+                    ">=",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Lt => {
-                self.internal_boolean_ops(|a, b| a < b, |a, b| a < b, "<", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a < b,
+                    |a, b| a < b,
+                    cmp::Ordering::is_lt,
+                    "<",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Lte => {
-                self.internal_boolean_ops(|a, b| a <= b, |a, b| a <= b, "<=", lhs, rhs)?;
+                self.internal_boolean_ops(
+                    |a, b| a <= b,
+                    |a, b| a <= b,
+                    cmp::Ordering::is_le,
+                    "<=",
+                    lhs,
+                    rhs,
+                )?;
             }
             InstOp::Eq => {
                 let rhs = self.stack.address(rhs)?;
@@ -318,6 +318,14 @@ pub enum VmErrorKind {
     ExpectedVariant { actual: TypeInfo },
     #[error("{actual} can't be converted to a constant value")]
     ConstNotSupported { actual: TypeInfo },
+    #[error("{actual} can't be sent across threads")]
+    SendNotSupported { actual: TypeInfo },
+    #[error("a value which contains itself can't be sent across threads")]
+    SendCycle,
+    #[error("the unit of a virtual machine can only be replaced when it's not executing")]
+    NotAtSafePoint,
+    #[error("function `{hash}` was resolved in a different unit than the one used by the virtual machine")]
+    IncompatibleUnit { hash: Hash },
     #[error("{actual} can't be converted to a hash key")]
     KeyNotSupported { actual: TypeInfo },
     #[error("missing interface environment")]
@@ -6,12 +6,31 @@ use std::future::Future;
 /// The execution environment for a virtual machine.
 pub struct VmExecution {
     vms: Vec<Vm>,
+    /// The virtual machine the execution was started with, once the execution
+    /// has completed.
+    completed: Option<Vm>,
 }
 
 impl VmExecution {
     /// Construct an execution from a virtual machine.
     pub(crate) fn new(vm: Vm) -> Self {
-        Self { vms: vec![vm] }
+        Self {
+            vms: vec![vm],
+            completed: None,
+        }
+    }
+
+    /// Convert the execution back into the virtual machine it was started
+    /// with, so that it can be reused.
+    ///
+    /// The virtual machine is not cleared, so if the execution didn't
+    /// complete it will still hold on to its state. Use [Vm::clear] before
+    /// reusing it.
+    pub fn into_vm(self) -> Option<Vm> {
+        match self.completed {
+            Some(vm) => Some(vm),
+            None => self.vms.into_iter().next(),
+        }
     }
 
     /// Get the current virtual machine.
@@ -82,7 +101,7 @@ impl VmExecution {
             if len == 1 {
                 let value = vm.stack_mut().pop()?;
                 debug_assert!(vm.stack().is_empty(), "the final vm should be empty");
-                self.vms.clear();
+                self.completed = self.vms.pop();
                 return Ok(GeneratorState::Complete(value));
             }
 
@@ -115,7 +134,7 @@ impl VmExecution {
             if len == 1 {
                 let value = vm.stack_mut().pop()?;
                 debug_assert!(vm.stack().is_empty(), "the final vm should be empty");
-                self.vms.clear();
+                self.completed = self.vms.pop();
                 return Ok(GeneratorState::Complete(value));
             }
 
@@ -16,6 +16,7 @@ use std::sync::Arc;
 use thiserror::Error;
 
 pub mod capture_output;
+pub mod temp_dir;
 
 /// Macro internals.
 #[doc(hidden)]
@@ -0,0 +1,37 @@
+//! Temporary directories which are removed once tests are done with them.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A temporary directory which is removed when dropped, even if the test
+/// using it fails.
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// Create a new unique temporary directory, using the given name as a
+    /// prefix.
+    pub fn new(name: &str) -> io::Result<Self> {
+        let id = COUNTER.fetch_add(1, Ordering::SeqCst);
+        let path =
+            std::env::temp_dir().join(format!("rune-{}-{}-{}", name, std::process::id(), id));
+        fs::create_dir_all(&path)?;
+        Ok(Self { path })
+    }
+
+    /// The path of the directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
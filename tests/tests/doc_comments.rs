@@ -0,0 +1,156 @@
+use rune_tests::*;
+use rune::{CompileVisitor, Diagnostics, FileSourceLoader, Options, Sources};
+use runestick::{Context, Item, Source, SourceId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+#[derive(Default)]
+struct DocVisitor {
+    docs: RefCell<HashMap<String, Vec<String>>>,
+}
+
+impl CompileVisitor for DocVisitor {
+    fn visit_doc_comment(&self, _: SourceId, item: &Item, docs: &[String]) {
+        self.docs
+            .borrow_mut()
+            .insert(item.to_string(), docs.to_vec());
+    }
+
+    fn visit_field_doc_comment(&self, _: SourceId, item: &Item, field: &str, docs: &[String]) {
+        self.docs
+            .borrow_mut()
+            .insert(format!("{}.{}", item, field), docs.to_vec());
+    }
+}
+
+fn collect_docs(source: &str) -> HashMap<String, Vec<String>> {
+    let context = Context::with_default_modules().unwrap();
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source));
+
+    let visitor = Rc::new(DocVisitor::default());
+    let mut diagnostics = Diagnostics::new();
+
+    rune::load_sources_with_visitor(
+        &context,
+        &Options::default(),
+        &mut sources,
+        &mut diagnostics,
+        visitor.clone(),
+        Rc::new(FileSourceLoader::new()),
+    )
+    .expect("source to compile");
+
+    let docs = visitor.docs.borrow().clone();
+    docs
+}
+
+#[test]
+fn test_doc_comments() {
+    let docs = collect_docs(
+        r#"
+        //! The crate.
+
+        /// A person.
+        ///
+        /// With a name.
+        struct Person {
+            /// The name.
+            name,
+            age,
+        }
+
+        impl Person {
+            /// Construct a person.
+            fn new(name) {
+                Person { name, age: 0 }
+            }
+        }
+
+        /// Colors.
+        enum Color {
+            /// Red.
+            Red,
+            Custom {
+                /// The hex code.
+                hex,
+            },
+        }
+
+        //// Not a doc comment.
+        fn plain() {
+        }
+
+        /// The answer.
+        const ANSWER = 42;
+
+        #[doc = "Attribute docs."]
+        fn main() {
+            /// Ignored on locals.
+            let a = 1;
+            a
+        }
+        "#,
+    );
+
+    assert_eq!(docs.get("{root}"), Some(&vec![String::from(" The crate.")]));
+    assert_eq!(
+        docs.get("Person"),
+        Some(&vec![
+            String::from(" A person."),
+            String::new(),
+            String::from(" With a name.")
+        ])
+    );
+    assert_eq!(docs.get("Person.name"), Some(&vec![String::from(" The name.")]));
+    assert_eq!(docs.get("Person.age"), None);
+    assert_eq!(
+        docs.get("Person::new"),
+        Some(&vec![String::from(" Construct a person.")])
+    );
+    assert_eq!(docs.get("Color"), Some(&vec![String::from(" Colors.")]));
+    assert_eq!(docs.get("Color::Red"), Some(&vec![String::from(" Red.")]));
+    assert_eq!(
+        docs.get("Color::Custom.hex"),
+        Some(&vec![String::from(" The hex code.")])
+    );
+    assert_eq!(docs.get("plain"), None);
+    assert_eq!(docs.get("ANSWER"), Some(&vec![String::from(" The answer.")]));
+    assert_eq!(
+        docs.get("main"),
+        Some(&vec![String::from("Attribute docs.")])
+    );
+}
+
+#[test]
+fn test_doc_comments_documenting_nothing() {
+    let out = rune_s! { i64 => r#"
+        fn foo() { 1 }
+
+        pub fn main() {
+            /// Ignored on expressions.
+            foo();
+
+            let a = if false {
+                1
+            } /// Ignored before else.
+            else {
+                2
+            };
+
+            let o = #{
+                /// Ignored on object keys.
+                b: 3,
+            };
+
+            /// Ignored on the last expression.
+            foo() + a + o.b
+            /// Ignored at the end of a block.
+        }
+
+        /// Ignored at the end of the file.
+    "#};
+
+    assert_eq!(out, 6);
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "futures-executor")]
+
+use rune_tests::*;
+use runestick::{Any, Module};
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+#[derive(Any, Debug, Default)]
+#[rune(install_with = "Counter::install_exports")]
+struct Counter {
+    #[rune(get, set, copy)]
+    count: i64,
+    #[rune(get)]
+    label: String,
+}
+
+#[runestick::export]
+impl Counter {
+    #[rune]
+    fn new(label: &str) -> Self {
+        Self {
+            count: 0,
+            label: label.to_owned(),
+        }
+    }
+
+    #[rune(name = "inc")]
+    fn increment(&mut self, amount: i64) {
+        self.count += amount;
+    }
+
+    #[rune]
+    async fn doubled(&self) -> i64 {
+        self.count * 2
+    }
+
+    #[rune(protocol = "STRING_DISPLAY")]
+    fn display(&self, buf: &mut String) -> std::fmt::Result {
+        write!(buf, "{}={}", self.label, self.count)
+    }
+
+    #[allow(unused)]
+    fn hidden(&self) -> i64 {
+        self.count
+    }
+}
+
+#[test]
+fn test_export_impl() -> runestick::Result<()> {
+    let mut module = Module::new();
+    module.ty::<Counter>()?;
+
+    let mut context = runestick::Context::with_default_modules()?;
+    context.install(&module)?;
+    let context = Arc::new(context);
+
+    let vm = vm_with_source(
+        &context,
+        r#"
+        pub async fn main() {
+            let counter = Counter::new("hits");
+            counter.inc(2);
+            counter.count = counter.count + 3;
+            (counter.count, counter.label, counter.doubled().await, `${counter}`)
+        }
+        "#,
+    )?;
+
+    let output = futures_executor::block_on(vm.execute(&["main"], ())?.async_complete())?;
+    let output: (i64, String, i64, String) = runestick::FromValue::from_value(output)?;
+
+    assert_eq!(
+        output,
+        (5, String::from("hits"), 10, String::from("hits=5"))
+    );
+
+    let vm = vm_with_source(
+        &context,
+        r#"
+        pub fn main() {
+            Counter::new("x").hidden()
+        }
+        "#,
+    )?;
+
+    assert!(vm.execute(&["main"], ())?.complete().is_err());
+    Ok(())
+}
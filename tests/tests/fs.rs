@@ -0,0 +1,79 @@
+#![cfg(feature = "tokio")]
+
+use rune_tests::*;
+use std::sync::Arc;
+
+#[test]
+fn test_fs() -> runestick::Result<()> {
+    let temp = temp_dir::TempDir::new("test-fs")?;
+    let dir = temp.path().to_string_lossy().into_owned();
+
+    let context = Arc::new(rune_modules::default_context()?);
+
+    let vm = vm_with_source(
+        &context,
+        r#"
+        pub async fn main(dir) {
+            match run(dir).await {
+                Ok(output) => Ok(output),
+                Err(error) => Err(`${error}`),
+            }
+        }
+
+        async fn run(dir) {
+            fs::create_dir_all(`${dir}/sub`).await?;
+            fs::write(`${dir}/a.txt`, "first\n").await?;
+            fs::append(`${dir}/a.txt`, b"second\r\n").await?;
+            fs::copy(`${dir}/a.txt`, `${dir}/sub/b.txt`).await?;
+            fs::rename(`${dir}/sub/b.txt`, `${dir}/sub/c.txt`).await?;
+
+            let lines = [];
+            let file = fs::File::open(`${dir}/sub/c.txt`).await?;
+
+            while let Some(line) = file.read_line().await? {
+                lines.push(line);
+            }
+
+            let names = [];
+            let entries = fs::read_dir(dir).await?;
+
+            while let Some(entry) = entries.next().await {
+                names.push(entry?.file_name());
+            }
+
+            let size = fs::metadata(`${dir}/a.txt`).await?.len();
+            let is_dir = fs::metadata(`${dir}/sub`).await?.is_dir();
+            fs::remove_file(`${dir}/a.txt`).await?;
+
+            let missing = match fs::read(`${dir}/a.txt`).await {
+                Err(error) => error.kind(),
+                Ok(..) => "found",
+            };
+
+            fs::remove_dir_all(dir).await?;
+            Ok((lines, names, size, is_dir, missing))
+        }
+        "#,
+    )?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    let output = runtime.block_on(async move {
+        vm.execute(&["main"], (dir,))?.async_complete().await
+    })?;
+
+    let output: Result<(Vec<String>, Vec<String>, i64, bool, String), String> =
+        runestick::FromValue::from_value(output)?;
+
+    let (lines, mut names, size, is_dir, missing) = output.expect("script to succeed");
+    names.sort();
+
+    assert_eq!(lines, vec!["first", "second"]);
+    assert_eq!(names, vec!["a.txt", "sub"]);
+    assert_eq!(size, 14);
+    assert!(is_dir);
+    assert_eq!(missing, "NotFound");
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use runestick::{Any, Context, ContextError, ContextSignature, Item, Module, Value};
+
+#[derive(Any)]
+struct Counter {
+    value: i64,
+}
+
+impl Counter {
+    fn add(&mut self, amount: i64) {
+        self.value += amount;
+    }
+}
+
+#[test]
+fn test_function_meta() -> runestick::Result<()> {
+    let mut module = Module::default();
+    module.ty::<Counter>()?;
+
+    module
+        .function(&["add"], |a: i64, b: i64| a + b)?
+        .docs(&["Add two numbers.", "", "Overflow panics."])
+        .arg::<i64>("a")
+        .arg::<i64>("b")
+        .returns::<i64>();
+
+    module
+        .inst_fn("add", Counter::add)?
+        .docs(&["Add to the counter."])
+        .arg::<i64>("amount");
+
+    module.function(&["plain"], |a: i64| a)?;
+    module
+        .function(&["dynamic"], |a: Value, b: &str| (a, b.len()))?
+        .untyped_arg("a")
+        .arg::<str>("b");
+
+    let mut context = Context::new();
+    context.install(&module)?;
+
+    let add = find(&context, |s| {
+        matches!(s, ContextSignature::Function { item, .. } if *item == Item::with_item(&["add"]))
+    });
+
+    let meta = add.meta();
+    assert_eq!(meta.docs, vec!["Add two numbers.", "", "Overflow panics."]);
+    assert_eq!(
+        meta.args.iter().map(|a| a.name.as_str()).collect::<Vec<_>>(),
+        vec!["a", "b"]
+    );
+    assert_eq!(add.to_string(), "add(a: integer, b: integer) -> integer");
+
+    let inst = find(&context, |s| {
+        matches!(s, ContextSignature::Instance { name, .. } if name == "add")
+    });
+
+    assert_eq!(inst.meta().docs, vec!["Add to the counter."]);
+    assert_eq!(inst.to_string(), "Counter::add(self: Counter, amount: integer)");
+
+    let plain = find(&context, |s| {
+        matches!(s, ContextSignature::Function { item, .. } if *item == Item::with_item(&["plain"]))
+    });
+
+    assert!(plain.meta().docs.is_empty());
+    assert_eq!(plain.to_string(), "plain(#0)");
+
+    // Arguments which accept any value don't have type information.
+    let dynamic = find(&context, |s| {
+        matches!(s, ContextSignature::Function { item, .. } if *item == Item::with_item(&["dynamic"]))
+    });
+
+    assert_eq!(dynamic.to_string(), "dynamic(a, b: String)");
+    Ok(())
+}
+
+#[test]
+fn test_function_meta_mismatched_args() -> runestick::Result<()> {
+    let mut module = Module::default();
+
+    module
+        .function(&["add"], |a: i64, b: i64| a + b)?
+        .arg::<i64>("a");
+
+    let mut context = Context::new();
+    let error = context.install(&module).unwrap_err();
+
+    assert!(matches!(
+        error,
+        ContextError::MismatchedFunctionArgs {
+            actual: 1,
+            expected: 2,
+            ..
+        }
+    ));
+
+    Ok(())
+}
+
+fn find<F>(context: &Context, f: F) -> &ContextSignature
+where
+    F: Fn(&ContextSignature) -> bool,
+{
+    context
+        .iter_functions()
+        .map(|(_, s)| s)
+        .find(|s| f(s))
+        .expect("missing function")
+}
@@ -0,0 +1,99 @@
+use rune::{ChangeKind, Runtime};
+use runestick::{Context, Function, Item, Source};
+use std::fs;
+
+#[test]
+fn test_hot_reload() -> runestick::Result<()> {
+    let dir = std::env::temp_dir().join(format!("rune-hot-reload-{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let path = dir.join("main.rn");
+
+    fs::write(
+        &path,
+        r#"
+        struct Point(x, y);
+        pub fn callback() { |a| a + 1 }
+        pub fn get() { answer }
+        pub fn answer() { 1 }
+        pub fn removed() { 0 }
+        "#,
+    )?;
+
+    let mut runtime = Runtime::new(Context::with_default_modules()?);
+    runtime.insert(Source::from_path(&path)?);
+    runtime.build()?;
+
+    let callback = runtime.call::<Function, _, _>(&["callback"], ())?;
+    let answer = runtime.call::<Function, _, _>(&["get"], ())?;
+    let mut vm = runtime.vm();
+
+    assert!(!runtime.refresh()?);
+
+    fs::write(
+        &path,
+        r#"
+        struct Point(x, y, z);
+        pub fn callback() { |a| a + 10 }
+        pub fn get() { answer }
+        pub fn answer() { 2 }
+        pub fn added(a) { a }
+        "#,
+    )?;
+
+    assert!(runtime.refresh()?);
+    let reload = runtime.reload()?;
+
+    let functions = reload
+        .functions
+        .iter()
+        .map(|change| (change.item.clone(), change.kind))
+        .collect::<Vec<_>>();
+
+    assert_eq!(functions.len(), 2);
+    assert!(functions.contains(&(Some(Item::with_item(&["added"])), ChangeKind::Added)));
+    assert!(functions.contains(&(Some(Item::with_item(&["removed"])), ChangeKind::Removed)));
+
+    assert_eq!(reload.types.len(), 1);
+    assert_eq!(reload.types[0].item, Some(Item::with_item(&["Point"])));
+    assert_eq!(reload.types[0].kind, ChangeKind::Changed);
+
+    // Old functions keep running the old unit until they're rebound.
+    assert_eq!(answer.call::<_, i64>(())?, 1);
+    assert_eq!(callback.call::<_, i64>((1,))?, 2);
+
+    let answer = runtime.rebind(&answer).expect("answer should still resolve");
+    let callback = runtime.rebind(&callback).expect("closure should still resolve");
+    assert_eq!(answer.call::<_, i64>(())?, 2);
+    assert_eq!(callback.call::<_, i64>((1,))?, 11);
+
+    runtime.migrate(&mut vm)?;
+    assert!(vm.is_same(runtime.runtime_context(), runtime.unit()));
+    assert_eq!(vm.call(&["added"], (42,))?.into_integer()?, 42);
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_rebind_changed_signature() -> runestick::Result<()> {
+    let mut runtime = Runtime::new(Context::with_default_modules()?);
+    runtime.insert(Source::new(
+        "entry",
+        "pub fn get() { add } pub fn add(a, b) { a + b }",
+    ));
+    runtime.build()?;
+
+    let add = runtime.call::<Function, _, _>(&["get"], ())?;
+
+    *runtime.sources_mut() = rune::Sources::new();
+    runtime.insert(Source::new(
+        "entry",
+        "pub fn get() { add } pub fn add(a, b, c) { a + b + c }",
+    ));
+
+    let reload = runtime.reload()?;
+    assert_eq!(reload.functions.len(), 1);
+    assert_eq!(reload.functions[0].kind, ChangeKind::Changed);
+    assert!(runtime.rebind(&add).is_none());
+    Ok(())
+}
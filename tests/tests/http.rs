@@ -0,0 +1,218 @@
+#![cfg(feature = "tokio")]
+
+use rune_tests::*;
+use std::io::{BufRead as _, BufReader, Read as _, Write as _};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Spawn a mock server which echoes the request line, interesting headers and
+/// the body of each request back in its response.
+fn mock_server() -> std::io::Result<String> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            if let Ok(stream) = stream {
+                thread::spawn(move || handle(stream));
+            }
+        }
+    });
+
+    Ok(format!("http://{}", addr))
+}
+
+fn handle(stream: TcpStream) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut stream = stream;
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_owned();
+    let path = parts.next().unwrap_or_default().to_owned();
+
+    let mut echo = format!("{} {}\n", method, path);
+    let mut length = 0;
+
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let line = line.trim_end();
+
+        if line.is_empty() {
+            break;
+        }
+
+        let (name, value) = line.split_once(": ").unwrap_or((line, ""));
+        let name = name.to_ascii_lowercase();
+
+        match name.as_str() {
+            "content-length" => length = value.parse().unwrap_or_default(),
+            "authorization" | "content-type" | "x-custom" => {
+                echo.push_str(&format!("{}: {}\n", name, value));
+            }
+            _ => (),
+        }
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    echo.push_str(&String::from_utf8_lossy(&body));
+
+    if path == "/slow" {
+        thread::sleep(Duration::from_millis(500));
+    }
+
+    let status = if path == "/missing" {
+        "404 Not Found"
+    } else {
+        "200 OK"
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\ncontent-length: {}\r\nx-test: a\r\nx-test: b\r\nconnection: close\r\n\r\n",
+        status,
+        echo.len()
+    )?;
+
+    if method != "HEAD" {
+        stream.write_all(echo.as_bytes())?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_http_client() -> runestick::Result<()> {
+    let url = mock_server()?;
+    let context = Arc::new(rune_modules::default_context()?);
+
+    let vm = vm_with_source(
+        &context,
+        r#"
+        pub async fn main(url) {
+            let client = http::Client::new();
+
+            let get = client.get(`${url}/get`).await?
+                .query(#{"a": "1", "b": "two"})
+                .header("x-custom", "yes")
+                .send().await?
+                .text().await?;
+
+            let put = client.put(`${url}/put`).await?
+                .bearer_auth("token")
+                .json(#{"hello": "world"})
+                .send().await?
+                .text().await?;
+
+            let post = client.post(`${url}/post`).await?
+                .basic_auth("user", Some("pass"))
+                .form(#{"key": "value"})
+                .send().await?
+                .text().await?;
+
+            let delete = client.delete(`${url}/delete`).await?.send().await?.text().await?;
+            let patch = client.patch(`${url}/patch`).await?.body_string("patched").send().await?.text().await?;
+
+            let head = client.head(`${url}/head`).await?.send().await?;
+            let head = (head.status().as_u16(), head.header("x-test"), head.headers()["x-test"]);
+
+            let options = client.request("options", `${url}/options`).await?.send().await?.text().await?;
+
+            let chunks = [];
+            let stream = client.get(`${url}/stream`).await?.send().await?.bytes_stream();
+
+            while let Some(chunk) = stream.next().await {
+                chunks.push(chunk?);
+            }
+
+            let missing = client.get(`${url}/missing`).await?.send().await?;
+            let missing = match missing.error_for_status() {
+                Err(error) => (error.kind(), error.status().map(|s| s.as_u16())),
+                Ok(..) => ("Ok", None),
+            };
+
+            let timeout = match client.get(`${url}/slow`).await?.timeout(0.05).send().await {
+                Err(error) => error.kind(),
+                Ok(..) => "Ok",
+            };
+
+            Ok((get, put, post, delete, patch, head, options, chunks.len() > 0, missing, timeout))
+        }
+        "#,
+    )?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    let output = runtime.block_on(async move { vm.execute(&["main"], (url,))?.async_complete().await })?;
+
+    let output: Result<
+        (
+            String,
+            String,
+            String,
+            String,
+            String,
+            (i64, Option<String>, String),
+            String,
+            bool,
+            (String, Option<i64>),
+            String,
+        ),
+        String,
+    > = runestick::FromValue::from_value(output)?;
+
+    let (get, put, post, delete, patch, head, options, streamed, missing, timeout) =
+        output.expect("script to succeed");
+
+    assert_eq!(get, "GET /get?a=1&b=two\nx-custom: yes\n");
+    assert_eq!(
+        put,
+        "PUT /put\nauthorization: Bearer token\ncontent-type: application/json\n{\"hello\":\"world\"}"
+    );
+    assert_eq!(
+        post,
+        "POST /post\nauthorization: Basic dXNlcjpwYXNz\ncontent-type: application/x-www-form-urlencoded\nkey=value"
+    );
+    assert_eq!(delete, "DELETE /delete\n");
+    assert_eq!(patch, "PATCH /patch\npatched");
+    assert_eq!(head, (200, Some(String::from("a, b")), String::from("a, b")));
+    assert_eq!(options, "OPTIONS /options\n");
+    assert!(streamed);
+    assert_eq!(missing, (String::from("Status"), Some(404)));
+    assert_eq!(timeout, "Timeout");
+    Ok(())
+}
+
+#[test]
+fn test_http_timeout_out_of_range() -> runestick::Result<()> {
+    let context = Arc::new(rune_modules::default_context()?);
+
+    let vm = vm_with_source(
+        &context,
+        r#"
+        pub async fn main() {
+            http::Client::new().get("http://127.0.0.1/").await?.timeout(1e20);
+        }
+        "#,
+    )?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    let error = runtime
+        .block_on(async move { vm.execute(&["main"], ())?.async_complete().await })
+        .unwrap_err();
+
+    assert!(error
+        .to_string()
+        .contains("cannot convert float seconds to Duration"));
+    Ok(())
+}
@@ -0,0 +1,145 @@
+#![cfg(feature = "tokio")]
+
+use rune_tests::*;
+use std::io::{Read as _, Write as _};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Send a raw request to the server and return the status code, the
+/// content-type and the body of the response.
+fn request(addr: &str, method: &str, path: &str, body: &str) -> (u16, String, String) {
+    let mut stream = loop {
+        match TcpStream::connect(addr) {
+            Ok(stream) => break stream,
+            Err(..) => thread::sleep(Duration::from_millis(10)),
+        }
+    };
+
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nhost: {}\r\nx-custom: yes\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+        method,
+        path,
+        addr,
+        body.len(),
+        body
+    )
+    .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let mut lines = head.lines();
+    let status = lines.next().unwrap().split(' ').nth(1).unwrap();
+
+    let content_type = lines
+        .filter_map(|line| line.split_once(": "))
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+        .map(|(_, value)| value.to_owned())
+        .unwrap_or_default();
+
+    (status.parse().unwrap(), content_type, body.to_owned())
+}
+
+/// Serve a script on the given runtime and test its responses.
+fn test_serve(runtime: tokio::runtime::Runtime) -> runestick::Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?.to_string();
+    let context = Arc::new(rune_modules::default_context()?);
+
+    let vm = vm_with_source(
+        &context,
+        r#"
+        use http_server::{Response, Server};
+
+        pub async fn main(listener) {
+            let server = Server::new();
+            server.get("/hello/:name", hello);
+            server.post("/echo", echo);
+            server.get("/files/*path", |request| request.param("path").unwrap());
+            server.get("/query", |request| Response::json(request.query()));
+            server.get("/fail", |request| Err("oops"));
+            server.route("*", "/any", |request| {
+                Response::new().status(201).header("content-type", request.header("x-custom").unwrap())
+            });
+            server.serve(listener).await
+        }
+
+        fn hello(request) {
+            `Hello ${request.param("name").unwrap()}!`
+        }
+
+        async fn echo(request) {
+            let body = request.json()?;
+            Response::json(#{"method": request.method(), "path": request.path(), "body": body})
+        }
+        "#,
+    )?;
+
+    let listener = rune_modules::http_server::Listener::from_std(listener);
+    let execution = vm.send_execute(&["main"], (listener,))?;
+
+    runtime.block_on(async move {
+        tokio::spawn(async move {
+            let _ = execution.async_complete().await;
+        });
+
+        let responses = tokio::task::spawn_blocking(move || {
+            vec![
+                request(&addr, "GET", "/hello/world", ""),
+                request(&addr, "POST", "/echo", "[1, 2]"),
+                request(&addr, "GET", "/files/a/b/c.txt", ""),
+                request(&addr, "GET", "/query?a=1&b=two", ""),
+                request(&addr, "GET", "/fail", ""),
+                request(&addr, "DELETE", "/any", ""),
+                request(&addr, "POST", "/hello/world", ""),
+                request(&addr, "GET", "/missing", ""),
+            ]
+        })
+        .await
+        .unwrap();
+
+        let text = "text/plain; charset=utf-8";
+        let json = "application/json";
+
+        assert_eq!(responses[0], (200, text.into(), "Hello world!".into()));
+        assert_eq!(
+            responses[1],
+            (
+                200,
+                json.into(),
+                r#"{"body":[1,2],"method":"POST","path":"/echo"}"#.into()
+            )
+        );
+        assert_eq!(responses[2], (200, text.into(), "a/b/c.txt".into()));
+        assert_eq!(responses[3], (200, json.into(), r#"{"a":"1","b":"two"}"#.into()));
+        assert_eq!(responses[4].0, 500);
+        assert_eq!(responses[5], (201, "yes".into(), "".into()));
+        assert_eq!(responses[6], (405, text.into(), "method not allowed".into()));
+        assert_eq!(responses[7], (404, text.into(), "not found".into()));
+    });
+
+    Ok(())
+}
+
+#[test]
+fn test_http_server() -> runestick::Result<()> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    test_serve(runtime)
+}
+
+#[test]
+fn test_http_server_multi_thread() -> runestick::Result<()> {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(4)
+        .enable_all()
+        .build()?;
+
+    test_serve(runtime)
+}
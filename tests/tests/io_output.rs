@@ -0,0 +1,33 @@
+use rune_tests::*;
+use runestick::modules::io::{self, CaptureOutput};
+use std::sync::Arc;
+
+#[test]
+fn test_capture_output() -> runestick::Result<()> {
+    let output = CaptureOutput::new();
+
+    let mut context = rune_modules::with_config(false)?;
+    context.install(&io::output_module(output.clone())?)?;
+    let context = Arc::new(context);
+
+    let vm = vm_with_source(
+        &context,
+        r#"
+        pub fn main() {
+            print("a");
+            println("b");
+            dbg(1, "c");
+            eprint("d");
+            eprintln("e");
+            eprintln!("{} {}", 1, 2);
+        }
+        "#,
+    )?;
+
+    vm.execute(&["main"], ())?.complete()?;
+
+    assert_eq!(output.drain_stdout(), b"ab\n1\n\"c\"\n");
+    assert_eq!(output.drain_stderr(), b"de\n1 2\n");
+    assert!(output.drain_stdout().is_empty());
+    Ok(())
+}
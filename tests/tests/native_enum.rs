@@ -0,0 +1,112 @@
+use rune_tests::*;
+use runestick::{FromValue, Module, NativeEnum, ToValue};
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq, NativeEnum, ToValue, FromValue)]
+#[rune(item = "geometry::Shape")]
+enum Shape {
+    Circle(i64),
+    Line(i64, i64),
+    Rect { w: i64, h: i64 },
+    Empty,
+}
+
+fn area(shape: Shape) -> i64 {
+    match shape {
+        Shape::Circle(r) => 3 * r * r,
+        Shape::Line(..) => 0,
+        Shape::Rect { w, h } => w * h,
+        Shape::Empty => 0,
+    }
+}
+
+#[test]
+fn test_native_enum() -> runestick::Result<()> {
+    let mut module = Module::with_item(&["geometry"]);
+    module.native_enum::<Shape>()?;
+    module.function(&["area"], area)?;
+
+    let mut context = runestick::Context::with_default_modules()?;
+    context.install(&module)?;
+    let context = Arc::new(context);
+
+    let vm = vm_with_source(
+        &context,
+        r#"
+        use geometry::Shape;
+
+        fn describe(shape) {
+            match shape {
+                Shape::Circle(r) => `circle ${r}`,
+                Shape::Line(a, b) => `line ${a} ${b}`,
+                Shape::Rect { w, h } => `rect ${w}x${h}`,
+                Shape::Empty => "empty",
+                _ => "unknown",
+            }
+        }
+
+        pub fn main(from_rust) {
+            let shapes = [Shape::Circle(2), Shape::Line(1, 2), Shape::Rect { h: 3, w: 4 }, Shape::Empty];
+            let circle = Shape::Circle;
+
+            (
+                shapes.iter().map(describe).collect_vec(),
+                describe(from_rust),
+                shapes.iter().map(geometry::area).collect_vec(),
+                shapes[0] is Shape,
+                describe(circle(5)),
+                Shape::Rect { w: 1, h: 2 },
+            )
+        }
+        "#,
+    )?;
+
+    let output = vm.execute(&["main"], (Shape::Line(7, 8),))?.complete()?;
+    let output: (Vec<String>, String, Vec<i64>, bool, String, Shape) = FromValue::from_value(output)?;
+
+    assert_eq!(
+        output,
+        (
+            vec![
+                String::from("circle 2"),
+                String::from("line 1 2"),
+                String::from("rect 4x3"),
+                String::from("empty"),
+            ],
+            String::from("line 7 8"),
+            vec![12, 0, 12, 0],
+            true,
+            String::from("circle 5"),
+            Shape::Rect { w: 1, h: 2 },
+        )
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_native_enum_builder() -> runestick::Result<()> {
+    let mut module = Module::new();
+    module.enum_(&["Light"]).unit("Red").unit("Green");
+
+    let mut context = runestick::Context::with_default_modules()?;
+    context.install(&module)?;
+    let context = Arc::new(context);
+
+    let vm = vm_with_source(
+        &context,
+        r#"
+        pub fn main() {
+            match Light::Red { Light::Green => "go", Light::Red => "stop", _ => "unknown" }
+        }
+        "#,
+    )?;
+
+    let output: String = FromValue::from_value(vm.execute(&["main"], ())?.complete()?)?;
+    assert_eq!(output, "stop");
+
+    let mut module = Module::new();
+    module.enum_(&["Light"]).unit("Red").unit("Red");
+    assert!(runestick::Context::new().install(&module).is_err());
+    Ok(())
+}
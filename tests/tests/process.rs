@@ -0,0 +1,138 @@
+#![cfg(all(feature = "tokio", unix))]
+
+use rune_tests::*;
+use std::sync::Arc;
+
+#[test]
+fn test_process() -> runestick::Result<()> {
+    let dir = std::env::temp_dir().canonicalize()?;
+    let dir = dir.to_string_lossy().into_owned();
+    let expected = dir.clone();
+
+    let context = Arc::new(rune_modules::default_context()?);
+
+    let vm = vm_with_source(
+        &context,
+        r#"
+        use process::{Command, Stdio};
+
+        pub async fn main(dir) {
+            let command = Command::new("/bin/sh");
+            command.args(["-c", "read line; echo \"got $line\"; echo \"$FOO\"; pwd; echo oops >&2"]);
+            command.env("FOO", "bar");
+            command.current_dir(dir);
+            command.stdin(Stdio::piped());
+            command.stdout(Stdio::piped());
+            command.stderr(Stdio::piped());
+
+            let child = command.spawn()?;
+
+            let stdin = child.stdin().unwrap();
+            stdin.write("hello\n").await?;
+            stdin.close();
+
+            let out = [];
+            let lines = child.stdout_lines().unwrap();
+
+            while let Some(line) = lines.next().await {
+                out.push(line?);
+            }
+
+            let err = [];
+            let lines = child.stderr_lines().unwrap();
+
+            while let Some(line) = lines.next().await {
+                err.push(line?);
+            }
+
+            let status = child.wait().await?;
+            Ok((out, err, status.success(), status.code()))
+        }
+        "#,
+    )?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    let output = runtime.block_on(async move {
+        vm.execute(&["main"], (expected,))?
+            .async_complete()
+            .await
+    })?;
+
+    let output: Result<(Vec<String>, Vec<String>, bool, Option<i64>), runestick::Value> =
+        runestick::FromValue::from_value(output)?;
+    let (out, err, success, code) = output.expect("script to succeed");
+
+    assert_eq!(out, vec![String::from("got hello"), String::from("bar"), dir]);
+    assert_eq!(err, vec![String::from("oops")]);
+    assert!(success);
+    assert_eq!(code, Some(0));
+    Ok(())
+}
+
+#[test]
+fn test_process_timeout_and_kill() -> runestick::Result<()> {
+    let context = Arc::new(rune_modules::default_context()?);
+
+    let vm = vm_with_source(
+        &context,
+        r#"
+        use process::Command;
+
+        pub async fn main() {
+            let command = Command::new("sleep");
+            command.arg("10");
+
+            let child = command.spawn()?;
+            let timed_out = child.wait_timeout(0.05).await?.is_none();
+            child.kill().await?;
+            let status = child.wait().await?;
+            Ok((timed_out, status.success(), status.code(), status.signal()))
+        }
+        "#,
+    )?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    let output = runtime.block_on(async move { vm.execute(&["main"], ())?.async_complete().await })?;
+
+    let output: Result<(bool, bool, Option<i64>, Option<i64>), runestick::Value> =
+        runestick::FromValue::from_value(output)?;
+
+    assert_eq!(output.expect("script to succeed"), (true, false, None, Some(9)));
+    Ok(())
+}
+
+#[test]
+fn test_process_timeout_out_of_range() -> runestick::Result<()> {
+    let context = Arc::new(rune_modules::default_context()?);
+
+    let vm = vm_with_source(
+        &context,
+        r#"
+        use process::Command;
+
+        pub async fn main() {
+            let child = Command::new("true").spawn()?;
+            child.wait_timeout(1e20).await
+        }
+        "#,
+    )?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    let error = runtime
+        .block_on(async move { vm.execute(&["main"], ())?.async_complete().await })
+        .unwrap_err();
+
+    assert!(error
+        .to_string()
+        .contains("cannot convert float seconds to Duration"));
+    Ok(())
+}
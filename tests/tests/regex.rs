@@ -0,0 +1,81 @@
+use rune_tests::*;
+use std::sync::Arc;
+
+#[test]
+fn test_regex() -> runestick::Result<()> {
+    let context = Arc::new(rune_modules::default_context()?);
+
+    let vm = vm_with_source(
+        &context,
+        r#"
+        use regex::Regex;
+
+        pub fn main() {
+            let re = Regex::new("(?P<year>\\d{4})-(?P<month>\\d{2})(-(\\d{2}))?")?;
+            let text = "from 2020-01 to 2021-02-03";
+
+            let is_match = (re.is_match(text), re.is_match("nothing"));
+
+            let first = re.find(text).unwrap();
+            let first = (first.start(), first.end(), first.as_str());
+
+            let all = re.find_iter(text).map(|m| m.as_str()).collect_vec();
+
+            let captures = re.captures(text).unwrap();
+            let captures = (captures["year"], captures["month"], captures["0"], captures.get("4"));
+
+            let days = re.captures_iter(text).map(|c| c.get("4")).collect_vec();
+
+            let replaced = re.replace_all(text, "$month/$year");
+            let replaced_first = re.replace(text, |c| `<${c["year"]}>`);
+            let replaced_fn = re.replace_all(text, |c| `<${c["month"]}>`);
+
+            let split = Regex::new("\\s*,\\s*")?.split("a , b,c");
+            let invalid = match Regex::new("(") { Err(..) => true, Ok(..) => false };
+
+            Ok((is_match, first, all, captures, days, replaced, replaced_first, replaced_fn, split, invalid))
+        }
+        "#,
+    )?;
+
+    let output = vm.execute(&["main"], ())?.complete()?;
+
+    let output: Result<
+        (
+            (bool, bool),
+            (usize, usize, String),
+            Vec<String>,
+            (String, String, String, Option<String>),
+            Vec<Option<String>>,
+            String,
+            String,
+            String,
+            Vec<String>,
+            bool,
+        ),
+        runestick::Value,
+    > = runestick::FromValue::from_value(output)?;
+
+    let (is_match, first, all, captures, days, replaced, replaced_first, replaced_fn, split, invalid) =
+        output.expect("script to succeed");
+
+    assert_eq!(is_match, (true, false));
+    assert_eq!(first, (5, 12, String::from("2020-01")));
+    assert_eq!(all, vec![String::from("2020-01"), String::from("2021-02-03")]);
+    assert_eq!(
+        captures,
+        (
+            String::from("2020"),
+            String::from("01"),
+            String::from("2020-01"),
+            None
+        )
+    );
+    assert_eq!(days, vec![None, Some(String::from("03"))]);
+    assert_eq!(replaced, "from 01/2020 to 02/2021");
+    assert_eq!(replaced_first, "from <2020> to 2021-02-03");
+    assert_eq!(replaced_fn, "from <01> to <02>");
+    assert_eq!(split, vec!["a", "b", "c"]);
+    assert!(invalid);
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use rune::Runtime;
+use runestick::{Context, Source, VmErrorKind};
+
+#[test]
+fn test_runtime_call() -> runestick::Result<()> {
+    let mut runtime = Runtime::new(Context::with_default_modules()?);
+    runtime.insert(Source::new(
+        "entry",
+        r#"
+        pub fn add(a, b) { a + b }
+        pub fn greet(name) { `Hello ${name}` }
+        "#,
+    ));
+    runtime.build()?;
+
+    assert_eq!(runtime.call::<i64, _, _>(&["add"], (1, 2))?, 3);
+    assert_eq!(runtime.call::<i64, _, _>(&["add"], (3, 4))?, 7);
+
+    let output = runtime.call::<String, _, _>(&["greet"], (String::from("World"),))?;
+    assert_eq!(output, "Hello World");
+
+    let error = runtime.call::<i64, _, _>(&["missing"], ()).unwrap_err();
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::MissingEntry { .. }
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_runtime_pool() -> runestick::Result<()> {
+    let mut runtime = Runtime::new(Context::with_default_modules()?);
+    runtime.insert(Source::new("entry", "pub fn main() { 1 }"));
+    runtime.build()?;
+
+    let vm = runtime.vm();
+    assert!(vm.is_same(runtime.runtime_context(), runtime.unit()));
+    runtime.release(vm);
+
+    assert_eq!(runtime.call::<i64, _, _>(&["main"], ())?, 1);
+
+    // Pooled virtual machines are cleared.
+    let vm = runtime.vm();
+    assert!(vm.stack().is_empty());
+    assert!(vm.call_frames().is_empty());
+
+    // Virtual machines for an old unit are not reused after a rebuild.
+    runtime.build()?;
+    runtime.release(vm);
+    assert!(runtime
+        .vm()
+        .is_same(runtime.runtime_context(), runtime.unit()));
+    Ok(())
+}
+
+#[test]
+fn test_runtime_diagnostics() -> runestick::Result<()> {
+    let mut runtime = Runtime::new(Context::with_default_modules()?);
+    runtime.insert(Source::new("entry", "pub fn main() { 1 }"));
+    runtime.build()?;
+    assert!(runtime.diagnostics().is_empty());
+
+    runtime
+        .sources_mut()
+        .insert(Source::new("broken", "pub fn broken( {"));
+    assert!(runtime.build().is_err());
+    assert!(runtime.diagnostics().has_error());
+
+    // The last successful unit is kept around, together with its sources.
+    assert_eq!(runtime.call::<i64, _, _>(&["main"], ())?, 1);
+    assert!(runtime.loaded_sources().get(0).is_some());
+    assert!(runtime.loaded_sources().get(1).is_none());
+
+    let mut buffer = rune::termcolor::Buffer::no_color();
+    runtime.emit_diagnostics(&mut buffer)?;
+    let output = String::from_utf8(buffer.into_inner()).unwrap();
+    assert!(output.contains("broken"));
+    Ok(())
+}
@@ -0,0 +1,135 @@
+#![cfg(feature = "tokio")]
+
+use rune_modules::{fs, http, process};
+use rune_tests::*;
+use std::sync::Arc;
+
+#[test]
+fn test_sandbox() -> runestick::Result<()> {
+    let dir = std::env::temp_dir().join(format!("rune-test-sandbox-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("data"))?;
+    std::fs::create_dir_all(dir.join("out"))?;
+    std::fs::write(dir.join("data").join("a.txt"), "hello")?;
+
+    let fs_policy = fs::Policy::new()
+        .allow(dir.join("data"), fs::Access::Read)
+        .allow(dir.join("out"), fs::Access::Write);
+
+    let process_policy = process::Policy::new().allow_command("rune-test-command");
+
+    let http_policy = http::Policy::new()
+        .allow_host("example.com")
+        .allow_method("GET");
+
+    let mut context = runestick::Context::with_default_modules()?;
+    context.install(&fs::module_with_policy(fs_policy)?)?;
+    context.install(&process::module_with_policy(process_policy)?)?;
+    context.install(&http::module_with_policy(http_policy)?)?;
+    let context = Arc::new(context);
+
+    let vm = vm_with_source(
+        &context,
+        r#"
+        fn denied(result) {
+            match result {
+                Err(error) => error.is_denied(),
+                Ok(..) => false,
+            }
+        }
+
+        pub async fn main(dir) {
+            let read = fs::read_to_string(`${dir}/data/a.txt`).await.is_ok();
+            let written = fs::write(`${dir}/out/b.txt`, "world").await.is_ok();
+
+            let kind = match fs::write(`${dir}/data/b.txt`, "world").await {
+                Err(error) => error.kind(),
+                Ok(..) => "written",
+            };
+
+            let fs_denied = [
+                denied(fs::read_to_string(`${dir}/out/b.txt`).await),
+                denied(fs::read_to_string(`${dir}/data/../out/b.txt`).await),
+                denied(fs::read_dir(dir).await),
+                denied(fs::copy(`${dir}/data/a.txt`, `${dir}/data/c.txt`).await),
+                denied(fs::File::create(`${dir}/c.txt`).await),
+            ];
+
+            let process_denied = denied(process::Command::new("ls").spawn());
+
+            let http_denied = [
+                denied(http::get("http://example.org").await),
+                denied(http::Client::new().post("http://example.com").await),
+            ];
+
+            (read, written, kind, fs_denied, process_denied, http_denied)
+        }
+        "#,
+    )?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    let path = dir.to_string_lossy().into_owned();
+
+    let output = runtime.block_on(async move {
+        vm.execute(&["main"], (path,))?.async_complete().await
+    })?;
+
+    std::fs::remove_dir_all(&dir)?;
+
+    let (read, written, kind, fs_denied, process_denied, http_denied): (
+        bool,
+        bool,
+        String,
+        Vec<bool>,
+        bool,
+        Vec<bool>,
+    ) = runestick::FromValue::from_value(output)?;
+
+    assert!(read);
+    assert!(written);
+    assert_eq!(kind, "Denied");
+    assert_eq!(fs_denied, vec![true; 5]);
+    assert!(process_denied);
+    assert_eq!(http_denied, vec![true; 2]);
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_sandbox_process_env() -> runestick::Result<()> {
+    std::env::set_var("RUNE_TEST_SANDBOX_ALLOWED", "allowed");
+    std::env::set_var("RUNE_TEST_SANDBOX_DENIED", "denied");
+
+    let policy = process::Policy::new()
+        .allow_command("/bin/sh")
+        .allow_env("RUNE_TEST_SANDBOX_ALLOWED");
+
+    let mut context = runestick::Context::with_default_modules()?;
+    context.install(&process::module_with_policy(policy)?)?;
+    let context = Arc::new(context);
+
+    let vm = vm_with_source(
+        &context,
+        r#"
+        pub async fn main() {
+            let command = process::Command::new("/bin/sh");
+            command.arg("-c");
+            command.arg("test \"$RUNE_TEST_SANDBOX_ALLOWED\" = allowed && test -z \"$RUNE_TEST_SANDBOX_DENIED\"");
+            let output = command.spawn()?.wait_with_output().await?;
+            Ok(output.status.code())
+        }
+        "#,
+    )?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    let output = runtime.block_on(async move { vm.execute(&["main"], ())?.async_complete().await })?;
+
+    let output: Result<Option<i64>, runestick::Value> = runestick::FromValue::from_value(output)?;
+    assert_eq!(output.expect("command to succeed"), Some(0));
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use rune_tests::*;
+use runestick::{FromValue as _, SendValue, VmErrorKind};
+use std::sync::Arc;
+
+const SOURCE: &str = r#"
+struct Point { x, y }
+enum Shape { Circle(radius), Rect { w, h }, Empty }
+
+pub fn produce() {
+    [Point { x: 1, y: 2 }, Shape::Circle(3), Shape::Rect { w: 4, h: 5 }, Shape::Empty, (Some("six"), Ok(7))]
+}
+
+pub fn consume(values) {
+    let out = [];
+
+    for value in values {
+        match value {
+            Point { x, y } => out.push(x + y),
+            Shape::Circle(r) => out.push(r),
+            Shape::Rect { w, h } => out.push(w * h),
+            Shape::Empty => out.push(0),
+            (Some(s), Ok(r)) => out.push(`${s} ${r}`),
+        }
+    }
+
+    (out[0], out[1], out[2], out[3], out[4])
+}
+
+pub fn future() {
+    async { 1 }
+}
+
+pub fn cyclic() {
+    let v = [];
+    v.push(Some(v));
+    v
+}
+
+pub fn shared() {
+    let v = [1];
+    [v, v]
+}
+"#;
+
+#[test]
+fn test_send_value_between_threads() -> runestick::Result<()> {
+    let context = Arc::new(rune_modules::default_context()?);
+    let vm = vm_with_source(&context, SOURCE).unwrap();
+
+    let values = vm.call(&["produce"], ())?;
+    let send = SendValue::from_value(values.clone())?;
+
+    // The original value is left intact by the conversion.
+    assert!(SendValue::from_value(values).is_ok());
+
+    let output = std::thread::spawn(move || {
+        let context = Arc::new(rune_modules::default_context().unwrap());
+        let vm = vm_with_source(&context, SOURCE).unwrap();
+        let output = vm.call(&["consume"], (send,)).unwrap();
+        SendValue::from_value(output).unwrap()
+    })
+    .join()
+    .unwrap();
+
+    let output: (i64, i64, i64, i64, String) =
+        runestick::FromValue::from_value(output.into_value())?;
+    assert_eq!(output, (3, 3, 20, 0, String::from("six 7")));
+    Ok(())
+}
+
+#[test]
+fn test_send_value_rejects_futures() -> runestick::Result<()> {
+    let context = Arc::new(rune_modules::default_context()?);
+    let vm = vm_with_source(&context, SOURCE).unwrap();
+
+    let future = vm.call(&["future"], ())?;
+    let error = SendValue::from_value(future).unwrap_err();
+
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::SendNotSupported { .. }
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_send_value_rejects_cycles() -> runestick::Result<()> {
+    let context = Arc::new(rune_modules::default_context()?);
+    let vm = vm_with_source(&context, SOURCE).unwrap();
+
+    let cyclic = vm.clone().call(&["cyclic"], ())?;
+    let error = SendValue::from_value(cyclic).unwrap_err();
+    assert!(matches!(error.into_kind(), VmErrorKind::SendCycle));
+
+    // A value which is referenced more than once without containing itself
+    // is copied once for every reference.
+    let shared = vm.call(&["shared"], ())?;
+    let shared = SendValue::from_value(shared)?;
+    let shared: Vec<Vec<i64>> = runestick::FromValue::from_value(shared.into_value())?;
+    assert_eq!(shared, vec![vec![1], vec![1]]);
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use rune_tests::*;
+use runestick::{Item, TypedSeed, Value};
+use serde::de::DeserializeSeed as _;
+use std::sync::Arc;
+
+#[test]
+fn test_serialize_types() -> runestick::Result<()> {
+    let context = Arc::new(rune_modules::default_context()?);
+
+    let vm = vm_with_source(
+        &context,
+        r#"
+        struct Point { x, y }
+        struct Pair(a, b);
+        struct Empty;
+        enum Shape { Circle(radius), Line(a, b), Rect { w, h }, Nothing }
+
+        pub fn main() {
+            Ok([
+                json::to_string(Point { x: 1, y: [2, 3] })?,
+                json::to_string(Pair(1, "two"))?,
+                json::to_string(Empty)?,
+                json::to_string(Shape::Circle(1.5))?,
+                json::to_string(Shape::Line(1, 2))?,
+                json::to_string(Shape::Rect { w: 3, h: Some(4) })?,
+                json::to_string(Shape::Nothing)?,
+                json::to_string(Ok(1))?,
+                json::to_string(Err("bad"))?,
+            ])
+        }
+        "#,
+    )?;
+
+    let output = vm.execute(&["main"], ())?.complete()?;
+    let output: Result<Vec<String>, Value> = runestick::FromValue::from_value(output)?;
+
+    assert_eq!(
+        output.expect("script to succeed"),
+        vec![
+            r#"{"x":1,"y":[2,3]}"#,
+            r#"[1,"two"]"#,
+            r#"null"#,
+            r#"{"Circle":1.5}"#,
+            r#"{"Line":[1,2]}"#,
+            r#"{"Rect":{"h":4,"w":3}}"#,
+            r#""Nothing""#,
+            r#"{"Ok":1}"#,
+            r#"{"Err":"bad"}"#,
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_deserialize_typed() -> runestick::Result<()> {
+    let context = Arc::new(rune_modules::default_context()?);
+
+    let vm = vm_with_source(
+        &context,
+        r#"
+        struct Point { x, y }
+        struct Pair(a, b);
+        enum Shape { Circle(radius), Line(a, b), Rect { w, h }, Nothing }
+
+        pub fn describe(value) {
+            match value {
+                Point { x, y } => `point ${x} ${y}`,
+                Pair(a, b) => `pair ${a} ${b}`,
+                Shape::Circle(radius) => `circle ${radius}`,
+                Shape::Line(a, b) => `line ${a} ${b}`,
+                Shape::Rect { w, h } => `rect ${w} ${h}`,
+                Shape::Nothing => "nothing",
+                _ => "unknown",
+            }
+        }
+        "#,
+    )?;
+
+    let unit = vm.unit().clone();
+
+    let decode = |item: &[&str], json: &str| -> runestick::Result<Value> {
+        let seed = TypedSeed::from_item(&unit, &Item::with_item(item));
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        Ok(seed.deserialize(&mut deserializer)?)
+    };
+
+    let inputs = vec![
+        decode(&["Point"], r#"{"x": 1, "y": 2}"#)?,
+        decode(&["Pair"], r#"[1, "two"]"#)?,
+        decode(&["Shape"], r#"{"Circle": 3}"#)?,
+        decode(&["Shape"], r#"{"Line": [1, 2]}"#)?,
+        decode(&["Shape"], r#"{"Rect": {"w": 3, "h": 4}}"#)?,
+        decode(&["Shape"], r#""Nothing""#)?,
+    ];
+
+    let mut outputs = Vec::new();
+
+    for input in inputs {
+        let output = vm.clone().execute(&["describe"], (input,))?.complete()?;
+        outputs.push(runestick::FromValue::from_value(output)?);
+    }
+
+    let outputs: Vec<String> = outputs;
+
+    assert_eq!(
+        outputs,
+        vec![
+            "point 1 2",
+            "pair 1 two",
+            "circle 3",
+            "line 1 2",
+            "rect 3 4",
+            "nothing",
+        ]
+    );
+
+    assert!(decode(&["Shape"], r#"{"Square": 1}"#).is_err());
+    assert!(decode(&["Shape"], r#""Circle""#).is_err());
+    assert!(decode(&["Pair"], r#"[1, 2, 3]"#).is_err());
+    assert!(decode(&["Missing"], r#"null"#).is_err());
+
+    // Struct fields are not validated, so a missing field only shows up once
+    // the script tries to use it.
+    let partial = decode(&["Point"], r#"{"x": 1}"#)?;
+    let output = vm.clone().execute(&["describe"], (partial,))?.complete()?;
+    let output: String = runestick::FromValue::from_value(output)?;
+    assert_eq!(output, "unknown");
+    Ok(())
+}
@@ -0,0 +1,150 @@
+use rune_tests::*;
+
+#[test]
+fn test_string_search() {
+    assert_eq! {
+        rune! { (bool, bool, Option<usize>, Option<usize>, Option<usize>, Option<usize>) =>
+            pub fn main() {
+                let s = "héllo wörld";
+                (s.contains("wör"), s.contains('x'), s.find('l'), s.rfind("l"), s.find('ö'), s.find("x"))
+            }
+        },
+        (true, false, Some(3), Some(11), Some(8), None),
+    };
+
+    assert_eq! {
+        rune! { (Option<String>, Option<String>, Option<String>) =>
+            pub fn main() {
+                let s = "prefix-body.rn";
+                (s.strip_prefix("prefix-"), s.strip_suffix(".rn"), s.strip_prefix('x'))
+            }
+        },
+        (Some(String::from("body.rn")), Some(String::from("prefix-body")), None),
+    };
+}
+
+#[test]
+fn test_string_bad_pattern() {
+    assert_vm_error!(
+        r#"pub fn main() { "ab".contains(1) }"#,
+        BadArgumentAt { arg, .. } => {
+            assert_eq!(arg, 1);
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { "ab".splitn(2, 1) }"#,
+        BadArgumentAt { arg, .. } => {
+            assert_eq!(arg, 2);
+        }
+    );
+}
+
+#[test]
+fn test_string_splitting() {
+    assert_eq! {
+        rune! { (Vec<String>, Vec<String>, Vec<String>, Vec<String>) =>
+            pub fn main() {
+                let splitn = "a=b=c".splitn(2, '=').collect_vec();
+                let whitespace = "  a \t b\nc  ".split_whitespace().collect_vec();
+                let lines = "one\ntwo\r\nthree\n".lines().collect_vec();
+                let lines_rev = "one\ntwo".lines().rev().collect_vec();
+                (splitn, whitespace, lines, lines_rev)
+            }
+        },
+        (
+            vec![String::from("a"), String::from("b=c")],
+            vec![String::from("a"), String::from("b"), String::from("c")],
+            vec![String::from("one"), String::from("two"), String::from("three")],
+            vec![String::from("two"), String::from("one")],
+        ),
+    };
+}
+
+#[test]
+fn test_string_transforms() {
+    assert_eq! {
+        rune! { (String, String, String, String, String, String, String) =>
+            pub fn main() {
+                let s = "Straße";
+                (
+                    s.to_lowercase(),
+                    s.to_uppercase(),
+                    "ab".repeat(3),
+                    "  x  ".trim_start(),
+                    "ö".pad_start(3, '.'),
+                    "ö".pad_end(3, '-'),
+                    "ab".center(5, '*'),
+                )
+            }
+        },
+        (
+            String::from("straße"),
+            String::from("STRASSE"),
+            String::from("ababab"),
+            String::from("x  "),
+            String::from("..ö"),
+            String::from("ö--"),
+            String::from("*ab**"),
+        ),
+    };
+}
+
+#[test]
+fn test_string_transforms_overflow() {
+    assert_vm_error!(
+        r#"pub fn main() { "abc".repeat(9223372036854775807) }"#,
+        Overflow => {}
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { "ab".repeat(4611686018427387904) }"#,
+        Panic { reason } => {
+            assert!(reason.to_string().starts_with("memory allocation failed"));
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { "ab".pad_start(9223372036854775807, 'x') }"#,
+        Panic { reason } => {
+            assert!(reason.to_string().starts_with("memory allocation failed"));
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { "ab".center(9223372036854775807, 'ö') }"#,
+        Panic { reason } => {
+            assert!(reason.to_string().starts_with("memory allocation failed"));
+        }
+    );
+}
+
+#[test]
+fn test_string_iterators() {
+    assert_eq! {
+        rune! { (Vec<(usize, char)>, Vec<u8>) =>
+            pub fn main() {
+                ("aé b".char_indices().collect_vec(), "aé".bytes().collect_vec())
+            }
+        },
+        (vec![(0, 'a'), (1, 'é'), (3, ' '), (4, 'b')], vec![b'a', 0xc3, 0xa9]),
+    };
+}
+
+#[test]
+fn test_string_from_utf8() {
+    assert_eq! {
+        rune_s! { Result<(String, String, usize), String> => r#"
+            pub fn main() {
+                let ok = String::from_utf8(b"h\xc3\xa9")?;
+                let error = match String::from_utf8(b"ab\xff") { Err(error) => error, Ok(..) => return Err("ok") };
+                Ok((ok, `${error}`, error.valid_up_to()))
+            }
+        "#},
+        Ok((
+            String::from("hé"),
+            String::from("invalid utf-8 sequence of 1 bytes from index 2"),
+            2,
+        )),
+    };
+}
@@ -0,0 +1,218 @@
+#![cfg(feature = "tokio")]
+
+use rune_tests::*;
+use std::sync::Arc;
+
+#[test]
+fn test_time() -> runestick::Result<()> {
+    let context = Arc::new(rune_modules::default_context()?);
+
+    let vm = vm_with_source(
+        &context,
+        r#"
+        use time::{Duration, Instant, SystemTime};
+
+        pub async fn main() {
+            let a = Duration::from_millis(1500);
+            let b = Duration::from_secs(1) + Duration::from_micros(500000);
+            let c = Duration::from_millis(1000);
+            c += Duration::from_secs(1);
+
+            let durations = (
+                a == b,
+                a < c,
+                c >= a,
+                (c - a).as_millis(),
+                (a * 2).as_secs(),
+                (a / 3).as_millis(),
+                a.as_secs_f64(),
+                `${Duration::from_millis(1)}`,
+            );
+
+            let start = Instant::now();
+            time::sleep(Duration::from_millis(20)).await;
+            let elapsed = start.elapsed() >= Duration::from_millis(20);
+            let later = start + Duration::from_secs(1);
+            let instants = (elapsed, later > start, later.duration_since(start).as_secs());
+
+            let ticks = time::interval(Duration::from_millis(5));
+            ticks.next().await;
+            ticks.next().await;
+            let ticked = start.elapsed() >= Duration::from_millis(25);
+
+            let fast = time::timeout(async { 42 }, Duration::from_secs(1)).await;
+            let slow = time::timeout(time::sleep(Duration::from_secs(10)), Duration::from_millis(10)).await;
+            let slow = match slow { Err(error) => `${error}`, Ok(..) => "ok" };
+            let timeouts = (fast?, slow);
+
+            let parsed = SystemTime::parse_rfc3339("2021-03-01T12:30:00.250+01:00")?;
+            let epoch = SystemTime::unix_epoch();
+            let before = SystemTime::from_unix_timestamp(-60);
+
+            let system = (
+                parsed.to_rfc3339(),
+                parsed.unix_timestamp(),
+                parsed.unix_timestamp_millis(),
+                parsed.duration_since(epoch)?.as_secs(),
+                `${epoch + Duration::from_secs(86400)}`,
+                before.unix_timestamp(),
+                before < epoch,
+                SystemTime::now() > parsed,
+                SystemTime::parse_rfc3339("not a date").is_err(),
+            );
+
+            Ok((durations, instants, ticked, timeouts, system))
+        }
+        "#,
+    )?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    let output = runtime.block_on(async move { vm.execute(&["main"], ())?.async_complete().await })?;
+
+    type Output = (
+        (bool, bool, bool, u64, u64, u64, f64, String),
+        (bool, bool, u64),
+        bool,
+        (i64, String),
+        (String, i64, i64, u64, String, i64, bool, bool, bool),
+    );
+
+    let output: Result<Output, runestick::Value> = runestick::FromValue::from_value(output)?;
+    let (durations, instants, ticked, timeouts, system) = output.expect("script to succeed");
+
+    assert_eq!(
+        durations,
+        (true, true, true, 500, 3, 500, 1.5, String::from("1ms"))
+    );
+    assert_eq!(instants, (true, true, 1));
+    assert!(ticked);
+    assert_eq!(timeouts, (42, String::from("deadline has elapsed")));
+    assert_eq!(
+        system,
+        (
+            String::from("2021-03-01T11:30:00.250Z"),
+            1614598200,
+            1614598200250,
+            1614598200,
+            String::from("1970-01-02T00:00:00Z"),
+            -60,
+            true,
+            true,
+            true,
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn test_virtual_time() -> runestick::Result<()> {
+    let epoch = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1614598200);
+    let clock = rune_modules::time::Clock::virtual_at(epoch);
+    let context = Arc::new(rune_modules::with_clock(true, clock.clone())?);
+
+    let vm = vm_with_source(
+        &context,
+        r#"
+        use time::{Duration, Instant, SystemTime};
+
+        pub async fn main() {
+            let start = Instant::now();
+            let at_start = SystemTime::now().to_rfc3339();
+
+            time::sleep(Duration::from_secs(3600)).await;
+            let slept = start.elapsed().as_secs();
+
+            let ticks = time::interval(Duration::from_secs(60));
+            let count = 0;
+
+            while count < 5 {
+                ticks.next().await;
+                count += 1;
+            }
+
+            let ticked = start.elapsed().as_secs();
+            let timed_out = time::timeout(time::sleep(Duration::from_secs(120)), Duration::from_secs(30)).await.is_err();
+
+            time::advance(Duration::from_secs(10)).await;
+            let advanced = start.elapsed().as_secs();
+
+            (at_start, slept, ticked, timed_out, advanced, SystemTime::now().unix_timestamp())
+        }
+        "#,
+    )?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    let started = std::time::Instant::now();
+
+    let output = runtime.block_on(async move {
+        clock.start();
+        vm.execute(&["main"], ())?.async_complete().await
+    })?;
+
+    let output: (String, u64, u64, bool, u64, i64) = runestick::FromValue::from_value(output)?;
+
+    assert_eq!(
+        output,
+        (
+            String::from("2021-03-01T11:30:00Z"),
+            3600,
+            3840,
+            true,
+            3880,
+            1614598200 + 3880,
+        )
+    );
+
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    Ok(())
+}
+
+#[test]
+fn test_advance_system_clock() -> runestick::Result<()> {
+    let context = Arc::new(rune_modules::default_context()?);
+
+    let vm = vm_with_source(
+        &context,
+        r#"
+        pub async fn main() {
+            time::advance(time::Duration::from_secs(1)).await;
+        }
+        "#,
+    )?;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+
+    let error = runtime
+        .block_on(async move { vm.execute(&["main"], ())?.async_complete().await })
+        .unwrap_err();
+
+    assert!(error
+        .to_string()
+        .contains("time can only be advanced with a virtual clock"));
+    Ok(())
+}
+
+#[test]
+fn test_duration_from_secs_f64() {
+    assert_vm_error!(
+        r#"pub fn main() { time::Duration::from_secs_f64(1e20) }"#,
+        Panic { reason } => {
+            assert!(reason.to_string().starts_with("cannot convert float seconds to Duration"));
+        }
+    );
+
+    assert_vm_error!(
+        r#"pub fn main() { time::Duration::from_secs_f64(-1.0) }"#,
+        Panic { reason } => {
+            assert!(reason.to_string().starts_with("cannot convert float seconds to Duration"));
+        }
+    );
+}
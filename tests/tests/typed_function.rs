@@ -0,0 +1,69 @@
+use rune_tests::*;
+use runestick::VmErrorKind;
+use std::sync::Arc;
+
+#[test]
+fn test_typed_function() -> runestick::Result<()> {
+    let context = Arc::new(rune_modules::default_context()?);
+
+    let mut vm = vm_with_source(
+        &context,
+        r#"
+        pub fn add(a, b) { a + b }
+        pub fn greet(name) { `Hello ${name}` }
+        pub fn fail() { panic("boom") }
+        "#,
+    )
+    .unwrap();
+
+    let unit = vm.unit().clone();
+    let add = unit.function::<(i64, i64), i64>(&["add"])?;
+    let greet = unit.function::<(String,), String>(&["greet"])?;
+    let fail = unit.function::<(), ()>(&["fail"])?;
+
+    for n in 0..10 {
+        assert_eq!(add.call(&mut vm, (n, 1))?, n + 1);
+    }
+
+    assert_eq!(
+        greet.call(&mut vm, (String::from("World"),))?,
+        "Hello World"
+    );
+
+    // The virtual machine can be used again after an error.
+    assert!(fail.call(&mut vm, ()).is_err());
+    assert!(vm.stack().is_empty());
+    assert_eq!(add.call(&mut vm, (1, 2))?, 3);
+    Ok(())
+}
+
+#[test]
+fn test_typed_function_errors() -> runestick::Result<()> {
+    let context = Arc::new(rune_modules::default_context()?);
+    let source = "pub fn add(a, b) { a + b }";
+
+    let vm = vm_with_source(&context, source).unwrap();
+    let unit = vm.unit().clone();
+
+    let error = unit.function::<(i64,), i64>(&["add"]).unwrap_err();
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::BadArgumentCount {
+            actual: 1,
+            expected: 2
+        }
+    ));
+
+    let error = unit.function::<(), i64>(&["missing"]).unwrap_err();
+    assert!(matches!(error.into_kind(), VmErrorKind::MissingEntry { .. }));
+
+    let add = unit.function::<(i64, i64), i64>(&["add"])?;
+    let mut other = vm_with_source(&context, source).unwrap();
+    let error = add.call(&mut other, (1, 2)).unwrap_err();
+    assert!(matches!(
+        error.into_kind(),
+        VmErrorKind::IncompatibleUnit { .. }
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use rune_tests::*;
+use runestick::{FromValue, ToValue};
+use std::sync::Arc;
+
+#[derive(Debug, PartialEq, ToValue, FromValue)]
+enum Shape {
+    Circle(i64),
+    Line(i64, i64),
+    Rect { w: i64, h: i64 },
+    Nothing,
+}
+
+#[derive(Debug, PartialEq, ToValue, FromValue)]
+#[rune(item = "geometry::Direction")]
+enum Direction {
+    Up,
+    Down,
+}
+
+#[test]
+fn test_to_value_enum() -> runestick::Result<()> {
+    let context = Arc::new(runestick::Context::with_default_modules()?);
+
+    let vm = vm_with_source(
+        &context,
+        r#"
+        enum Shape { Circle(radius), Line(a, b), Rect { w, h }, Nothing }
+
+        mod geometry {
+            pub enum Direction { Up, Down }
+        }
+
+        pub fn shape(shape) {
+            match shape {
+                Shape::Circle(r) => Shape::Rect { w: r, h: r + 1 },
+                Shape::Line(a, b) => Shape::Circle(a + b),
+                Shape::Rect { w, h } => Shape::Line(w, h),
+                Shape::Nothing => Shape::Nothing,
+                _ => panic("unexpected value"),
+            }
+        }
+
+        pub fn flip(direction) {
+            match direction {
+                geometry::Direction::Up => geometry::Direction::Down,
+                geometry::Direction::Down => geometry::Direction::Up,
+                _ => panic("unexpected value"),
+            }
+        }
+        "#,
+    )?;
+
+    let call = |name: &str, value: Shape| -> runestick::Result<Shape> {
+        let output = vm.clone().execute(&[name], (value,))?.complete()?;
+        Ok(Shape::from_value(output)?)
+    };
+
+    assert_eq!(call("shape", Shape::Circle(2))?, Shape::Rect { w: 2, h: 3 });
+    assert_eq!(call("shape", Shape::Line(1, 2))?, Shape::Circle(3));
+    assert_eq!(call("shape", Shape::Rect { w: 4, h: 5 })?, Shape::Line(4, 5));
+    assert_eq!(call("shape", Shape::Nothing)?, Shape::Nothing);
+
+    let output = vm.clone().execute(&["flip"], (Direction::Up,))?.complete()?;
+    assert_eq!(Direction::from_value(output)?, Direction::Down);
+
+    let variant = match Direction::Down.to_value()? {
+        runestick::Value::Variant(variant) => variant,
+        actual => panic!("expected variant, got {:?}", actual),
+    };
+
+    assert_eq!(
+        variant.borrow_ref()?.rtti().item.to_string(),
+        "geometry::Direction::Down"
+    );
+    Ok(())
+}